@@ -0,0 +1,58 @@
+use super::resources::{AnimationClip, ClipKind, SheetKind};
+use bevy::prelude::*;
+
+/// Plays one clip of a sprite sheet. Spawn it next to a `Sprite` showing the sheet's
+/// image; the atlas is filled in when the animation starts.
+#[derive(Component, Debug, Clone)]
+pub struct SpriteAnimation {
+    pub sheet: SheetKind,
+    pub clip: ClipKind,
+    pub frame: usize,
+    pub timer: Timer,
+    pub finished: bool,
+}
+
+impl SpriteAnimation {
+    pub fn new(sheet: SheetKind, clip: ClipKind) -> Self {
+        let mut animation = Self {
+            sheet,
+            clip,
+            frame: 0,
+            timer: Timer::default(),
+            finished: false,
+        };
+        animation.restart();
+        animation
+    }
+
+    /// Switches to `clip` from its first frame, unless it is already playing.
+    pub fn play(&mut self, clip: ClipKind) {
+        if self.clip != clip {
+            self.clip = clip;
+            self.restart();
+        }
+    }
+
+    /// The clip being played, falling back to idle for clips the sheet lacks.
+    pub fn current(&self) -> AnimationClip {
+        self.sheet
+            .clip(self.clip)
+            .unwrap_or_else(|| self.sheet.clip(ClipKind::Idle).unwrap())
+    }
+
+    /// Atlas index of the frame being shown.
+    pub fn index(&self) -> usize {
+        self.current().first + self.frame
+    }
+
+    fn restart(&mut self) {
+        self.frame = 0;
+        self.finished = false;
+        self.timer = Timer::from_seconds(1.0 / self.current().fps, TimerMode::Repeating);
+    }
+}
+
+/// Despawns the entity once its one-shot clip has played, for effects that outlive
+/// the thing they show, like a collected star.
+#[derive(Component)]
+pub struct DespawnWhenFinished;
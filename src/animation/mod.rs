@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+use plugins::*;
+
+pub mod components;
+mod plugins;
+pub mod resources;
+mod systems;
+
+/// Flipbook animations played from the sprite sheets in `assets/sprites`.
+pub struct AnimationPlugin;
+impl Plugin for AnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(SpriteAnimationPlugin);
+    }
+}
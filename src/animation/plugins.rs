@@ -0,0 +1,21 @@
+use super::systems::animation::*;
+use crate::{AppState, game::states::SimulationState};
+use bevy::prelude::*;
+
+pub struct SpriteAnimationPlugin;
+impl Plugin for SpriteAnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, create_sheet_layouts)
+            .add_systems(
+                Update,
+                (
+                    attach_atlases,
+                    (player_clips, animate_sprites, finish_clips)
+                        .chain()
+                        .run_if(not(in_state(SimulationState::GamePaused))),
+                )
+                    .chain(),
+            )
+            .add_systems(OnExit(AppState::Game), despawn_finishing_animations);
+    }
+}
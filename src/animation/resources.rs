@@ -0,0 +1,142 @@
+use crate::loading::resources::SpriteAssets;
+use bevy::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationMode {
+    Loop,
+    Once,
+}
+
+/// A run of frames on a sheet.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnimationClip {
+    pub first: usize,
+    pub frames: usize,
+    pub fps: f32,
+    pub mode: AnimationMode,
+}
+
+impl AnimationClip {
+    const fn new(first: usize, frames: usize, fps: f32, mode: AnimationMode) -> Self {
+        Self {
+            first,
+            frames,
+            fps,
+            mode,
+        }
+    }
+
+    /// Seconds the clip takes to play through once.
+    pub fn duration(&self) -> f32 {
+        self.frames as f32 / self.fps
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClipKind {
+    Idle,
+    Hurt,
+    SpawnIn,
+    Despawn,
+}
+
+/// The sprite sheets, each a single row of equally sized frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SheetKind {
+    Player,
+    PlayerTwo,
+    Enemy,
+    Star,
+}
+
+impl SheetKind {
+    pub const ALL: [SheetKind; 4] = [
+        SheetKind::Player,
+        SheetKind::PlayerTwo,
+        SheetKind::Enemy,
+        SheetKind::Star,
+    ];
+
+    /// Sheet for the ball of player `index`; the second player has their own colour.
+    pub fn player(index: usize) -> Self {
+        match index {
+            0 => SheetKind::Player,
+            _ => SheetKind::PlayerTwo,
+        }
+    }
+
+    pub fn frame_size(&self) -> UVec2 {
+        match self {
+            SheetKind::Player | SheetKind::PlayerTwo | SheetKind::Enemy => UVec2::splat(64),
+            SheetKind::Star => UVec2::splat(30),
+        }
+    }
+
+    pub fn columns(&self) -> u32 {
+        match self {
+            SheetKind::Player | SheetKind::PlayerTwo => 17,
+            SheetKind::Enemy => 15,
+            SheetKind::Star => 20,
+        }
+    }
+
+    /// Sheet used unless the arena theme brings its own.
+    pub fn default_path(&self) -> &'static str {
+        match self {
+            SheetKind::Player => "sprites/ball_blue_sheet.png",
+            SheetKind::PlayerTwo => "sprites/ball_green_sheet.png",
+            SheetKind::Enemy => "sprites/ball_red_sheet.png",
+            SheetKind::Star => "sprites/star_sheet.png",
+        }
+    }
+
+    pub fn image(&self, sprites: &SpriteAssets) -> Handle<Image> {
+        match self {
+            SheetKind::Player => sprites.ball_blue_sheet.clone(),
+            SheetKind::PlayerTwo => sprites.ball_green_sheet.clone(),
+            SheetKind::Enemy => sprites.ball_red_sheet.clone(),
+            SheetKind::Star => sprites.star_sheet.clone(),
+        }
+    }
+
+    /// Where each clip sits on the sheet; every sheet has an idle clip.
+    pub fn clip(&self, kind: ClipKind) -> Option<AnimationClip> {
+        use AnimationMode::*;
+
+        let sheet = match self {
+            SheetKind::PlayerTwo => SheetKind::Player,
+            sheet => *sheet,
+        };
+
+        match (sheet, kind) {
+            (SheetKind::Player, ClipKind::Idle) => Some(AnimationClip::new(0, 1, 1.0, Loop)),
+            (SheetKind::Player, ClipKind::Hurt) => Some(AnimationClip::new(1, 4, 10.0, Loop)),
+            (SheetKind::Player, ClipKind::SpawnIn) => Some(AnimationClip::new(5, 6, 20.0, Once)),
+            (SheetKind::Player, ClipKind::Despawn) => Some(AnimationClip::new(11, 6, 20.0, Once)),
+            (SheetKind::Enemy, ClipKind::Idle) => Some(AnimationClip::new(0, 1, 1.0, Loop)),
+            // Blinks for a while so players see where an enemy appears.
+            (SheetKind::Enemy, ClipKind::SpawnIn) => Some(AnimationClip::new(1, 8, 10.0, Once)),
+            (SheetKind::Enemy, ClipKind::Despawn) => Some(AnimationClip::new(9, 6, 20.0, Once)),
+            (SheetKind::Star, ClipKind::Idle) => Some(AnimationClip::new(0, 8, 8.0, Loop)),
+            (SheetKind::Star, ClipKind::SpawnIn) => Some(AnimationClip::new(8, 6, 20.0, Once)),
+            (SheetKind::Star, ClipKind::Despawn) => Some(AnimationClip::new(14, 6, 20.0, Once)),
+            _ => None,
+        }
+    }
+}
+
+/// Atlas layouts of the sheets, created at startup.
+#[derive(Resource, Default)]
+pub struct SheetLayouts {
+    pub layouts: Vec<(SheetKind, Handle<TextureAtlasLayout>)>,
+}
+
+impl SheetLayouts {
+    pub fn get(&self, sheet: SheetKind) -> Handle<TextureAtlasLayout> {
+        self.layouts
+            .iter()
+            .find(|(kind, _)| *kind == sheet)
+            .map(|(_, layout)| layout.clone())
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,109 @@
+pub mod animation {
+    use super::super::{
+        components::{DespawnWhenFinished, SpriteAnimation},
+        resources::{AnimationMode, ClipKind, SheetKind, SheetLayouts},
+    };
+    use crate::game::components::player::{Invulnerable, Player};
+    use bevy::prelude::*;
+
+    pub fn create_sheet_layouts(
+        mut commands: Commands,
+        mut atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    ) {
+        let layouts = SheetKind::ALL
+            .into_iter()
+            .map(|sheet| {
+                let layout = TextureAtlasLayout::from_grid(
+                    sheet.frame_size(),
+                    sheet.columns(),
+                    1,
+                    None,
+                    None,
+                );
+                (sheet, atlas_layouts.add(layout))
+            })
+            .collect();
+
+        commands.insert_resource(SheetLayouts { layouts });
+    }
+
+    pub fn attach_atlases(
+        mut animation_query: Query<(&SpriteAnimation, &mut Sprite), Added<SpriteAnimation>>,
+        sheet_layouts: Res<SheetLayouts>,
+    ) {
+        for (animation, mut sprite) in &mut animation_query {
+            sprite.texture_atlas = Some(TextureAtlas {
+                layout: sheet_layouts.get(animation.sheet),
+                index: animation.index(),
+            });
+        }
+    }
+
+    pub fn animate_sprites(
+        mut animation_query: Query<(&mut SpriteAnimation, &mut Sprite)>,
+        time: Res<Time>,
+    ) {
+        for (mut animation, mut sprite) in &mut animation_query {
+            let clip = animation.current();
+            let steps = animation
+                .timer
+                .tick(time.delta())
+                .times_finished_this_tick() as usize;
+
+            if steps > 0 && !animation.finished {
+                let frame = animation.frame + steps;
+                animation.frame = match clip.mode {
+                    AnimationMode::Loop => frame % clip.frames,
+                    AnimationMode::Once if frame >= clip.frames => {
+                        animation.finished = true;
+                        clip.frames - 1
+                    }
+                    AnimationMode::Once => frame,
+                };
+            }
+
+            if let Some(atlas) = &mut sprite.texture_atlas {
+                atlas.index = animation.index();
+            }
+        }
+    }
+
+    /// Moves on from one-shot clips once they have played.
+    pub fn finish_clips(
+        mut commands: Commands,
+        mut animation_query: Query<(Entity, &mut SpriteAnimation, Has<DespawnWhenFinished>)>,
+    ) {
+        for (entity, mut animation, despawn) in &mut animation_query {
+            if !animation.finished {
+                continue;
+            }
+
+            if despawn {
+                commands.entity(entity).despawn();
+            } else if animation.clip == ClipKind::SpawnIn {
+                animation.play(ClipKind::Idle);
+            }
+        }
+    }
+
+    pub fn player_clips(
+        mut player_query: Query<(&mut SpriteAnimation, Has<Invulnerable>), With<Player>>,
+    ) {
+        for (mut animation, invulnerable) in &mut player_query {
+            if invulnerable {
+                animation.play(ClipKind::Hurt);
+            } else if animation.clip == ClipKind::Hurt {
+                animation.play(ClipKind::Idle);
+            }
+        }
+    }
+
+    pub fn despawn_finishing_animations(
+        mut commands: Commands,
+        animation_query: Query<Entity, With<DespawnWhenFinished>>,
+    ) {
+        for entity in &animation_query {
+            commands.entity(entity).despawn();
+        }
+    }
+}
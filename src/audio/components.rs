@@ -0,0 +1,71 @@
+pub mod music {
+    use bevy::prelude::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum MusicLayer {
+        Base,
+        /// Mixed in as the game gets harder.
+        Intense,
+    }
+
+    /// One looping stem of a track, faded in on spawn and out before despawning.
+    #[derive(Component)]
+    pub struct MusicVoice {
+        pub layer: MusicLayer,
+        /// 0 is silent, 1 full volume.
+        pub fade: f32,
+        pub fading_out: bool,
+    }
+
+    impl MusicVoice {
+        pub fn new(layer: MusicLayer) -> Self {
+            Self {
+                layer,
+                fade: 0.0,
+                fading_out: false,
+            }
+        }
+    }
+}
+
+pub mod mixer {
+    use super::super::resources::mixer::SoundKind;
+    use bevy::prelude::*;
+    use std::time::Duration;
+
+    /// Spawn this to play a sound. The mixer turns it into a voice, or drops it when
+    /// its cooldown hasn't passed or no voice can be freed for it.
+    #[derive(Component, Debug, Clone, Copy)]
+    pub struct SoundEffect {
+        pub kind: SoundKind,
+        /// Where in the arena the sound comes from; `None` plays it centered at full volume.
+        pub position: Option<Vec2>,
+    }
+
+    impl SoundEffect {
+        pub fn new(kind: SoundKind) -> Self {
+            Self {
+                kind,
+                position: None,
+            }
+        }
+
+        pub fn at(kind: SoundKind, position: Vec2) -> Self {
+            Self {
+                kind,
+                position: Some(position),
+            }
+        }
+    }
+
+    /// The ears positional sounds are panned between. It sits at the origin of its own
+    /// space, see `resources::mixer::pan`.
+    #[derive(Component)]
+    pub struct SoundListener;
+
+    /// A sound effect that is currently playing.
+    #[derive(Component)]
+    pub struct SoundVoice {
+        pub started: Duration,
+    }
+}
@@ -0,0 +1,16 @@
+use bevy::prelude::*;
+use plugins::*;
+
+pub mod components;
+mod plugins;
+pub mod resources;
+mod systems;
+
+/// Background music that follows the app state, and the mixer every sound effect
+/// goes through.
+pub struct GameAudioPlugin;
+impl Plugin for GameAudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(MixerPlugin).add_plugins(MusicPlugin);
+    }
+}
@@ -0,0 +1,31 @@
+use super::{
+    resources::{mixer::Mixer, music::Music},
+    systems::{mixer::*, music::*},
+};
+use crate::AppState;
+use bevy::prelude::*;
+
+pub struct MixerPlugin;
+impl Plugin for MixerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Mixer>()
+            .add_systems(Startup, spawn_sound_listener)
+            .add_systems(PostUpdate, (play_button_clicks, mix_sound_effects).chain());
+    }
+}
+
+pub struct MusicPlugin;
+impl Plugin for MusicPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Music>().add_systems(
+            Update,
+            (
+                follow_app_state.run_if(state_changed::<AppState>),
+                update_intensity.run_if(in_state(AppState::Game)),
+                duck_music,
+                fade_music,
+            )
+                .chain(),
+        );
+    }
+}
@@ -0,0 +1,236 @@
+pub mod music {
+    use crate::{AppState, loading::resources::MusicAssets};
+    use bevy::prelude::*;
+
+    pub const MUSIC_VOLUME: f32 = 0.5;
+    pub const CROSSFADE_SECS: f32 = 1.5;
+    /// Music volume while the pause menu is open.
+    pub const DUCKED_VOLUME: f32 = 0.3;
+    pub const DUCK_SECS: f32 = 0.3;
+    /// Enemies beyond the starting ones needed for full intensity.
+    pub const INTENSITY_ENEMY_RANGE: f32 = 8.0;
+    pub const INTENSITY_SECS: f32 = 2.0;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Track {
+        Menu,
+        Game,
+        GameOver,
+    }
+
+    impl Track {
+        /// Loading and the editor stay silent, the lobby and settings share the menu music.
+        pub fn for_state(state: AppState) -> Option<Self> {
+            match state {
+                AppState::MainMenu | AppState::Lobby | AppState::Settings => Some(Track::Menu),
+                AppState::Game => Some(Track::Game),
+                AppState::GameOver => Some(Track::GameOver),
+                AppState::Loading | AppState::Editor => None,
+            }
+        }
+
+        pub fn handle(&self, music: &MusicAssets) -> Handle<AudioSource> {
+            match self {
+                Track::Menu => music.menu.clone(),
+                Track::Game => music.game.clone(),
+                Track::GameOver => music.game_over.clone(),
+            }
+        }
+
+        /// Stem played in sync with the base one and faded in with the difficulty.
+        pub fn intense_handle(&self, music: &MusicAssets) -> Option<Handle<AudioSource>> {
+            match self {
+                Track::Game => Some(music.game_intense.clone()),
+                Track::Menu | Track::GameOver => None,
+            }
+        }
+    }
+
+    #[derive(Resource, Debug)]
+    pub struct Music {
+        pub current: Option<Track>,
+        /// Multiplier eased toward `DUCKED_VOLUME` while paused.
+        pub duck: f32,
+        /// 0 plays the base stem alone, 1 mixes in the intense stem fully.
+        pub intensity: f32,
+    }
+
+    impl Default for Music {
+        fn default() -> Self {
+            Self {
+                current: None,
+                duck: 1.0,
+                intensity: 0.0,
+            }
+        }
+    }
+}
+
+pub mod mixer {
+    use crate::{loading::resources::SoundAssets, settings::resources::Settings};
+    use bevy::prelude::*;
+    use std::{collections::HashMap, time::Duration};
+
+    /// Sound effects playing at once; the lowest priority one is cut for a more
+    /// important sound.
+    pub const MAX_VOICES: usize = 8;
+    /// Distance between the listener's ears, in the listener's own space.
+    pub const EAR_GAP: f32 = 2.0;
+    /// How far toward one ear a sound at the arena edge is panned, 1 being fully.
+    pub const PAN_WIDTH: f32 = 0.8;
+    /// Distance from the player at which sounds reach `MIN_DISTANCE_VOLUME`.
+    pub const HEARING_DISTANCE: f32 = 1200.0;
+    pub const MIN_DISTANCE_VOLUME: f32 = 0.3;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum AudioBus {
+        Master,
+        Music,
+        Sfx,
+        Ui,
+    }
+
+    impl AudioBus {
+        pub const ALL: [AudioBus; 4] = [
+            AudioBus::Master,
+            AudioBus::Music,
+            AudioBus::Sfx,
+            AudioBus::Ui,
+        ];
+
+        pub fn label(&self) -> &'static str {
+            match self {
+                AudioBus::Master => "Master",
+                AudioBus::Music => "Music",
+                AudioBus::Sfx => "Effects",
+                AudioBus::Ui => "Interface",
+            }
+        }
+
+        pub fn setting(&self, settings: &Settings) -> f32 {
+            match self {
+                AudioBus::Master => settings.master_volume,
+                AudioBus::Music => settings.music_volume,
+                AudioBus::Sfx => settings.sfx_volume,
+                AudioBus::Ui => settings.ui_volume,
+            }
+        }
+
+        pub fn setting_mut<'a>(&self, settings: &'a mut Settings) -> &'a mut f32 {
+            match self {
+                AudioBus::Master => &mut settings.master_volume,
+                AudioBus::Music => &mut settings.music_volume,
+                AudioBus::Sfx => &mut settings.sfx_volume,
+                AudioBus::Ui => &mut settings.ui_volume,
+            }
+        }
+
+        /// Volume of the bus with the master volume applied.
+        pub fn volume(&self, settings: &Settings) -> f32 {
+            match self {
+                AudioBus::Master => settings.master_volume,
+                _ => settings.master_volume * self.setting(settings),
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum SoundKind {
+        Bounce,
+        StarCollect,
+        Explosion,
+        ButtonClick,
+    }
+
+    impl SoundKind {
+        pub fn bus(&self) -> AudioBus {
+            match self {
+                SoundKind::Bounce | SoundKind::StarCollect | SoundKind::Explosion => AudioBus::Sfx,
+                SoundKind::ButtonClick => AudioBus::Ui,
+            }
+        }
+
+        /// Higher priority sounds take the voice of lower priority ones.
+        pub fn priority(&self) -> u8 {
+            match self {
+                SoundKind::Bounce => 0,
+                SoundKind::StarCollect | SoundKind::ButtonClick => 1,
+                SoundKind::Explosion => 2,
+            }
+        }
+
+        /// Minimum time between two plays, so simultaneous hits collapse into one.
+        pub fn cooldown(&self) -> Duration {
+            Duration::from_millis(match self {
+                SoundKind::Bounce => 80,
+                SoundKind::StarCollect | SoundKind::ButtonClick => 50,
+                SoundKind::Explosion => 100,
+            })
+        }
+
+        pub fn volume(&self) -> f32 {
+            match self {
+                SoundKind::Bounce => 0.6,
+                SoundKind::StarCollect | SoundKind::Explosion => 1.0,
+                SoundKind::ButtonClick => 0.5,
+            }
+        }
+
+        /// Largest random change of playback speed, so repeated sounds don't all sound alike.
+        pub fn pitch_variation(&self) -> f32 {
+            match self {
+                SoundKind::Bounce => 0.15,
+                SoundKind::Explosion => 0.1,
+                SoundKind::StarCollect => 0.05,
+                SoundKind::ButtonClick => 0.0,
+            }
+        }
+
+        pub fn handle(&self, sounds: &SoundAssets) -> Handle<AudioSource> {
+            match self {
+                SoundKind::Bounce => sounds.bounce.clone(),
+                SoundKind::StarCollect => sounds.star_collect.clone(),
+                SoundKind::Explosion => sounds.explosion.clone(),
+                SoundKind::ButtonClick => sounds.click.clone(),
+            }
+        }
+    }
+
+    /// Position on the axis between the listener's ears for a sound at `x` in an arena
+    /// `width` wide: -`PAN_WIDTH` at the left edge, `PAN_WIDTH` at the right one.
+    pub fn pan(x: f32, width: f32) -> f32 {
+        if width <= 0.0 {
+            return 0.0;
+        }
+        ((x / width) * 2.0 - 1.0).clamp(-1.0, 1.0) * PAN_WIDTH
+    }
+
+    /// Volume of a sound `distance` away from the nearest player.
+    pub fn distance_volume(distance: f32) -> f32 {
+        (1.0 - distance / HEARING_DISTANCE).max(MIN_DISTANCE_VOLUME)
+    }
+
+    #[derive(Resource, Default)]
+    pub struct Mixer {
+        pub last_played: HashMap<SoundKind, Duration>,
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn pan_follows_the_sound_across_the_arena() {
+            assert_eq!(pan(0.0, 800.0), -PAN_WIDTH);
+            assert_eq!(pan(400.0, 800.0), 0.0);
+            assert_eq!(pan(800.0, 800.0), PAN_WIDTH);
+        }
+
+        #[test]
+        fn pan_clamps_sounds_outside_the_arena() {
+            assert_eq!(pan(-100.0, 800.0), -PAN_WIDTH);
+            assert_eq!(pan(900.0, 800.0), PAN_WIDTH);
+            assert_eq!(pan(100.0, 0.0), 0.0);
+        }
+    }
+}
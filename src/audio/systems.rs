@@ -0,0 +1,227 @@
+pub mod music {
+    use super::super::components::music::{MusicLayer, MusicVoice};
+    use super::super::resources::{mixer::AudioBus, music::*};
+    use crate::{
+        AppState,
+        game::{
+            components::enemy::{Enemy, NUMBER_OF_ENEMIES},
+            states::SimulationState,
+        },
+        loading::resources::GameAssets,
+        settings::resources::Settings,
+    };
+    use bevy::{audio::Volume, prelude::*};
+
+    /// Crossfades to the track of the state that was just entered.
+    pub fn follow_app_state(
+        mut commands: Commands,
+        game_assets: Res<GameAssets>,
+        state: Res<State<AppState>>,
+        mut music: ResMut<Music>,
+        mut voice_query: Query<&mut MusicVoice>,
+    ) {
+        let track = Track::for_state(*state.get());
+        if track == music.current {
+            return;
+        }
+        music.current = track;
+
+        for mut voice in &mut voice_query {
+            voice.fading_out = true;
+        }
+
+        let Some(track) = track else {
+            return;
+        };
+        commands.spawn((
+            MusicVoice::new(MusicLayer::Base),
+            AudioPlayer::new(track.handle(&game_assets.music)),
+            PlaybackSettings::LOOP.with_volume(Volume::SILENT),
+        ));
+        if let Some(handle) = track.intense_handle(&game_assets.music) {
+            music.intensity = 0.0;
+            commands.spawn((
+                MusicVoice::new(MusicLayer::Intense),
+                AudioPlayer::new(handle),
+                PlaybackSettings::LOOP.with_volume(Volume::SILENT),
+            ));
+        }
+    }
+
+    /// Follows the number of enemies, which grows the longer a run lasts.
+    pub fn update_intensity(
+        enemy_query: Query<(), With<Enemy>>,
+        mut music: ResMut<Music>,
+        time: Res<Time>,
+    ) {
+        let extra_enemies = enemy_query.iter().count().saturating_sub(NUMBER_OF_ENEMIES);
+        let target = (extra_enemies as f32 / INTENSITY_ENEMY_RANGE).min(1.0);
+        let step = time.delta_secs() / INTENSITY_SECS;
+        music.intensity += (target - music.intensity).clamp(-step, step);
+    }
+
+    pub fn duck_music(
+        simulation_state: Option<Res<State<SimulationState>>>,
+        mut music: ResMut<Music>,
+        time: Res<Time>,
+    ) {
+        let paused =
+            simulation_state.is_some_and(|state| *state.get() == SimulationState::GamePaused);
+        let target = if paused { DUCKED_VOLUME } else { 1.0 };
+        let step = time.delta_secs() * (1.0 - DUCKED_VOLUME) / DUCK_SECS;
+        music.duck += (target - music.duck).clamp(-step, step);
+    }
+
+    pub fn fade_music(
+        mut commands: Commands,
+        mut voice_query: Query<(Entity, &mut MusicVoice, Option<&mut AudioSink>)>,
+        music: Res<Music>,
+        settings: Res<Settings>,
+        time: Res<Time>,
+    ) {
+        let step = time.delta_secs() / CROSSFADE_SECS;
+        let bus_volume = AudioBus::Music.volume(&settings);
+
+        for (entity, mut voice, sink) in &mut voice_query {
+            if voice.fading_out {
+                voice.fade -= step;
+                if voice.fade <= 0.0 {
+                    commands.entity(entity).despawn();
+                    continue;
+                }
+            } else {
+                voice.fade = (voice.fade + step).min(1.0);
+            }
+
+            let layer_volume = match voice.layer {
+                MusicLayer::Base => 1.0,
+                MusicLayer::Intense => music.intensity,
+            };
+            if let Some(mut sink) = sink {
+                sink.set_volume(Volume::Linear(
+                    voice.fade * layer_volume * music.duck * MUSIC_VOLUME * bus_volume,
+                ));
+            }
+        }
+    }
+}
+
+pub mod mixer {
+    use super::super::components::mixer::{SoundEffect, SoundListener, SoundVoice};
+    use super::super::resources::mixer::*;
+    use crate::{
+        game::{components::player::Player, resources::arena::Arena},
+        loading::resources::GameAssets,
+        settings::resources::Settings,
+    };
+    use bevy::{audio::Volume, prelude::*};
+    use rand::Rng;
+
+    pub fn spawn_sound_listener(mut commands: Commands) {
+        commands.spawn((
+            SoundListener,
+            SpatialListener::new(EAR_GAP),
+            Transform::default(),
+        ));
+    }
+
+    /// Turns requested sound effects into voices, honouring cooldowns and the voice limit.
+    #[allow(clippy::too_many_arguments)]
+    pub fn mix_sound_effects(
+        mut commands: Commands,
+        game_assets: Res<GameAssets>,
+        request_query: Query<(Entity, &SoundEffect), Without<SoundVoice>>,
+        voice_query: Query<(Entity, &SoundEffect, &SoundVoice)>,
+        player_query: Query<&Transform, With<Player>>,
+        arena: Res<Arena>,
+        mut mixer: ResMut<Mixer>,
+        settings: Res<Settings>,
+        time: Res<Time>,
+    ) {
+        let now = time.elapsed();
+        let mut voices: Vec<(Entity, u8, std::time::Duration)> = voice_query
+            .iter()
+            .map(|(entity, sound, voice)| (entity, sound.kind.priority(), voice.started))
+            .collect();
+
+        let arena_width = arena.size.x;
+        let players: Vec<Vec2> = player_query
+            .iter()
+            .map(|transform| transform.translation.truncate())
+            .collect();
+
+        let mut requests: Vec<(Entity, SoundEffect)> = request_query
+            .iter()
+            .map(|(entity, sound)| (entity, *sound))
+            .collect();
+        requests.sort_by_key(|(_, sound)| std::cmp::Reverse(sound.kind.priority()));
+
+        let mut rng = rand::rng();
+        for (entity, sound) in requests {
+            let kind = sound.kind;
+            let cooling_down = mixer
+                .last_played
+                .get(&kind)
+                .is_some_and(|last| now.saturating_sub(*last) < kind.cooldown());
+            if cooling_down {
+                commands.entity(entity).despawn();
+                continue;
+            }
+
+            if voices.len() >= MAX_VOICES {
+                let Some(weakest) = voices
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, (_, priority, started))| (*priority, *started))
+                    .map(|(index, _)| index)
+                    .filter(|index| voices[*index].1 < kind.priority())
+                else {
+                    commands.entity(entity).despawn();
+                    continue;
+                };
+                commands.entity(voices.swap_remove(weakest).0).despawn();
+            }
+
+            let variation = kind.pitch_variation();
+            let speed = 1.0 + rng.random_range(-variation..=variation);
+            let mut volume = kind.volume() * kind.bus().volume(&settings);
+            let mut playback = PlaybackSettings::DESPAWN.with_speed(speed);
+
+            if let Some(position) = sound.position {
+                let nearest_player = players
+                    .iter()
+                    .map(|player| player.distance(position))
+                    .min_by(f32::total_cmp);
+                volume *= nearest_player.map_or(1.0, distance_volume);
+
+                // Placed on the axis between the listener's ears rather than in the world.
+                let ear_space = Vec3::new(pan(position.x, arena_width), 0.0, 0.0);
+                commands.entity(entity).insert((
+                    Transform::from_translation(ear_space),
+                    GlobalTransform::from_translation(ear_space),
+                ));
+                playback = playback.with_spatial(true);
+            }
+
+            commands.entity(entity).insert((
+                SoundVoice { started: now },
+                AudioPlayer::new(kind.handle(&game_assets.sounds)),
+                playback.with_volume(Volume::Linear(volume)),
+            ));
+
+            mixer.last_played.insert(kind, now);
+            voices.push((entity, kind.priority(), now));
+        }
+    }
+
+    pub fn play_button_clicks(
+        mut commands: Commands,
+        button_query: Query<&Interaction, (Changed<Interaction>, With<Button>)>,
+    ) {
+        for interaction in &button_query {
+            if *interaction == Interaction::Pressed {
+                commands.spawn(SoundEffect::new(SoundKind::ButtonClick));
+            }
+        }
+    }
+}
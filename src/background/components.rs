@@ -0,0 +1,22 @@
+use bevy::prelude::*;
+
+/// Root of one backdrop layer. Its children are positioned relative to it.
+#[derive(Component)]
+pub struct BackgroundLayer {
+    pub drift: Vec2,
+    pub parallax: f32,
+    /// Size of the area the layer repeats over.
+    pub area: Vec2,
+}
+
+/// A star of a starfield layer, at `base` when the layer hasn't drifted yet.
+#[derive(Component)]
+pub struct BackgroundStar {
+    pub base: Vec2,
+}
+
+/// A tiled layer, `tile_size` apart.
+#[derive(Component)]
+pub struct BackgroundTiles {
+    pub tile_size: f32,
+}
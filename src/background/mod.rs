@@ -0,0 +1,16 @@
+use bevy::prelude::*;
+use plugins::*;
+
+pub mod components;
+mod plugins;
+pub mod resources;
+mod systems;
+
+/// Parallax backdrop behind the arena, and the theme packs from `assets/themes`
+/// that choose its layers, colours and ball sprites.
+pub struct BackgroundPlugin;
+impl Plugin for BackgroundPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(ThemePlugin).add_plugins(ParallaxPlugin);
+    }
+}
@@ -0,0 +1,31 @@
+use super::{
+    resources::{AppliedTheme, SelectedTheme, ThemeLibrary},
+    systems::{parallax::*, theme::*},
+};
+use bevy::prelude::*;
+
+pub struct ThemePlugin;
+impl Plugin for ThemePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ThemeLibrary::load())
+            .init_resource::<SelectedTheme>()
+            .init_resource::<AppliedTheme>()
+            .add_systems(Startup, select_saved_theme)
+            .add_systems(
+                Update,
+                (
+                    apply_theme.run_if(resource_changed::<SelectedTheme>),
+                    theme_new_sprites,
+                    theme_new_obstacles,
+                )
+                    .chain(),
+            );
+    }
+}
+
+pub struct ParallaxPlugin;
+impl Plugin for ParallaxPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, scroll_layers);
+    }
+}
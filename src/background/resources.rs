@@ -0,0 +1,189 @@
+use crate::animation::resources::SheetKind;
+use bevy::{asset::io::file::FileAssetReader, prelude::*};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+pub const THEMES_DIR: &str = "themes";
+pub const BACKGROUND_Z: f32 = -10.0;
+/// How far layers reach past the window, so shakes and zooms don't show their edges.
+pub const LAYER_MARGIN: f32 = 1.5;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum LayerKind {
+    Stars { count: u32, size: (f32, f32) },
+    Tiles { image: String, tile_size: f32 },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LayerData {
+    pub kind: LayerKind,
+    pub color: (f32, f32, f32, f32),
+    /// Movement in pixels per second.
+    pub drift: (f32, f32),
+    /// 0 keeps the layer fixed on screen, 1 moves it with the arena.
+    pub parallax: f32,
+}
+
+/// Sprite sheets replacing the default ones, with the same frame layout.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct ThemeSprites {
+    pub player: Option<String>,
+    pub player_two: Option<String>,
+    pub enemy: Option<String>,
+    pub star: Option<String>,
+}
+
+impl ThemeSprites {
+    pub fn path(&self, sheet: SheetKind) -> Option<&str> {
+        match sheet {
+            SheetKind::Player => self.player.as_deref(),
+            SheetKind::PlayerTwo => self.player_two.as_deref(),
+            SheetKind::Enemy => self.enemy.as_deref(),
+            SheetKind::Star => self.star.as_deref(),
+        }
+    }
+}
+
+/// A theme pack as stored in `assets/themes/*.ron`. Colours are linear RGB.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ThemeData {
+    pub name: String,
+    pub clear_color: (f32, f32, f32),
+    pub obstacle_color: (f32, f32, f32),
+    pub bumper_color: (f32, f32, f32),
+    #[serde(default)]
+    pub layers: Vec<LayerData>,
+    #[serde(default)]
+    pub sprites: ThemeSprites,
+}
+
+impl ThemeData {
+    /// Every image the theme refers to: replacement sheets and tile layers.
+    pub fn image_paths(&self) -> impl Iterator<Item = &str> {
+        let sheets = SheetKind::ALL
+            .into_iter()
+            .filter_map(|sheet| self.sprites.path(sheet));
+        let tiles = self.layers.iter().filter_map(|layer| match &layer.kind {
+            LayerKind::Tiles { image, .. } => Some(image.as_str()),
+            LayerKind::Stars { .. } => None,
+        });
+        sheets.chain(tiles)
+    }
+}
+
+impl Default for ThemeData {
+    fn default() -> Self {
+        Self {
+            name: "Default".to_string(),
+            clear_color: (0.024, 0.025, 0.028),
+            obstacle_color: (0.3, 0.32, 0.4),
+            bumper_color: (0.95, 0.6, 0.1),
+            layers: Vec::new(),
+            sprites: ThemeSprites::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ThemeEntry {
+    /// File name without extension, as saved in the settings.
+    pub id: String,
+    pub data: ThemeData,
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct ThemeLibrary {
+    pub themes: Vec<ThemeEntry>,
+}
+
+impl ThemeLibrary {
+    /// Reads every `.ron` file of the themes folder, sorted by file name. Themes
+    /// that fail to parse are reported and skipped; a plain default is used when
+    /// nothing could be loaded.
+    pub fn load() -> Self {
+        let dir = Self::dir();
+        let mut paths: Vec<PathBuf> = fs::read_dir(&dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                    .filter(|path| path.extension().is_some_and(|ext| ext == "ron"))
+                    .collect()
+            })
+            .unwrap_or_else(|err| {
+                error!("Could not read themes folder {}: {err}", dir.display());
+                Vec::new()
+            });
+        paths.sort();
+
+        let mut themes: Vec<ThemeEntry> = paths
+            .into_iter()
+            .filter_map(|path| match Self::read_theme(&path) {
+                Ok(data) => {
+                    let id = path.file_stem()?.to_string_lossy().into_owned();
+                    Some(ThemeEntry { id, data })
+                }
+                Err(err) => {
+                    error!("Invalid theme file {}: {err}", path.display());
+                    None
+                }
+            })
+            .collect();
+
+        if themes.is_empty() {
+            themes.push(ThemeEntry {
+                id: "default".to_string(),
+                data: ThemeData::default(),
+            });
+        }
+
+        Self { themes }
+    }
+
+    pub fn dir() -> PathBuf {
+        FileAssetReader::get_base_path().join(THEMES_DIR)
+    }
+
+    pub fn read_theme(path: &Path) -> Result<ThemeData, String> {
+        let content = fs::read_to_string(path).map_err(|err| err.to_string())?;
+        ron::from_str(&content).map_err(|err| err.to_string())
+    }
+
+    pub fn get(&self, selected: &SelectedTheme) -> &ThemeEntry {
+        &self.themes[selected.index.min(self.themes.len() - 1)]
+    }
+
+    pub fn index_of(&self, id: &str) -> Option<usize> {
+        self.themes.iter().position(|theme| theme.id == id)
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct SelectedTheme {
+    pub index: usize,
+}
+
+impl SelectedTheme {
+    pub fn next(&mut self, library: &ThemeLibrary) {
+        self.index = (self.index + 1) % library.themes.len();
+    }
+}
+
+/// Handles of the applied theme, kept so newly spawned things can be themed too.
+#[derive(Resource, Default)]
+pub struct AppliedTheme {
+    pub sheets: Vec<(SheetKind, Handle<Image>)>,
+    pub obstacle_color: Color,
+    pub bumper_color: Color,
+}
+
+impl AppliedTheme {
+    pub fn sheet(&self, sheet: SheetKind) -> Option<Handle<Image>> {
+        self.sheets
+            .iter()
+            .find(|(kind, _)| *kind == sheet)
+            .map(|(_, image)| image.clone())
+    }
+}
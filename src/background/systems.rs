@@ -0,0 +1,250 @@
+pub mod theme {
+    use super::super::{
+        components::BackgroundLayer,
+        resources::{AppliedTheme, SelectedTheme, ThemeLibrary},
+    };
+    use super::parallax::spawn_layer;
+    use crate::animation::{components::SpriteAnimation, resources::SheetKind};
+    use crate::game::{components::obstacle::Obstacle, resources::level::ObstacleShape};
+    use crate::settings::resources::Settings;
+    use bevy::{prelude::*, window::PrimaryWindow};
+
+    type ObstacleLook = (
+        &'static Obstacle,
+        Option<&'static mut Sprite>,
+        Option<&'static MeshMaterial2d<ColorMaterial>>,
+    );
+
+    pub fn select_saved_theme(
+        settings: Res<Settings>,
+        theme_library: Res<ThemeLibrary>,
+        mut selected_theme: ResMut<SelectedTheme>,
+    ) {
+        if let Some(index) = theme_library.index_of(&settings.theme) {
+            selected_theme.index = index;
+        }
+    }
+
+    /// Rebuilds the backdrop and restyles everything already on screen.
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply_theme(
+        mut commands: Commands,
+        layer_query: Query<Entity, With<BackgroundLayer>>,
+        mut sprite_query: Query<(&SpriteAnimation, &mut Sprite), Without<Obstacle>>,
+        mut obstacle_query: Query<ObstacleLook>,
+        window_query: Query<&Window, With<PrimaryWindow>>,
+        theme_library: Res<ThemeLibrary>,
+        selected_theme: Res<SelectedTheme>,
+        asset_server: Res<AssetServer>,
+        mut applied: ResMut<AppliedTheme>,
+        mut clear_color: ResMut<ClearColor>,
+        mut materials: ResMut<Assets<ColorMaterial>>,
+    ) {
+        let theme = &theme_library.get(&selected_theme).data;
+        info!("Applying theme \"{}\"", theme.name);
+
+        clear_color.0 = Color::linear_rgb(
+            theme.clear_color.0,
+            theme.clear_color.1,
+            theme.clear_color.2,
+        );
+        *applied = AppliedTheme {
+            sheets: SheetKind::ALL
+                .into_iter()
+                .map(|sheet| {
+                    let path = theme.sprites.path(sheet).unwrap_or(sheet.default_path());
+                    (sheet, asset_server.load(path.to_string()))
+                })
+                .collect(),
+            obstacle_color: Color::linear_rgb(
+                theme.obstacle_color.0,
+                theme.obstacle_color.1,
+                theme.obstacle_color.2,
+            ),
+            bumper_color: Color::linear_rgb(
+                theme.bumper_color.0,
+                theme.bumper_color.1,
+                theme.bumper_color.2,
+            ),
+        };
+
+        for entity in &layer_query {
+            commands.entity(entity).despawn();
+        }
+        if let Ok(window) = window_query.single() {
+            for (depth, layer) in theme.layers.iter().enumerate() {
+                spawn_layer(&mut commands, layer, depth, window.size(), &asset_server);
+            }
+        }
+
+        for (animation, mut sprite) in &mut sprite_query {
+            skin_sprite(animation, &mut sprite, &applied);
+        }
+        for (obstacle, sprite, material) in &mut obstacle_query {
+            color_obstacle(obstacle, sprite, material, &applied, &mut materials);
+        }
+    }
+
+    pub fn theme_new_sprites(
+        mut sprite_query: Query<(&SpriteAnimation, &mut Sprite), Added<SpriteAnimation>>,
+        applied: Res<AppliedTheme>,
+    ) {
+        for (animation, mut sprite) in &mut sprite_query {
+            skin_sprite(animation, &mut sprite, &applied);
+        }
+    }
+
+    pub fn theme_new_obstacles(
+        mut obstacle_query: Query<ObstacleLook, Added<Obstacle>>,
+        applied: Res<AppliedTheme>,
+        mut materials: ResMut<Assets<ColorMaterial>>,
+    ) {
+        for (obstacle, sprite, material) in &mut obstacle_query {
+            color_obstacle(obstacle, sprite, material, &applied, &mut materials);
+        }
+    }
+
+    fn skin_sprite(animation: &SpriteAnimation, sprite: &mut Sprite, applied: &AppliedTheme) {
+        if let Some(image) = applied.sheet(animation.sheet) {
+            sprite.image = image;
+        }
+    }
+
+    fn color_obstacle(
+        obstacle: &Obstacle,
+        sprite: Option<Mut<Sprite>>,
+        material: Option<&MeshMaterial2d<ColorMaterial>>,
+        applied: &AppliedTheme,
+        materials: &mut Assets<ColorMaterial>,
+    ) {
+        let color = match obstacle.shape {
+            ObstacleShape::Bumper { .. } => applied.bumper_color,
+            ObstacleShape::Rect { .. } | ObstacleShape::Circle { .. } => applied.obstacle_color,
+        };
+
+        if let Some(mut sprite) = sprite {
+            sprite.color = color;
+        }
+        if let Some(material) = material.and_then(|material| materials.get_mut(&material.0)) {
+            material.color = color;
+        }
+    }
+}
+
+pub mod parallax {
+    use super::super::{
+        components::{BackgroundLayer, BackgroundStar, BackgroundTiles},
+        resources::{BACKGROUND_Z, LAYER_MARGIN, LayerData, LayerKind},
+    };
+    use bevy::{prelude::*, window::PrimaryWindow};
+    use rand::Rng;
+
+    type LayerContent = (
+        &'static mut Transform,
+        Option<&'static BackgroundStar>,
+        Option<&'static BackgroundTiles>,
+    );
+
+    pub fn spawn_layer(
+        commands: &mut Commands,
+        layer: &LayerData,
+        depth: usize,
+        window_size: Vec2,
+        asset_server: &AssetServer,
+    ) {
+        let area = window_size * LAYER_MARGIN;
+        let (r, g, b, a) = layer.color;
+        let color = Color::linear_rgba(r, g, b, a);
+
+        let mut root = commands.spawn((
+            BackgroundLayer {
+                drift: Vec2::from(layer.drift),
+                parallax: layer.parallax,
+                area,
+            },
+            Transform::from_xyz(0.0, 0.0, BACKGROUND_Z - depth as f32),
+            Visibility::default(),
+        ));
+
+        match &layer.kind {
+            LayerKind::Stars { count, size } => {
+                let mut rng = rand::rng();
+                root.with_children(|parent| {
+                    for _ in 0..*count {
+                        let base =
+                            Vec2::new(rng.random_range(0.0..area.x), rng.random_range(0.0..area.y));
+                        let size = rng.random_range(size.0..=size.1);
+                        parent.spawn((
+                            BackgroundStar { base },
+                            Sprite {
+                                color,
+                                custom_size: Some(Vec2::splat(size)),
+                                ..Default::default()
+                            },
+                            Transform::default(),
+                        ));
+                    }
+                });
+            }
+            LayerKind::Tiles { image, tile_size } => {
+                root.with_child((
+                    BackgroundTiles {
+                        tile_size: *tile_size,
+                    },
+                    Sprite {
+                        image: asset_server.load(image.clone()),
+                        color,
+                        custom_size: Some(area + *tile_size),
+                        image_mode: SpriteImageMode::Tiled {
+                            tile_x: true,
+                            tile_y: true,
+                            stretch_value: 1.0,
+                        },
+                        ..Default::default()
+                    },
+                    Transform::default(),
+                ));
+            }
+        }
+    }
+
+    /// Layers follow the camera by `1 - parallax` of its distance from the arena
+    /// centre, and their content drifts and wraps around inside them.
+    pub fn scroll_layers(
+        mut layer_query: Query<(&BackgroundLayer, &mut Transform, &Children)>,
+        mut child_query: Query<LayerContent, Without<BackgroundLayer>>,
+        camera_query: Query<&GlobalTransform, With<Camera2d>>,
+        window_query: Query<&Window, With<PrimaryWindow>>,
+        time: Res<Time>,
+    ) {
+        let (Ok(camera), Ok(window)) = (camera_query.single(), window_query.single()) else {
+            return;
+        };
+        let camera = camera.translation().truncate();
+        let anchor = window.size() / 2.0;
+        let elapsed = time.elapsed_secs();
+
+        for (layer, mut transform, children) in &mut layer_query {
+            let position = camera - (camera - anchor) * layer.parallax;
+            transform.translation.x = position.x;
+            transform.translation.y = position.y;
+
+            let drift = layer.drift * elapsed;
+            for child in children {
+                let Ok((mut child_transform, star, tiles)) = child_query.get_mut(*child) else {
+                    continue;
+                };
+
+                let offset = if let Some(star) = star {
+                    (star.base + drift).rem_euclid(layer.area) - layer.area / 2.0
+                } else if let Some(tiles) = tiles {
+                    drift.rem_euclid(Vec2::splat(tiles.tile_size)) - tiles.tile_size / 2.0
+                } else {
+                    continue;
+                };
+                child_transform.translation.x = offset.x;
+                child_transform.translation.y = offset.y;
+            }
+        }
+    }
+}
@@ -0,0 +1,93 @@
+//! Small in-memory stand-in for the leaderboard service, for trying the client locally:
+//! `cargo run --bin leaderboard_server -- [port]`.
+
+use serde_json::Value;
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+};
+
+const DEFAULT_PORT: u16 = 8080;
+const DEFAULT_LIMIT: usize = 10;
+
+fn main() -> std::io::Result<()> {
+    let port = std::env::args()
+        .nth(1)
+        .and_then(|port| port.parse().ok())
+        .unwrap_or(DEFAULT_PORT);
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+    println!("Leaderboard listening on http://127.0.0.1:{port}");
+
+    let mut scores: Vec<Value> = Vec::new();
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(err) => {
+                eprintln!("Connection failed: {err}");
+                continue;
+            }
+        };
+        if let Err(err) = handle(stream, &mut scores) {
+            eprintln!("Request failed: {err}");
+        }
+    }
+
+    Ok(())
+}
+
+fn handle(mut stream: TcpStream, scores: &mut Vec<Value>) -> std::io::Result<()> {
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header)?;
+        let header = header.trim();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            content_length = value.trim().parse().unwrap_or(0);
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let target = parts.next().unwrap_or_default();
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+    let (status, response) = match (method, path) {
+        ("GET", "/scores") => {
+            let limit = query
+                .split('&')
+                .find_map(|pair| pair.strip_prefix("limit="))
+                .and_then(|limit| limit.parse().ok())
+                .unwrap_or(DEFAULT_LIMIT);
+            let top: Vec<&Value> = scores.iter().take(limit).collect();
+            ("200 OK", serde_json::to_string(&top).unwrap_or_default())
+        }
+        ("POST", "/scores") => match serde_json::from_slice::<Value>(&body) {
+            Ok(entry) if entry["score"].is_u64() => {
+                println!("Accepted {entry}");
+                scores.push(entry);
+                scores.sort_by_key(|entry| std::cmp::Reverse(entry["score"].as_u64()));
+                ("201 Created", "{}".to_string())
+            }
+            _ => ("400 Bad Request", "{}".to_string()),
+        },
+        _ => ("404 Not Found", "{}".to_string()),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+        response.len()
+    )
+}
@@ -0,0 +1,236 @@
+//! Re-runs a recorded run without rendering and checks that it really ends with the
+//! claimed score on the claimed tick: `cargo run --bin verify_replay -- <file.replay>`.
+//!
+//! Prints a JSON verdict and exits with 0 when verified, 1 on a mismatch and 2 when
+//! the replay can't be checked at all.
+
+use bevy::{
+    asset::AssetPlugin, ecs::message::Messages, prelude::*, state::app::StatesPlugin,
+    window::PrimaryWindow,
+};
+use bevy_dash_ball_game::{
+    AppState,
+    background::resources::ThemeLibrary,
+    game::{
+        GamePlugin,
+        components::player::{Player, RemoteInput},
+        messages::game_states::GameOver,
+        resources::{
+            arena::Arena,
+            level::{LevelEntry, LevelLibrary, SelectedLevel},
+            random::MatchSeed,
+            replay::{Replay, ReplayOutcome},
+        },
+        schedules::GameplayStep,
+        states::SimulationState,
+    },
+    loading::resources::GameAssets,
+    transition::{messages::TransitionRequest, resources::ScreenTransition},
+    widgets::resources::{UiTheme, UiThemeLibrary},
+};
+use serde::Serialize;
+use std::{path::PathBuf, process::ExitCode, time::Duration};
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum Status {
+    Verified,
+    Mismatch,
+    Invalid,
+}
+
+#[derive(Serialize, Debug)]
+struct Verdict {
+    status: Status,
+    replay: String,
+    replay_hash: Option<String>,
+    claimed_score: Option<u32>,
+    claimed_tick: Option<u32>,
+    simulated_score: Option<u32>,
+    simulated_tick: Option<u32>,
+    reasons: Vec<String>,
+}
+
+impl Verdict {
+    fn invalid(replay: String, reason: String) -> Self {
+        Self {
+            status: Status::Invalid,
+            replay,
+            replay_hash: None,
+            claimed_score: None,
+            claimed_tick: None,
+            simulated_score: None,
+            simulated_tick: None,
+            reasons: vec![reason],
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let Some(path) = std::env::args().nth(1).map(PathBuf::from) else {
+        eprintln!("Usage: verify_replay <file.replay>");
+        return ExitCode::from(2);
+    };
+
+    let verdict = verify(&path);
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&verdict).expect("verdicts are always serializable")
+    );
+
+    match verdict.status {
+        Status::Verified => ExitCode::SUCCESS,
+        Status::Mismatch => ExitCode::from(1),
+        Status::Invalid => ExitCode::from(2),
+    }
+}
+
+fn verify(path: &std::path::Path) -> Verdict {
+    let name = path.display().to_string();
+    let replay = match Replay::load(path) {
+        Ok(replay) => replay,
+        Err(err) => return Verdict::invalid(name, format!("could not read replay: {err}")),
+    };
+    let Some(claimed) = replay.outcome.clone() else {
+        return Verdict::invalid(name, "replay does not claim an outcome".to_string());
+    };
+    if replay
+        .frames
+        .iter()
+        .any(|frame| frame.inputs.len() != replay.mode.player_count())
+    {
+        return Verdict::invalid(name, "frame inputs don't match the game mode".to_string());
+    }
+
+    let simulated = simulate(&replay);
+    let mut reasons = Vec::new();
+    match &simulated {
+        Some(simulated) => {
+            if simulated.score != claimed.score {
+                reasons.push(format!(
+                    "claimed score {} but the run scored {}",
+                    claimed.score, simulated.score
+                ));
+            }
+            if simulated.game_over_tick != claimed.game_over_tick {
+                reasons.push(format!(
+                    "claimed game over on tick {} but it happened on tick {}",
+                    claimed.game_over_tick, simulated.game_over_tick
+                ));
+            }
+            if simulated.results != claimed.results {
+                reasons.push("player results differ".to_string());
+            }
+        }
+        None => reasons.push(format!(
+            "no game over within the {} recorded ticks",
+            replay.frames.len()
+        )),
+    }
+
+    Verdict {
+        status: if reasons.is_empty() {
+            Status::Verified
+        } else {
+            Status::Mismatch
+        },
+        replay: name,
+        replay_hash: Some(replay.hash()),
+        claimed_score: Some(claimed.score),
+        claimed_tick: Some(claimed.game_over_tick),
+        simulated_score: simulated.as_ref().map(|outcome| outcome.score),
+        simulated_tick: simulated.as_ref().map(|outcome| outcome.game_over_tick),
+        reasons,
+    }
+}
+
+/// Steps a windowless copy of the game through the recorded ticks, feeding each player
+/// its recorded direction, and returns how the run ended.
+fn simulate(replay: &Replay) -> Option<ReplayOutcome> {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default(), StatesPlugin))
+        .init_asset::<Image>()
+        .init_asset::<AudioSource>()
+        .init_asset::<Font>()
+        .init_asset::<Mesh>()
+        .init_asset::<ColorMaterial>()
+        .init_resource::<ButtonInput<KeyCode>>()
+        .add_message::<TransitionRequest>()
+        .init_resource::<ScreenTransition>()
+        .init_resource::<UiTheme>()
+        .insert_state(AppState::MainMenu)
+        .add_plugins(GamePlugin)
+        .insert_resource(LevelLibrary {
+            levels: vec![LevelEntry {
+                path: None,
+                data: replay.level.clone(),
+            }],
+        })
+        .insert_resource(SelectedLevel::default())
+        .insert_resource(replay.mode)
+        .insert_resource(replay.control_scheme)
+        .insert_resource(MatchSeed {
+            value: Some(replay.seed),
+        })
+        .insert_resource(Arena {
+            size: replay.arena.into(),
+        });
+    app.world_mut().spawn((Window::default(), PrimaryWindow));
+    // Themes only change the look, so none of their images or fonts are needed here.
+    let no_themes = ThemeLibrary { themes: Vec::new() };
+    let no_ui_themes = UiThemeLibrary { themes: Vec::new() };
+    let game_assets = GameAssets::load(
+        app.world().resource::<AssetServer>(),
+        &no_themes,
+        &no_ui_themes,
+    );
+    app.insert_resource(game_assets);
+
+    app.finish();
+    app.cleanup();
+    app.update();
+
+    let world = app.world_mut();
+
+    // Only the state transition: ticks are stepped by hand below. Recorded runs
+    // start after the countdown.
+    world
+        .resource_mut::<NextState<AppState>>()
+        .set(AppState::Game);
+    world
+        .resource_mut::<NextState<SimulationState>>()
+        .set(SimulationState::GameRunning);
+    world.run_schedule(StateTransition);
+
+    for (tick, frame) in replay.frames.iter().enumerate() {
+        let players: Vec<(Entity, usize)> = world
+            .query::<(Entity, &Player)>()
+            .iter(world)
+            .map(|(entity, player)| (entity, player.index))
+            .collect();
+        for (entity, player_index) in players {
+            let direction = frame
+                .inputs
+                .get(player_index)
+                .copied()
+                .map(Vec2::from)
+                .unwrap_or_default();
+            world.entity_mut(entity).insert(RemoteInput { direction });
+        }
+
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_nanos(frame.delta_nanos));
+        *world.resource_mut::<Time>() = time;
+        world.run_schedule(GameplayStep);
+
+        if let Some(game_over) = world.resource_mut::<Messages<GameOver>>().drain().next() {
+            return Some(ReplayOutcome {
+                score: game_over.score,
+                results: game_over.results,
+                game_over_tick: tick as u32 + 1,
+            });
+        }
+    }
+
+    None
+}
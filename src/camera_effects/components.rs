@@ -0,0 +1,12 @@
+use bevy::prelude::*;
+
+/// Spawn this to kick the camera. It is consumed the next time cues are applied.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub enum CameraCue {
+    /// A player was hit: heavy shake and, offline, a moment of slow motion.
+    Death,
+    /// An enemy brushed past a player without touching it.
+    NearMiss,
+    /// Stars collected in quick succession, `chain` of them so far.
+    Combo { chain: u32 },
+}
@@ -0,0 +1,17 @@
+use bevy::prelude::*;
+use plugins::*;
+
+pub mod components;
+mod plugins;
+pub mod resources;
+mod systems;
+
+/// Screen shake, zoom punches and hit-stop layered on top of the game camera. All
+/// of them can be turned off in the settings.
+pub struct CameraEffectsPlugin;
+impl Plugin for CameraEffectsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(CameraCuePlugin)
+            .add_plugins(CameraShakePlugin);
+    }
+}
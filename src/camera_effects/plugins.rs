@@ -0,0 +1,45 @@
+use super::{
+    resources::{CameraEffects, ComboTracker},
+    systems::{cues::*, shake::*},
+};
+use crate::{
+    AppState,
+    game::{
+        states::SimulationState,
+        systems::{camera::camera_position, network::camera_follows_window},
+    },
+};
+use bevy::prelude::*;
+
+pub struct CameraCuePlugin;
+impl Plugin for CameraCuePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ComboTracker>()
+            .add_systems(OnEnter(AppState::Game), reset_combo)
+            .add_systems(
+                Update,
+                (detect_near_misses, detect_combos)
+                    .run_if(in_state(AppState::Game))
+                    .run_if(in_state(SimulationState::GameRunning)),
+            );
+    }
+}
+
+pub struct CameraShakePlugin;
+impl Plugin for CameraShakePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraEffects>()
+            .add_systems(
+                Update,
+                (
+                    apply_camera_cues,
+                    hit_stop,
+                    shake_camera
+                        .after(camera_position)
+                        .run_if(camera_follows_window),
+                )
+                    .chain(),
+            )
+            .add_systems(OnExit(AppState::Game), clear_camera_effects);
+    }
+}
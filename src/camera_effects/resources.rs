@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+
+pub const MAX_SHAKE_OFFSET: f32 = 24.0;
+pub const MAX_SHAKE_ANGLE: f32 = 0.04;
+pub const SHAKE_FREQUENCY: f32 = 25.0;
+/// Trauma lost per second.
+pub const TRAUMA_DECAY: f32 = 1.5;
+pub const DEATH_TRAUMA: f32 = 0.8;
+pub const NEAR_MISS_TRAUMA: f32 = 0.3;
+/// Extra distance between player and enemy edges that still counts as a near miss.
+pub const NEAR_MISS_MARGIN: f32 = 20.0;
+
+/// Seconds between star pickups that keep a combo going.
+pub const COMBO_WINDOW: f32 = 1.5;
+/// Zoom per star in a combo, as a fraction of the view.
+pub const COMBO_ZOOM: f32 = 0.02;
+pub const MAX_ZOOM: f32 = 0.1;
+/// Zoom lost per second.
+pub const ZOOM_DECAY: f32 = 0.4;
+
+pub const HIT_STOP_SPEED: f32 = 0.2;
+pub const HIT_STOP_SECS: f32 = 0.35;
+
+#[derive(Resource, Default)]
+pub struct CameraEffects {
+    /// 0 to 1; the shake grows with its square so small bumps stay subtle.
+    pub trauma: f32,
+    /// Fraction the view is zoomed in by.
+    pub zoom: f32,
+    /// Real seconds of slow motion left.
+    pub hit_stop: f32,
+}
+
+impl CameraEffects {
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).min(1.0);
+    }
+
+    pub fn punch_zoom(&mut self, amount: f32) {
+        self.zoom = self.zoom.max(amount.min(MAX_ZOOM));
+    }
+}
+
+/// Star pickups of the current run, to tell combos apart from single pickups.
+#[derive(Resource, Default)]
+pub struct ComboTracker {
+    pub last_score: u32,
+    pub chain: u32,
+    pub since_pickup: f32,
+}
@@ -0,0 +1,185 @@
+pub mod cues {
+    use super::super::{
+        components::CameraCue,
+        resources::{
+            COMBO_WINDOW, COMBO_ZOOM, CameraEffects, ComboTracker, DEATH_TRAUMA, HIT_STOP_SECS,
+            NEAR_MISS_MARGIN, NEAR_MISS_TRAUMA,
+        },
+    };
+    use crate::game::{
+        components::{
+            enemy::{Enemy, Telegraphing},
+            player::{Invulnerable, PLAYER_SIZE, Player},
+        },
+        resources::{network::NetworkRole, score::Score},
+    };
+    use crate::settings::resources::Settings;
+    use bevy::prelude::*;
+    use std::collections::HashSet;
+
+    pub fn apply_camera_cues(
+        mut commands: Commands,
+        cue_query: Query<(Entity, &CameraCue)>,
+        mut effects: ResMut<CameraEffects>,
+        settings: Res<Settings>,
+        network_role: Res<NetworkRole>,
+    ) {
+        for (entity, cue) in &cue_query {
+            commands.entity(entity).despawn();
+            if !settings.camera_effects {
+                continue;
+            }
+
+            match *cue {
+                CameraCue::Death => {
+                    effects.add_trauma(DEATH_TRAUMA);
+                    // Slowing down a networked match would put the peers out of step.
+                    if *network_role == NetworkRole::Offline {
+                        effects.hit_stop = HIT_STOP_SECS;
+                    }
+                }
+                CameraCue::NearMiss => effects.add_trauma(NEAR_MISS_TRAUMA),
+                CameraCue::Combo { chain } => effects.punch_zoom(COMBO_ZOOM * chain as f32),
+            }
+        }
+    }
+
+    /// Cues a near miss when an enemy leaves a player's surroundings without having
+    /// hit it.
+    pub fn detect_near_misses(
+        mut commands: Commands,
+        player_query: Query<(Entity, &Transform, Has<Invulnerable>), With<Player>>,
+        enemy_query: Query<(Entity, &Transform, &Enemy), Without<Telegraphing>>,
+        mut close_pairs: Local<HashSet<(Entity, Entity)>>,
+    ) {
+        let mut still_close = HashSet::new();
+
+        for (player_entity, player_transform, invulnerable) in &player_query {
+            for (enemy_entity, enemy_transform, enemy) in &enemy_query {
+                let pair = (player_entity, enemy_entity);
+                let touching = PLAYER_SIZE / 2.0 + enemy.radius();
+                let distance = player_transform
+                    .translation
+                    .distance(enemy_transform.translation);
+
+                if distance < touching + NEAR_MISS_MARGIN {
+                    still_close.insert(pair);
+                } else if close_pairs.contains(&pair) && !invulnerable {
+                    commands.spawn(CameraCue::NearMiss);
+                }
+            }
+        }
+
+        *close_pairs = still_close;
+    }
+
+    pub fn detect_combos(
+        mut commands: Commands,
+        mut tracker: ResMut<ComboTracker>,
+        score: Res<Score>,
+        time: Res<Time>,
+    ) {
+        tracker.since_pickup += time.delta_secs();
+
+        if score.value < tracker.last_score {
+            *tracker = ComboTracker::default();
+        }
+        if score.value == tracker.last_score {
+            return;
+        }
+
+        let pickups = score.value - tracker.last_score;
+        tracker.chain = if tracker.since_pickup <= COMBO_WINDOW {
+            tracker.chain + pickups
+        } else {
+            pickups
+        };
+        tracker.last_score = score.value;
+        tracker.since_pickup = 0.0;
+
+        if tracker.chain > 1 {
+            commands.spawn(CameraCue::Combo {
+                chain: tracker.chain,
+            });
+        }
+    }
+
+    pub fn reset_combo(mut tracker: ResMut<ComboTracker>) {
+        *tracker = ComboTracker::default();
+    }
+}
+
+pub mod shake {
+    use super::super::resources::{
+        CameraEffects, HIT_STOP_SPEED, MAX_SHAKE_ANGLE, MAX_SHAKE_OFFSET, SHAKE_FREQUENCY,
+        TRAUMA_DECAY, ZOOM_DECAY,
+    };
+    use crate::settings::resources::Settings;
+    use bevy::prelude::*;
+
+    /// Offsets the camera after `camera_position` has centred it on the arena.
+    pub fn shake_camera(
+        mut camera_query: Query<(&mut Transform, &mut Projection), With<Camera2d>>,
+        mut effects: ResMut<CameraEffects>,
+        settings: Res<Settings>,
+        time: Res<Time<Real>>,
+    ) {
+        let delta = time.delta_secs();
+        if settings.camera_effects {
+            effects.trauma = (effects.trauma - TRAUMA_DECAY * delta).max(0.0);
+            effects.zoom = (effects.zoom - ZOOM_DECAY * delta).max(0.0);
+        } else {
+            *effects = CameraEffects::default();
+        }
+
+        let Ok((mut transform, mut projection)) = camera_query.single_mut() else {
+            return;
+        };
+
+        // Layered sines drift smoothly, unlike fresh random numbers every frame.
+        let shake = effects.trauma * effects.trauma;
+        let t = time.elapsed_secs() * SHAKE_FREQUENCY;
+        let noise = |seed: f32| ((t + seed).sin() + (t * 1.7 + seed * 3.0).sin()) / 2.0;
+
+        transform.translation.x += MAX_SHAKE_OFFSET * shake * noise(0.0);
+        transform.translation.y += MAX_SHAKE_OFFSET * shake * noise(10.0);
+        transform.rotation = Quat::from_rotation_z(MAX_SHAKE_ANGLE * shake * noise(20.0));
+
+        if let Projection::Orthographic(orthographic) = &mut *projection {
+            orthographic.scale = 1.0 - effects.zoom;
+        }
+    }
+
+    pub fn hit_stop(
+        mut effects: ResMut<CameraEffects>,
+        mut virtual_time: ResMut<Time<Virtual>>,
+        real_time: Res<Time<Real>>,
+    ) {
+        effects.hit_stop = (effects.hit_stop - real_time.delta_secs()).max(0.0);
+        let speed = if effects.hit_stop > 0.0 {
+            HIT_STOP_SPEED
+        } else {
+            1.0
+        };
+
+        if virtual_time.relative_speed() != speed {
+            virtual_time.set_relative_speed(speed);
+        }
+    }
+
+    pub fn clear_camera_effects(
+        mut effects: ResMut<CameraEffects>,
+        mut virtual_time: ResMut<Time<Virtual>>,
+        mut camera_query: Query<(&mut Transform, &mut Projection), With<Camera2d>>,
+    ) {
+        *effects = CameraEffects::default();
+        virtual_time.set_relative_speed(1.0);
+
+        if let Ok((mut transform, mut projection)) = camera_query.single_mut() {
+            transform.rotation = Quat::IDENTITY;
+            if let Projection::Orthographic(orthographic) = &mut *projection {
+                orthographic.scale = 1.0;
+            }
+        }
+    }
+}
@@ -0,0 +1,9 @@
+pub mod layout {
+    use bevy::prelude::*;
+
+    #[derive(Component)]
+    pub struct EditorUi;
+
+    #[derive(Component)]
+    pub struct EditorStatusText;
+}
@@ -0,0 +1,19 @@
+use bevy::prelude::*;
+use plugins::*;
+
+mod components;
+mod plugins;
+mod resources;
+mod systems;
+
+pub const EDITOR_FLAG: &str = "--editor";
+
+pub struct EditorPlugin;
+impl Plugin for EditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(EditorStatePlugin)
+            .add_plugins(LayoutPlugin)
+            .add_plugins(EditingPlugin)
+            .add_plugins(TestPlayPlugin);
+    }
+}
@@ -0,0 +1,70 @@
+use super::{
+    resources::{
+        editing::{EditorLevel, EditorTools},
+        test_play::TestPlay,
+    },
+    systems::{drawing::*, editing::*, editor_state::*, layout::*, test_play::*},
+};
+use crate::AppState;
+use bevy::prelude::*;
+
+pub struct EditorStatePlugin;
+impl Plugin for EditorStatePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EditorTools>()
+            .add_systems(
+                OnEnter(AppState::Editor),
+                init_editor_level.run_if(not(resource_exists::<EditorLevel>)),
+            )
+            .add_systems(
+                Update,
+                (switch_level, save_level).run_if(in_state(AppState::Editor)),
+            );
+    }
+}
+
+pub struct LayoutPlugin;
+impl Plugin for LayoutPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Editor), spawn_editor_ui)
+            .add_systems(OnExit(AppState::Editor), despawn_editor_ui)
+            .add_systems(
+                Update,
+                update_status_text.run_if(in_state(AppState::Editor)),
+            );
+    }
+}
+
+pub struct EditingPlugin;
+impl Plugin for EditingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (select_tool, handle_mouse, edit_selection, draw_level)
+                .chain()
+                .run_if(in_state(AppState::Editor)),
+        );
+    }
+}
+
+pub struct TestPlayPlugin;
+impl Plugin for TestPlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Editor), clear_test_play)
+            .add_systems(Update, start_test_play.run_if(in_state(AppState::Editor)))
+            .add_systems(
+                Update,
+                stop_test_play
+                    .run_if(in_state(AppState::Game))
+                    .run_if(resource_exists::<TestPlay>),
+            )
+            .add_systems(
+                OnEnter(AppState::GameOver),
+                return_to_editor.run_if(resource_exists::<TestPlay>),
+            )
+            .add_systems(
+                OnEnter(AppState::MainMenu),
+                return_to_editor.run_if(resource_exists::<TestPlay>),
+            );
+    }
+}
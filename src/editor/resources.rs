@@ -0,0 +1,232 @@
+pub mod editing {
+    use crate::game::resources::level::{LevelData, ObstacleData, ObstacleShape, Point, SpawnZone};
+    use bevy::prelude::*;
+
+    pub const GRID_SIZE: f32 = 40.0;
+    pub const PLAYER_START_RADIUS: f32 = 16.0;
+    pub const MIN_ITEM_SIZE: f32 = 10.0;
+    pub const ZONE_EDGE_WIDTH: f32 = 12.0;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum EditorTool {
+        #[default]
+        Rect,
+        Circle,
+        Bumper,
+        StarZone,
+        EnemyZone,
+        PlayerStart,
+    }
+
+    impl EditorTool {
+        pub fn label(&self) -> &'static str {
+            match self {
+                EditorTool::Rect => "Rect",
+                EditorTool::Circle => "Circle",
+                EditorTool::Bumper => "Bumper",
+                EditorTool::StarZone => "Star Zone",
+                EditorTool::EnemyZone => "Enemy Zone",
+                EditorTool::PlayerStart => "Player Start",
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum EditorItem {
+        Obstacle(usize),
+        StarZone(usize),
+        EnemyZone(usize),
+        PlayerStart(usize),
+    }
+
+    #[derive(Resource, Default)]
+    pub struct EditorTools {
+        pub tool: EditorTool,
+        pub snap_to_grid: bool,
+        pub selection: Option<EditorItem>,
+        pub drag_offset: Option<Vec2>,
+    }
+
+    impl EditorTools {
+        pub fn snap(&self, point: Vec2) -> Vec2 {
+            if self.snap_to_grid {
+                (point / GRID_SIZE).round() * GRID_SIZE
+            } else {
+                point
+            }
+        }
+    }
+
+    /// Working copy of the level being edited, kept alive while test-playing.
+    #[derive(Resource)]
+    pub struct EditorLevel {
+        pub index: usize,
+        pub data: LevelData,
+        pub dirty: bool,
+    }
+
+    impl EditorLevel {
+        pub fn place(&mut self, tool: EditorTool, point: Vec2) -> EditorItem {
+            let position = Point::from(point);
+            let zone = SpawnZone {
+                position,
+                width: 200.0,
+                height: 160.0,
+            };
+            self.dirty = true;
+
+            match tool {
+                EditorTool::Rect => self.push_obstacle(
+                    ObstacleShape::Rect {
+                        width: 120.0,
+                        height: 40.0,
+                    },
+                    position,
+                ),
+                EditorTool::Circle => {
+                    self.push_obstacle(ObstacleShape::Circle { radius: 40.0 }, position)
+                }
+                EditorTool::Bumper => self.push_obstacle(
+                    ObstacleShape::Bumper {
+                        radius: 32.0,
+                        strength: 600.0,
+                    },
+                    position,
+                ),
+                EditorTool::StarZone => {
+                    self.data.star_zones.push(zone);
+                    EditorItem::StarZone(self.data.star_zones.len() - 1)
+                }
+                EditorTool::EnemyZone => {
+                    self.data.enemy_zones.push(zone);
+                    EditorItem::EnemyZone(self.data.enemy_zones.len() - 1)
+                }
+                EditorTool::PlayerStart => {
+                    self.data.player_starts.push(position);
+                    EditorItem::PlayerStart(self.data.player_starts.len() - 1)
+                }
+            }
+        }
+
+        fn push_obstacle(&mut self, shape: ObstacleShape, position: Point) -> EditorItem {
+            self.data.obstacles.push(ObstacleData { shape, position });
+            EditorItem::Obstacle(self.data.obstacles.len() - 1)
+        }
+
+        /// Topmost item under `point`: player starts first, then obstacles, then zones.
+        /// Zones are only picked by their edge, so clicking inside one still places items.
+        pub fn item_at(&self, point: Vec2) -> Option<EditorItem> {
+            let start = self
+                .data
+                .player_starts
+                .iter()
+                .rposition(|start| Vec2::from(*start).distance(point) <= PLAYER_START_RADIUS);
+            if let Some(index) = start {
+                return Some(EditorItem::PlayerStart(index));
+            }
+
+            let obstacle = self.data.obstacles.iter().rposition(|obstacle| {
+                obstacle
+                    .shape
+                    .circle_contact(obstacle.position.into(), point, 0.0)
+                    .is_some()
+            });
+            if let Some(index) = obstacle {
+                return Some(EditorItem::Obstacle(index));
+            }
+
+            let on_edge = |zone: &SpawnZone| {
+                let offset = (point - Vec2::from(zone.position)).abs();
+                let half_size = Vec2::new(zone.width, zone.height) / 2.0;
+                let outer = half_size + ZONE_EDGE_WIDTH / 2.0;
+                let inner = half_size - ZONE_EDGE_WIDTH / 2.0;
+                offset.x <= outer.x
+                    && offset.y <= outer.y
+                    && (offset.x >= inner.x || offset.y >= inner.y)
+            };
+            if let Some(index) = self.data.star_zones.iter().rposition(on_edge) {
+                return Some(EditorItem::StarZone(index));
+            }
+            self.data
+                .enemy_zones
+                .iter()
+                .rposition(on_edge)
+                .map(EditorItem::EnemyZone)
+        }
+
+        pub fn position(&self, item: EditorItem) -> Vec2 {
+            match item {
+                EditorItem::Obstacle(index) => self.data.obstacles[index].position,
+                EditorItem::StarZone(index) => self.data.star_zones[index].position,
+                EditorItem::EnemyZone(index) => self.data.enemy_zones[index].position,
+                EditorItem::PlayerStart(index) => self.data.player_starts[index],
+            }
+            .into()
+        }
+
+        pub fn set_position(&mut self, item: EditorItem, point: Vec2) {
+            let position = Point::from(point);
+            match item {
+                EditorItem::Obstacle(index) => self.data.obstacles[index].position = position,
+                EditorItem::StarZone(index) => self.data.star_zones[index].position = position,
+                EditorItem::EnemyZone(index) => self.data.enemy_zones[index].position = position,
+                EditorItem::PlayerStart(index) => self.data.player_starts[index] = position,
+            }
+            self.dirty = true;
+        }
+
+        /// Grows (positive `amount`) or shrinks the item, keeping a minimum size.
+        pub fn resize(&mut self, item: EditorItem, amount: f32) {
+            let grow = |value: &mut f32| *value = (*value + amount).max(MIN_ITEM_SIZE);
+            match item {
+                EditorItem::Obstacle(index) => match &mut self.data.obstacles[index].shape {
+                    ObstacleShape::Rect { width, height } => {
+                        grow(width);
+                        grow(height);
+                    }
+                    ObstacleShape::Circle { radius } | ObstacleShape::Bumper { radius, .. } => {
+                        grow(radius)
+                    }
+                },
+                EditorItem::StarZone(index) => {
+                    let zone = &mut self.data.star_zones[index];
+                    grow(&mut zone.width);
+                    grow(&mut zone.height);
+                }
+                EditorItem::EnemyZone(index) => {
+                    let zone = &mut self.data.enemy_zones[index];
+                    grow(&mut zone.width);
+                    grow(&mut zone.height);
+                }
+                EditorItem::PlayerStart(_) => return,
+            }
+            self.dirty = true;
+        }
+
+        pub fn remove(&mut self, item: EditorItem) {
+            match item {
+                EditorItem::Obstacle(index) => {
+                    self.data.obstacles.remove(index);
+                }
+                EditorItem::StarZone(index) => {
+                    self.data.star_zones.remove(index);
+                }
+                EditorItem::EnemyZone(index) => {
+                    self.data.enemy_zones.remove(index);
+                }
+                EditorItem::PlayerStart(index) => {
+                    self.data.player_starts.remove(index);
+                }
+            }
+            self.dirty = true;
+        }
+    }
+}
+
+pub mod test_play {
+    use bevy::prelude::*;
+
+    /// Present while a level is being test-played from the editor.
+    #[derive(Resource)]
+    pub struct TestPlay;
+}
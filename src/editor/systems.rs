@@ -0,0 +1,406 @@
+pub mod editor_state {
+    use super::super::resources::editing::{EditorLevel, EditorTools};
+    use crate::game::resources::level::{LevelLibrary, SelectedLevel};
+    use bevy::prelude::*;
+
+    pub fn init_editor_level(
+        mut commands: Commands,
+        level_library: Res<LevelLibrary>,
+        selected_level: Res<SelectedLevel>,
+    ) {
+        let index = selected_level.index % level_library.levels.len();
+
+        commands.insert_resource(EditorLevel {
+            index,
+            data: level_library.levels[index].data.clone(),
+            dirty: false,
+        });
+    }
+
+    pub fn switch_level(
+        keyboard_input: Res<ButtonInput<KeyCode>>,
+        mut level_library: ResMut<LevelLibrary>,
+        mut selected_level: ResMut<SelectedLevel>,
+        mut editor_level: ResMut<EditorLevel>,
+        mut editor_tools: ResMut<EditorTools>,
+    ) {
+        let ctrl = keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+
+        let index = if ctrl && keyboard_input.just_pressed(KeyCode::KeyN) {
+            level_library.add_level()
+        } else if keyboard_input.just_pressed(KeyCode::Tab) {
+            (editor_level.index + 1) % level_library.levels.len()
+        } else {
+            return;
+        };
+
+        if editor_level.dirty {
+            warn!(
+                "Discarding unsaved changes to \"{}\"",
+                editor_level.data.name
+            );
+        }
+
+        selected_level.index = index;
+        *editor_level = EditorLevel {
+            index,
+            data: level_library.levels[index].data.clone(),
+            dirty: false,
+        };
+        editor_tools.selection = None;
+        editor_tools.drag_offset = None;
+    }
+
+    pub fn save_level(
+        keyboard_input: Res<ButtonInput<KeyCode>>,
+        mut level_library: ResMut<LevelLibrary>,
+        mut editor_level: ResMut<EditorLevel>,
+    ) {
+        let ctrl = keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+        if !(ctrl && keyboard_input.just_pressed(KeyCode::KeyS)) {
+            return;
+        }
+
+        let entry = &mut level_library.levels[editor_level.index];
+        let Some(path) = &entry.path else {
+            warn!(
+                "\"{}\" came from the network and can't be saved",
+                entry.data.name
+            );
+            return;
+        };
+        match LevelLibrary::write_level(path, &editor_level.data) {
+            Ok(()) => {
+                info!("Saved level to {}", path.display());
+                entry.data = editor_level.data.clone();
+                editor_level.dirty = false;
+            }
+            Err(err) => error!("Could not save level to {}: {err}", path.display()),
+        }
+    }
+}
+
+pub mod layout {
+    use super::super::components::layout::{EditorStatusText, EditorUi};
+    use super::super::resources::editing::{EditorLevel, EditorTools};
+    use crate::widgets::components::Themed;
+    use bevy::prelude::*;
+
+    pub const HELP_TEXT: &str = "[1-6] Tool  [G] Snap  [LMB] Place / Move  [RMB / Del] Delete  \
+        [+/-] Resize  [Arrows] Nudge  [Tab] Next Level  [Ctrl+N] New  [Ctrl+S] Save  [F5] Test Play";
+
+    pub fn spawn_editor_ui(mut commands: Commands) {
+        commands
+            .spawn((
+                EditorUi,
+                Node {
+                    width: Val::Percent(100.0),
+                    padding: UiRect::all(Val::Px(8.0)),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(4.0),
+                    ..Default::default()
+                },
+                Themed::HudBox,
+            ))
+            .with_children(|p| {
+                p.spawn((EditorStatusText, Themed::Caption, Text::new("")));
+                p.spawn((Themed::Hint, Text::new(HELP_TEXT)));
+            });
+    }
+
+    pub fn despawn_editor_ui(mut commands: Commands, editor_ui: Query<Entity, With<EditorUi>>) {
+        if let Ok(entity) = editor_ui.single() {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    pub fn update_status_text(
+        mut status_text: Query<&mut Text, With<EditorStatusText>>,
+        editor_level: Res<EditorLevel>,
+        editor_tools: Res<EditorTools>,
+    ) {
+        if let Ok(mut text) = status_text.single_mut() {
+            let dirty = if editor_level.dirty { "*" } else { "" };
+            let snap = if editor_tools.snap_to_grid {
+                "On"
+            } else {
+                "Off"
+            };
+
+            text.0 = format!(
+                "Level: {}{dirty}   Tool: {}   Snap: {snap}",
+                editor_level.data.name,
+                editor_tools.tool.label()
+            );
+        }
+    }
+}
+
+pub mod editing {
+    use super::super::resources::editing::{EditorLevel, EditorTool, EditorTools, GRID_SIZE};
+    use bevy::{prelude::*, window::PrimaryWindow};
+
+    pub fn select_tool(
+        keyboard_input: Res<ButtonInput<KeyCode>>,
+        mut editor_tools: ResMut<EditorTools>,
+    ) {
+        let tools = [
+            (KeyCode::Digit1, EditorTool::Rect),
+            (KeyCode::Digit2, EditorTool::Circle),
+            (KeyCode::Digit3, EditorTool::Bumper),
+            (KeyCode::Digit4, EditorTool::StarZone),
+            (KeyCode::Digit5, EditorTool::EnemyZone),
+            (KeyCode::Digit6, EditorTool::PlayerStart),
+        ];
+
+        for (key, tool) in tools {
+            if keyboard_input.just_pressed(key) {
+                editor_tools.tool = tool;
+            }
+        }
+
+        if keyboard_input.just_pressed(KeyCode::KeyG) {
+            editor_tools.snap_to_grid = !editor_tools.snap_to_grid;
+        }
+    }
+
+    pub fn handle_mouse(
+        mouse_input: Res<ButtonInput<MouseButton>>,
+        window_query: Query<&Window, With<PrimaryWindow>>,
+        camera_query: Query<(&Camera, &GlobalTransform)>,
+        mut editor_level: ResMut<EditorLevel>,
+        mut editor_tools: ResMut<EditorTools>,
+    ) {
+        let (Ok(window), Ok((camera, camera_transform))) =
+            (window_query.single(), camera_query.single())
+        else {
+            return;
+        };
+        let Some(cursor) = window
+            .cursor_position()
+            .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok())
+        else {
+            return;
+        };
+
+        if mouse_input.just_pressed(MouseButton::Left) {
+            let item = match editor_level.item_at(cursor) {
+                Some(item) => item,
+                None => editor_level.place(editor_tools.tool, editor_tools.snap(cursor)),
+            };
+
+            editor_tools.selection = Some(item);
+            editor_tools.drag_offset = Some(editor_level.position(item) - cursor);
+        } else if mouse_input.pressed(MouseButton::Left) {
+            if let (Some(item), Some(offset)) = (editor_tools.selection, editor_tools.drag_offset) {
+                let position = editor_tools.snap(cursor + offset);
+                if editor_level.position(item) != position {
+                    editor_level.set_position(item, position);
+                }
+            }
+        } else {
+            editor_tools.drag_offset = None;
+        }
+
+        if mouse_input.just_pressed(MouseButton::Right)
+            && let Some(item) = editor_level.item_at(cursor)
+        {
+            editor_level.remove(item);
+            editor_tools.selection = None;
+            editor_tools.drag_offset = None;
+        }
+    }
+
+    pub fn edit_selection(
+        keyboard_input: Res<ButtonInput<KeyCode>>,
+        mut editor_level: ResMut<EditorLevel>,
+        mut editor_tools: ResMut<EditorTools>,
+    ) {
+        let Some(item) = editor_tools.selection else {
+            return;
+        };
+
+        if keyboard_input.any_just_pressed([KeyCode::Delete, KeyCode::Backspace]) {
+            editor_level.remove(item);
+            editor_tools.selection = None;
+            editor_tools.drag_offset = None;
+            return;
+        }
+
+        if keyboard_input.any_just_pressed([KeyCode::Equal, KeyCode::NumpadAdd]) {
+            editor_level.resize(item, GRID_SIZE / 2.0);
+        }
+        if keyboard_input.any_just_pressed([KeyCode::Minus, KeyCode::NumpadSubtract]) {
+            editor_level.resize(item, -GRID_SIZE / 2.0);
+        }
+
+        let step = if editor_tools.snap_to_grid {
+            GRID_SIZE
+        } else {
+            1.0
+        };
+        let mut nudge = Vec2::ZERO;
+        if keyboard_input.just_pressed(KeyCode::ArrowLeft) {
+            nudge.x -= step;
+        }
+        if keyboard_input.just_pressed(KeyCode::ArrowRight) {
+            nudge.x += step;
+        }
+        if keyboard_input.just_pressed(KeyCode::ArrowDown) {
+            nudge.y -= step;
+        }
+        if keyboard_input.just_pressed(KeyCode::ArrowUp) {
+            nudge.y += step;
+        }
+        if nudge != Vec2::ZERO {
+            let position = editor_level.position(item) + nudge;
+            editor_level.set_position(item, position);
+        }
+    }
+}
+
+pub mod drawing {
+    use super::super::resources::editing::{
+        EditorItem, EditorLevel, EditorTools, GRID_SIZE, PLAYER_START_RADIUS,
+    };
+    use crate::game::resources::{
+        arena::Arena,
+        level::{ObstacleShape, SpawnZone},
+    };
+    use bevy::prelude::*;
+
+    pub const GRID_COLOR: Color = Color::linear_rgba(1.0, 1.0, 1.0, 0.05);
+    pub const OBSTACLE_COLOR: Color = Color::linear_rgb(0.6, 0.65, 0.8);
+    pub const BUMPER_COLOR: Color = Color::linear_rgb(0.95, 0.6, 0.1);
+    pub const STAR_ZONE_COLOR: Color = Color::linear_rgb(0.95, 0.85, 0.2);
+    pub const ENEMY_ZONE_COLOR: Color = Color::linear_rgb(0.9, 0.2, 0.2);
+    pub const PLAYER_START_COLOR: Color = Color::linear_rgb(0.2, 0.5, 1.0);
+    pub const SELECTION_COLOR: Color = Color::WHITE;
+
+    pub fn draw_level(
+        mut gizmos: Gizmos,
+        arena: Res<Arena>,
+        editor_level: Res<EditorLevel>,
+        editor_tools: Res<EditorTools>,
+    ) {
+        gizmos.grid_2d(
+            arena.center(),
+            (arena.size / GRID_SIZE).ceil().as_uvec2(),
+            Vec2::splat(GRID_SIZE),
+            GRID_COLOR,
+        );
+
+        let color_for = |item: EditorItem, color: Color| {
+            if editor_tools.selection == Some(item) {
+                SELECTION_COLOR
+            } else {
+                color
+            }
+        };
+        let mut draw_zone = |zone: &SpawnZone, color: Color| {
+            gizmos.rect_2d(
+                Vec2::from(zone.position),
+                Vec2::new(zone.width, zone.height),
+                color,
+            );
+        };
+
+        for (index, zone) in editor_level.data.star_zones.iter().enumerate() {
+            draw_zone(
+                zone,
+                color_for(EditorItem::StarZone(index), STAR_ZONE_COLOR),
+            );
+        }
+        for (index, zone) in editor_level.data.enemy_zones.iter().enumerate() {
+            draw_zone(
+                zone,
+                color_for(EditorItem::EnemyZone(index), ENEMY_ZONE_COLOR),
+            );
+        }
+
+        for (index, obstacle) in editor_level.data.obstacles.iter().enumerate() {
+            let position = Vec2::from(obstacle.position);
+            match obstacle.shape {
+                ObstacleShape::Rect { width, height } => {
+                    gizmos.rect_2d(
+                        position,
+                        Vec2::new(width, height),
+                        color_for(EditorItem::Obstacle(index), OBSTACLE_COLOR),
+                    );
+                }
+                ObstacleShape::Circle { radius } => {
+                    gizmos.circle_2d(
+                        position,
+                        radius,
+                        color_for(EditorItem::Obstacle(index), OBSTACLE_COLOR),
+                    );
+                }
+                ObstacleShape::Bumper { radius, .. } => {
+                    let color = color_for(EditorItem::Obstacle(index), BUMPER_COLOR);
+                    gizmos.circle_2d(position, radius, color);
+                    gizmos.circle_2d(position, radius * 0.6, color);
+                }
+            }
+        }
+
+        for (index, start) in editor_level.data.player_starts.iter().enumerate() {
+            let color = color_for(EditorItem::PlayerStart(index), PLAYER_START_COLOR);
+            gizmos.circle_2d(Vec2::from(*start), PLAYER_START_RADIUS, color);
+            gizmos.cross_2d(Vec2::from(*start), PLAYER_START_RADIUS, color);
+        }
+    }
+}
+
+pub mod test_play {
+    use super::super::resources::{editing::EditorLevel, test_play::TestPlay};
+    use crate::{
+        AppState,
+        game::resources::level::{LevelLibrary, SelectedLevel},
+        transition::messages::{TransitionEffect, TransitionRequest},
+    };
+    use bevy::prelude::*;
+
+    pub fn start_test_play(
+        mut commands: Commands,
+        keyboard_input: Res<ButtonInput<KeyCode>>,
+        editor_level: Res<EditorLevel>,
+        mut level_library: ResMut<LevelLibrary>,
+        mut selected_level: ResMut<SelectedLevel>,
+        mut transition_writer: MessageWriter<TransitionRequest>,
+    ) {
+        if keyboard_input.just_pressed(KeyCode::F5) {
+            // The game reads levels from the library, so test-play the unsaved working copy.
+            level_library.levels[editor_level.index].data = editor_level.data.clone();
+            selected_level.index = editor_level.index;
+
+            commands.insert_resource(TestPlay);
+            transition_writer.write(TransitionRequest::new(
+                AppState::Game,
+                TransitionEffect::Wipe,
+            ));
+        }
+    }
+
+    pub fn stop_test_play(
+        keyboard_input: Res<ButtonInput<KeyCode>>,
+        mut transition_writer: MessageWriter<TransitionRequest>,
+    ) {
+        if keyboard_input.just_pressed(KeyCode::F5) {
+            transition_writer.write(TransitionRequest::new(
+                AppState::Editor,
+                TransitionEffect::Wipe,
+            ));
+        }
+    }
+
+    pub fn return_to_editor(mut transition_writer: MessageWriter<TransitionRequest>) {
+        transition_writer.write(TransitionRequest::new(
+            AppState::Editor,
+            TransitionEffect::Wipe,
+        ));
+    }
+
+    pub fn clear_test_play(mut commands: Commands) {
+        commands.remove_resource::<TestPlay>();
+    }
+}
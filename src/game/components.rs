@@ -3,61 +3,220 @@ pub mod player {
 
     pub const PLAYER_SPEED: f32 = 500.0;
     pub const PLAYER_SIZE: f32 = 64.0;
-    #[derive(Component)]
-    pub struct Player {}
+    pub const PLAYER_ACCELERATION: f32 = 1800.0;
+    pub const PLAYER_FRICTION: f32 = 700.0;
+    pub const PLAYER_TURN_RATE: f32 = 4.0;
+    pub const PLAYER_WALL_RESTITUTION: f32 = 0.8;
+    pub const PLAYER_INVULNERABLE_TIME: f32 = 2.0;
+    /// How long a bumper kick counts as a dash.
+    pub const PLAYER_BOOST_TIME: f32 = 0.4;
+    pub const MAX_PLAYERS: usize = 2;
+    /// HUD colour of each player, matching their ball.
+    pub const PLAYER_COLORS: [Color; MAX_PLAYERS] =
+        [Color::WHITE, Color::linear_rgb(0.3, 1.0, 0.3)];
+
+    pub const WASD_KEYS: MovementKeys = MovementKeys {
+        up: KeyCode::KeyW,
+        down: KeyCode::KeyS,
+        left: KeyCode::KeyA,
+        right: KeyCode::KeyD,
+    };
+    pub const ARROW_KEYS: MovementKeys = MovementKeys {
+        up: KeyCode::ArrowUp,
+        down: KeyCode::ArrowDown,
+        left: KeyCode::ArrowLeft,
+        right: KeyCode::ArrowRight,
+    };
 
     #[derive(Component)]
-    pub struct ExplosionSoundPlayer {}
-    impl ExplosionSoundPlayer {
-        pub fn new(
-            asset_serve: &Res<'_, AssetServer>,
-        ) -> (ExplosionSoundPlayer, AudioPlayer, PlaybackSettings) {
-            (
-                ExplosionSoundPlayer {},
-                AudioPlayer::new(asset_serve.load("audio/assets_audio_explosionCrunch_000.oga")),
-                PlaybackSettings {
-                    mode: bevy::audio::PlaybackMode::Despawn,
-                    ..Default::default()
-                },
-            )
+    pub struct Player {
+        pub index: usize,
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    pub struct MovementKeys {
+        pub up: KeyCode,
+        pub down: KeyCode,
+        pub left: KeyCode,
+        pub right: KeyCode,
+    }
+
+    #[derive(Component, Clone)]
+    pub struct PlayerControls {
+        pub keys: Vec<MovementKeys>,
+        /// Position of the gamepad in connection order.
+        pub gamepad: usize,
+    }
+
+    impl PlayerControls {
+        /// A lone player can use both key sets, otherwise player one gets WASD and
+        /// player two the arrows. Each player also reads the gamepad matching its index.
+        pub fn for_player(index: usize, player_count: usize) -> Self {
+            let keys = if player_count == 1 {
+                vec![WASD_KEYS, ARROW_KEYS]
+            } else if index == 0 {
+                vec![WASD_KEYS]
+            } else {
+                vec![ARROW_KEYS]
+            };
+
+            Self {
+                keys,
+                gamepad: index,
+            }
+        }
+
+        pub fn direction(
+            &self,
+            keyboard_input: &ButtonInput<KeyCode>,
+            gamepads: &[&Gamepad],
+        ) -> Vec2 {
+            let mut dir = Vec2::ZERO;
+
+            for keys in &self.keys {
+                if keyboard_input.pressed(keys.left) {
+                    dir += Vec2::new(-1.0, 0.0);
+                }
+                if keyboard_input.pressed(keys.right) {
+                    dir += Vec2::new(1.0, 0.0);
+                }
+                if keyboard_input.pressed(keys.down) {
+                    dir += Vec2::new(0.0, -1.0);
+                }
+                if keyboard_input.pressed(keys.up) {
+                    dir += Vec2::new(0.0, 1.0);
+                }
+            }
+
+            if let Some(gamepad) = gamepads.get(self.gamepad) {
+                dir += gamepad.left_stick() + gamepad.dpad();
+            }
+
+            if dir.length() > 1.0 {
+                dir = dir.normalize();
+            }
+
+            dir
         }
     }
+
+    /// Direction received over the network or from the rollback input buffer, used
+    /// instead of local controls.
+    #[derive(Component, Default)]
+    pub struct RemoteInput {
+        pub direction: Vec2,
+    }
+
+    #[derive(Component, Clone)]
+    pub struct Invulnerable {
+        pub timer: Timer,
+    }
+
+    impl Default for Invulnerable {
+        fn default() -> Self {
+            Self {
+                timer: Timer::from_seconds(PLAYER_INVULNERABLE_TIME, TimerMode::Once),
+            }
+        }
+    }
+
+    /// Set for a moment after a bumper kicks the player, the game's only dash.
+    #[derive(Component, Clone)]
+    pub struct Boosted {
+        pub timer: Timer,
+    }
+
+    impl Default for Boosted {
+        fn default() -> Self {
+            Self {
+                timer: Timer::from_seconds(PLAYER_BOOST_TIME, TimerMode::Once),
+            }
+        }
+    }
+
+    #[derive(Component, Clone, Copy, Default)]
+    pub struct Velocity {
+        pub value: Vec2,
+    }
 }
 
 pub mod enemy {
+    use crate::animation::resources::{ClipKind, SheetKind};
     use bevy::prelude::*;
-    use rand::random;
 
     pub const NUMBER_OF_ENEMIES: usize = 2;
     pub const ENEMY_SPEED: f32 = 200.0;
     pub const ENEMY_SIZE: f32 = 64.0;
+    pub const ENEMY_MIN_SIZE: f32 = 48.0;
+    pub const ENEMY_MAX_SIZE: f32 = 88.0;
     pub const ENEMY_SPAWN_TIME: f32 = 5.0;
+    /// Mass of an enemy of `ENEMY_SIZE`; bigger ones are heavier by area.
+    pub const ENEMY_MASS: f32 = 1.0;
+    pub const ENEMY_MIN_SPEED: f32 = 100.0;
+    pub const ENEMY_MAX_SPEED: f32 = 400.0;
+    /// How quickly (per second) a speed outside the steady range settles back into it.
+    pub const ENEMY_SPEED_RECOVERY: f32 = 1.5;
+    pub const ENEMY_COLLISION_ITERATIONS: usize = 4;
 
-    #[derive(Component)]
+    #[derive(Component, Clone, Copy)]
     pub struct Enemy {
         pub direction: Vec2,
+        pub speed: f32,
+        pub size: f32,
+        pub mass: f32,
     }
 
-    #[derive(Component)]
-    pub struct BouncEnemySound {}
-    impl BouncEnemySound {
-        pub fn new(
-            asset_server: &Res<'_, AssetServer>,
-        ) -> (BouncEnemySound, AudioPlayer, PlaybackSettings) {
-            let audio = if random::<f32>() < 0.5 {
-                asset_server.load("audio/pluck_001.ogg")
-            } else {
-                asset_server.load("audio/pluck_002.ogg")
-            };
+    impl Enemy {
+        pub fn new(direction: Vec2, size: f32) -> Self {
+            Self {
+                direction,
+                speed: ENEMY_SPEED,
+                size,
+                mass: ENEMY_MASS * (size / ENEMY_SIZE).powi(2),
+            }
+        }
 
-            (
-                BouncEnemySound {},
-                AudioPlayer::new(audio),
-                PlaybackSettings {
-                    mode: bevy::audio::PlaybackMode::Despawn,
-                    ..Default::default()
-                },
-            )
+        pub fn radius(&self) -> f32 {
+            self.size / 2.0
+        }
+
+        pub fn velocity(&self) -> Vec2 {
+            self.direction * self.speed
+        }
+
+        /// Splits `velocity` back into direction and speed. Collisions keep their full
+        /// result; `settle_speed` brings it back into the steady range over time.
+        pub fn set_velocity(&mut self, velocity: Vec2) {
+            if let Some(direction) = velocity.try_normalize() {
+                self.direction = direction;
+            }
+            self.speed = velocity.length();
+        }
+
+        /// Eases the speed back into `ENEMY_MIN_SPEED..=ENEMY_MAX_SPEED` so chains of
+        /// collisions can't freeze or launch an enemy for good.
+        pub fn settle_speed(&mut self, delta_secs: f32) {
+            let steady = self.speed.clamp(ENEMY_MIN_SPEED, ENEMY_MAX_SPEED);
+            let keep = (-ENEMY_SPEED_RECOVERY * delta_secs).exp();
+            self.speed = steady + (self.speed - steady) * keep;
+        }
+    }
+
+    /// Set on enemies spawned mid-run while their spawn-in clip plays: they stay put
+    /// and can't hurt anyone until players have seen where they appear.
+    #[derive(Component, Clone)]
+    pub struct Telegraphing {
+        pub timer: Timer,
+    }
+
+    impl Default for Telegraphing {
+        fn default() -> Self {
+            let secs = SheetKind::Enemy
+                .clip(ClipKind::SpawnIn)
+                .map_or(0.0, |clip| clip.duration());
+            Self {
+                timer: Timer::from_seconds(secs, TimerMode::Once),
+            }
         }
     }
 }
@@ -71,11 +230,18 @@ pub mod star {
 
     #[derive(Component)]
     pub struct Star {}
+}
+
+pub mod obstacle {
+    use super::super::resources::level::ObstacleShape;
+    use bevy::prelude::*;
+
+    pub const OBSTACLE_COLOR: Color = Color::linear_rgb(0.3, 0.32, 0.4);
+    pub const BUMPER_COLOR: Color = Color::linear_rgb(0.95, 0.6, 0.1);
 
-    #[derive(Bundle)]
-    pub struct CollectStarSound {
-        pub audio: AudioPlayer,
-        pub settings: PlaybackSettings,
+    #[derive(Component)]
+    pub struct Obstacle {
+        pub shape: ObstacleShape,
     }
 }
 
@@ -84,11 +250,19 @@ pub mod ui {
         use bevy::prelude::*;
 
         #[derive(Component)]
-        pub struct HUD;
+        pub struct Hud;
 
         #[derive(Component)]
         pub struct Score;
 
+        #[derive(Component)]
+        pub struct PlayerScore {
+            pub index: usize,
+        }
+
+        #[derive(Component)]
+        pub struct Lives;
+
         #[derive(Component)]
         pub struct Enemy;
     }
@@ -98,15 +272,19 @@ pub mod ui {
 
         #[derive(Component)]
         pub struct PauseMenuContainer;
+    }
 
-        #[derive(Component)]
-        pub struct ButtonResume;
+    pub mod countdown {
+        use bevy::prelude::*;
 
         #[derive(Component)]
-        pub struct ButtonMainMenu;
+        pub struct CountdownOverlay;
 
-        #[derive(Component)]
-        pub struct ButtonQuit;
+        /// Seconds since the number on screen last changed, for its pop-in animation.
+        #[derive(Component, Default)]
+        pub struct CountdownText {
+            pub shown_for: f32,
+        }
     }
 
     pub mod gameover {
@@ -114,14 +292,5 @@ pub mod ui {
 
         #[derive(Component)]
         pub struct GameOverContainer;
-
-        #[derive(Component)]
-        pub struct ButtonRestart;
-
-        #[derive(Component)]
-        pub struct ButtonMainMenu;
-
-        #[derive(Component)]
-        pub struct ButtonQuit;
     }
 }
@@ -1,8 +1,18 @@
 pub mod game_states {
     use bevy::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+    pub struct PlayerResult {
+        pub index: usize,
+        pub score: u32,
+        /// Only set in versus mode, for the last ball standing.
+        pub winner: bool,
+    }
 
     #[derive(Message)]
     pub struct GameOver {
         pub score: u32,
+        pub results: Vec<PlayerResult>,
     }
 }
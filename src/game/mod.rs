@@ -1,25 +1,36 @@
-use bevy::prelude::*;
+use bevy::{ecs::schedule::ExecutorKind, prelude::*};
 
-mod components;
-mod messages;
+pub mod components;
+pub mod messages;
 mod plugins;
-mod resources;
-mod states;
-mod systems;
+pub mod resources;
+pub mod schedules;
+pub mod states;
+pub(crate) mod systems;
 
 use plugins::*;
+use schedules::*;
 use states::*;
 
 pub struct GamePlugin;
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
-        app.init_state::<SimulationState>()
+        // Single threaded so systems without an explicit order always run in the same
+        // order, which keeps re-simulated ticks identical.
+        app.init_schedule(GameplayStep)
+            .edit_schedule(GameplayStep, |schedule| {
+                schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+            })
+            .add_sub_state::<SimulationState>()
             .add_plugins(UIPlugin)
             .add_plugins(GameStatePlugin)
             .add_plugins(TimersPlugin)
+            .add_plugins(LevelPlugin)
             .add_plugins(CameraPlugin)
             .add_plugins(PlayerPlugin)
             .add_plugins(EnemyPlugin)
-            .add_plugins(StarPlugin);
+            .add_plugins(StarPlugin)
+            .add_plugins(ReplayPlugin)
+            .add_plugins(CountdownPlugin);
     }
 }
@@ -1,25 +1,53 @@
 use super::{
     messages::game_states::GameOver,
     resources::{
-        score::{HighScores, Score},
+        arena::Arena,
+        controls::{ControlScheme, MovementConfig},
+        countdown::Countdown,
+        level::{LevelLibrary, SelectedLevel},
+        mode::{GameMode, Lives},
+        network::NetworkRole,
+        random::{GameRng, MatchSeed},
+        replay::Replay,
+        score::{HighScores, RunResults, Score},
         timers::{EnemySpawnTimer, StarSpawnTimer},
     },
-    systems::{camera::*, enemy::*, game_state::*, player::*, star::*, timers::*, ui::*},
+    systems::{
+        camera::*,
+        enemy::*,
+        game_state::*,
+        network::*,
+        obstacle::*,
+        player::*,
+        replay::*,
+        star::*,
+        timers::*,
+        ui::{countdown::*, *},
+    },
+};
+use crate::{
+    AppState,
+    game::{schedules::GameplayStep, states::SimulationState},
 };
-use crate::{AppState, game::states::SimulationState};
 use bevy::prelude::*;
 
 pub struct EnemyPlugin;
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<EnemySpawnTimer>()
-            .add_systems(OnEnter(AppState::Game), spawn_enemies)
             .add_systems(
-                Update,
+                OnEnter(AppState::Game),
+                spawn_enemies.after(prepare_run).run_if(runs_simulation),
+            )
+            .add_systems(
+                GameplayStep,
                 (
+                    tick_telegraphs,
                     confine_enemy,
                     enemy_movement,
                     update_enemy_direction,
+                    enemy_hit_enemy,
+                    enemy_hit_obstacle,
                     enemy_hit_player,
                     spawn_enemys_over_time,
                 )
@@ -34,30 +62,54 @@ impl Plugin for EnemyPlugin {
 pub struct PlayerPlugin;
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
-        app.configure_sets(
-            Update,
-            (PlayerStateSet::Movement, PlayerStateSet::Confine).chain(),
-        )
-        .add_systems(OnEnter(AppState::Game), spawn_player)
-        .add_systems(
-            Update,
-            (
-                player_hit_star,
-                player_movement.in_set(PlayerStateSet::Movement),
-                confine_player.in_set(PlayerStateSet::Confine),
+        app.init_resource::<ControlScheme>()
+            .init_resource::<MovementConfig>()
+            .configure_sets(
+                GameplayStep,
+                (
+                    PlayerStateSet::Movement,
+                    PlayerStateSet::Collision,
+                    PlayerStateSet::Confine,
+                )
+                    .chain(),
             )
-                .run_if(in_state(AppState::Game))
-                .run_if(in_state(SimulationState::GameRunning)),
-        )
-        .add_systems(OnExit(AppState::Game), despawn_player);
+            .add_systems(
+                OnEnter(AppState::Game),
+                spawn_player.after(prepare_run).run_if(runs_simulation),
+            )
+            .add_systems(
+                GameplayStep,
+                (
+                    player_hit_star,
+                    tick_invulnerability,
+                    tick_boost,
+                    player_movement.in_set(PlayerStateSet::Movement),
+                    player_hit_obstacle.in_set(PlayerStateSet::Collision),
+                    confine_player.in_set(PlayerStateSet::Confine),
+                )
+                    .run_if(in_state(AppState::Game))
+                    .run_if(in_state(SimulationState::GameRunning)),
+            )
+            .add_systems(OnExit(AppState::Game), despawn_player);
+    }
+}
+
+pub struct LevelPlugin;
+impl Plugin for LevelPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(LevelLibrary::load())
+            .init_resource::<SelectedLevel>()
+            .add_systems(OnEnter(AppState::Game), spawn_obstacles)
+            .add_systems(OnExit(AppState::Game), despawn_obstacles);
     }
 }
 
 pub struct CameraPlugin;
 impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, spawn_camera)
-            .add_systems(Update, camera_position);
+        app.init_resource::<Arena>()
+            .add_systems(Startup, spawn_camera)
+            .add_systems(Update, camera_position.run_if(camera_follows_window));
     }
 }
 
@@ -65,9 +117,16 @@ pub struct StarPlugin;
 impl Plugin for StarPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<StarSpawnTimer>()
-            .add_systems(OnEnter(AppState::Game), spawn_stars)
             .add_systems(
-                Update,
+                OnEnter(AppState::Game),
+                // Both draw from `GameRng`, so their order must not change between runs.
+                spawn_stars
+                    .after(prepare_run)
+                    .after(spawn_enemies)
+                    .run_if(runs_simulation),
+            )
+            .add_systems(
+                GameplayStep,
                 spawn_stars_over_time
                     .run_if(in_state(AppState::Game))
                     .run_if(in_state(SimulationState::GameRunning)),
@@ -80,7 +139,7 @@ pub struct TimersPlugin;
 impl Plugin for TimersPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
-            Update,
+            GameplayStep,
             (tick_star_spawn_timer, tick_enemy_spawn_timer)
                 .chain()
                 .run_if(in_state(AppState::Game))
@@ -93,24 +152,102 @@ pub struct GameStatePlugin;
 impl Plugin for GameStatePlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<Score>()
+            .init_resource::<NetworkRole>()
             .init_resource::<HighScores>()
+            .init_resource::<RunResults>()
+            .init_resource::<GameMode>()
+            .init_resource::<Lives>()
+            .init_resource::<GameRng>()
+            .init_resource::<MatchSeed>()
             .add_message::<GameOver>()
-            .add_systems(OnEnter(AppState::Game), insert_score)
+            .add_systems(OnEnter(AppState::Game), (prepare_run, insert_score))
+            .add_systems(
+                OnTransition {
+                    exited: AppState::Game,
+                    entered: AppState::Game,
+                },
+                restart_game,
+            )
             .add_systems(
                 Update,
-                (
-                    exit_game,
-                    handle_game_over,
-                    update_high_scores,
-                    toggle_game_simulation,
-                )
-                    .run_if(in_state(AppState::Game)),
+                run_gameplay_step
+                    .run_if(in_state(AppState::Game))
+                    .run_if(steps_each_frame)
+                    .run_if(not(leaving_game)),
+            )
+            .add_systems(
+                Update,
+                (handle_game_over, update_high_scores)
+                    .run_if(in_state(AppState::Game))
+                    .run_if(not(leaving_game)),
+            )
+            .add_systems(
+                Update,
+                escape_game
+                    .run_if(in_state(AppState::Game))
+                    .run_if(not(in_state(SimulationState::GamePaused)))
+                    .run_if(not(leaving_game)),
+            )
+            .add_systems(
+                Update,
+                toggle_game_simulation
+                    .run_if(in_state(AppState::Game))
+                    .run_if(steps_each_frame)
+                    .run_if(not(leaving_game)),
+            )
+            .add_systems(
+                Update,
+                transition_to_main_menu
+                    .run_if(not(in_state(AppState::Loading)))
+                    .run_if(not(in_state(AppState::Editor)))
+                    .run_if(not(in_state(AppState::Lobby))),
             )
-            .add_systems(Update, transition_to_main_menu)
             .add_systems(OnExit(AppState::Game), remove_score);
     }
 }
 
+pub struct ReplayPlugin;
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Game), start_replay.after(prepare_run))
+            .add_systems(
+                GameplayStep,
+                record_replay_frame
+                    .before(PlayerStateSet::Movement)
+                    .run_if(in_state(AppState::Game))
+                    .run_if(in_state(SimulationState::GameRunning))
+                    .run_if(resource_exists::<Replay>),
+            )
+            .add_systems(Update, finish_replay.run_if(resource_exists::<Replay>));
+    }
+}
+
+pub struct CountdownPlugin;
+impl Plugin for CountdownPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(SimulationState::Countdown),
+            (
+                start_countdown.run_if(shows_countdown),
+                skip_countdown.run_if(not(shows_countdown)),
+            ),
+        )
+        .add_systems(
+            Update,
+            (
+                tick_countdown
+                    .run_if(in_state(SimulationState::Countdown))
+                    .run_if(steps_each_frame),
+                show_countdown.run_if(resource_exists_and_changed::<Countdown>),
+                animate_countdown.run_if(in_state(AppState::Game)),
+            )
+                .chain(),
+        )
+        .add_systems(OnEnter(SimulationState::GamePaused), hide_countdown)
+        .add_systems(OnExit(AppState::Game), hide_countdown);
+    }
+}
+
 pub struct UIPlugin;
 impl Plugin for UIPlugin {
     fn build(&self, app: &mut App) {
@@ -128,25 +265,23 @@ impl Plugin for UIPlugin {
             .add_systems(OnExit(AppState::GameOver), gameover::despawn_gameover_menu)
             .add_systems(
                 Update,
-                (hud::update_score_text, hud::update_enemys_text).run_if(in_state(AppState::Game)),
+                (
+                    hud::update_score_text,
+                    hud::update_player_score_text,
+                    hud::update_lives_text,
+                    hud::update_enemys_text,
+                )
+                    .run_if(in_state(AppState::Game)),
             )
             .add_systems(
                 Update,
-                (
-                    pause_menu::interact_with_resume_button,
-                    pause_menu::interact_with_main_menu_button,
-                    pause_menu::interact_with_quit_button,
-                )
-                    .run_if(in_state(SimulationState::GamePaused)),
+                pause_menu::handle_pause_actions
+                    .run_if(in_state(SimulationState::GamePaused))
+                    .run_if(not(leaving_game)),
             )
             .add_systems(
                 Update,
-                (
-                    gameover::interact_with_restart_button,
-                    gameover::interact_with_main_menu_button,
-                    gameover::interact_with_quit_button,
-                )
-                    .run_if(in_state(AppState::GameOver)),
+                gameover::handle_gameover_actions.run_if(in_state(AppState::GameOver)),
             );
     }
 }
@@ -1,9 +1,18 @@
 pub mod score {
+    use super::super::{components::player::MAX_PLAYERS, messages::game_states::PlayerResult};
     use bevy::prelude::*;
 
-    #[derive(Resource, Default)]
+    #[derive(Resource, Clone, Default)]
     pub struct Score {
         pub value: u32,
+        pub players: [u32; MAX_PLAYERS],
+    }
+
+    /// Outcome of the last run, kept around for the game over screen.
+    #[derive(Resource, Default)]
+    pub struct RunResults {
+        pub score: u32,
+        pub results: Vec<PlayerResult>,
     }
 
     #[derive(Resource, Default)]
@@ -12,6 +21,177 @@ pub mod score {
     }
 }
 
+pub mod mode {
+    use bevy::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    pub const CO_OP_LIVES: u32 = 3;
+
+    #[derive(Resource, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum GameMode {
+        #[default]
+        Single,
+        /// Two players sharing a pool of lives.
+        Coop,
+        /// Two players, the last ball standing wins.
+        Versus,
+    }
+
+    impl GameMode {
+        pub fn next(&self) -> Self {
+            match self {
+                GameMode::Single => GameMode::Coop,
+                GameMode::Coop => GameMode::Versus,
+                GameMode::Versus => GameMode::Single,
+            }
+        }
+
+        pub fn label(&self) -> &'static str {
+            match self {
+                GameMode::Single => "1 Player",
+                GameMode::Coop => "Co-op",
+                GameMode::Versus => "Versus",
+            }
+        }
+
+        pub fn player_count(&self) -> usize {
+            match self {
+                GameMode::Single => 1,
+                GameMode::Coop | GameMode::Versus => 2,
+            }
+        }
+
+        /// Lives shared by every player, `None` when each player only has one.
+        pub fn shared_lives(&self) -> Option<u32> {
+            match self {
+                GameMode::Single => Some(1),
+                GameMode::Coop => Some(CO_OP_LIVES),
+                GameMode::Versus => None,
+            }
+        }
+    }
+
+    #[derive(Resource, Clone, Default)]
+    pub struct Lives {
+        pub value: u32,
+    }
+}
+
+pub mod network {
+    use bevy::prelude::*;
+
+    /// Whether this app runs the game simulation or mirrors a remote one.
+    #[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum NetworkRole {
+        #[default]
+        Offline,
+        Host,
+        Client,
+        /// Both peers simulate every frame and roll back on mispredicted input.
+        Rollback,
+        /// Watches a broadcast match without taking part.
+        Spectator,
+    }
+}
+
+pub mod random {
+    use bevy::prelude::*;
+    use rand::{SeedableRng, random, rngs::StdRng};
+
+    /// Source of every random decision the simulation makes, so a run can be
+    /// reproduced from its seed.
+    #[derive(Resource, Clone)]
+    pub struct GameRng {
+        pub seed: u64,
+        pub rng: StdRng,
+    }
+
+    impl GameRng {
+        pub fn new(seed: u64) -> Self {
+            Self {
+                seed,
+                rng: StdRng::seed_from_u64(seed),
+            }
+        }
+    }
+
+    impl Default for GameRng {
+        fn default() -> Self {
+            Self::new(random())
+        }
+    }
+
+    /// Seed for the next run; a fresh one is picked when unset.
+    #[derive(Resource, Default)]
+    pub struct MatchSeed {
+        pub value: Option<u64>,
+    }
+}
+
+pub mod controls {
+    use super::super::components::player::{
+        PLAYER_ACCELERATION, PLAYER_FRICTION, PLAYER_SPEED, PLAYER_TURN_RATE,
+        PLAYER_WALL_RESTITUTION,
+    };
+    use bevy::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Resource, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum ControlScheme {
+        /// Instant movement at a fixed speed, clamped to the walls.
+        #[default]
+        Classic,
+        /// Momentum based movement using `MovementConfig`.
+        Slippery,
+    }
+
+    impl ControlScheme {
+        pub fn next(&self) -> Self {
+            match self {
+                ControlScheme::Classic => ControlScheme::Slippery,
+                ControlScheme::Slippery => ControlScheme::Classic,
+            }
+        }
+
+        pub fn label(&self) -> &'static str {
+            match self {
+                ControlScheme::Classic => "Classic",
+                ControlScheme::Slippery => "Slippery",
+            }
+        }
+    }
+
+    /// Tuning for `ControlScheme::Slippery`, taken from the level being played.
+    /// Every field is optional in level files.
+    #[derive(Resource, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+    #[serde(default)]
+    pub struct MovementConfig {
+        /// Speed gained per second while a direction is held.
+        pub acceleration: f32,
+        pub max_speed: f32,
+        /// Speed lost per second while no direction is held.
+        pub friction: f32,
+        /// How fast sideways velocity is bled off when steering, per second.
+        pub turn_rate: f32,
+        pub bounce_off_walls: bool,
+        /// Fraction of the speed kept when bouncing off a wall.
+        pub wall_restitution: f32,
+    }
+
+    impl Default for MovementConfig {
+        fn default() -> Self {
+            Self {
+                acceleration: PLAYER_ACCELERATION,
+                max_speed: PLAYER_SPEED,
+                friction: PLAYER_FRICTION,
+                turn_rate: PLAYER_TURN_RATE,
+                bounce_off_walls: true,
+                wall_restitution: PLAYER_WALL_RESTITUTION,
+            }
+        }
+    }
+}
+
 pub mod timers {
     use super::super::components::{enemy::ENEMY_SPAWN_TIME, star::STAR_SPAWN_TIME};
     use bevy::prelude::*;
@@ -42,3 +222,587 @@ pub mod timers {
         }
     }
 }
+
+pub mod countdown {
+    use bevy::prelude::*;
+
+    pub const COUNTDOWN_FROM: u32 = 3;
+    pub const COUNTDOWN_STEP_SECS: f32 = 0.7;
+    /// How long "Go!" stays on screen once the run has started.
+    pub const GO_SECS: f32 = 0.6;
+
+    #[derive(Resource)]
+    pub struct Countdown {
+        pub remaining: u32,
+        pub timer: Timer,
+    }
+
+    impl Default for Countdown {
+        fn default() -> Self {
+            Self {
+                remaining: COUNTDOWN_FROM,
+                timer: Timer::from_seconds(COUNTDOWN_STEP_SECS, TimerMode::Repeating),
+            }
+        }
+    }
+}
+
+pub mod arena {
+    use bevy::prelude::*;
+
+    pub const ARENA_WIDTH: f32 = 1280.0;
+    pub const ARENA_HEIGHT: f32 = 720.0;
+
+    /// Logical size of the play field. The simulation never reads the window, so
+    /// peers and replays agree on the bounds whatever their window size.
+    #[derive(Resource, Debug, Clone, Copy)]
+    pub struct Arena {
+        pub size: Vec2,
+    }
+
+    impl Default for Arena {
+        fn default() -> Self {
+            Self {
+                size: Vec2::new(ARENA_WIDTH, ARENA_HEIGHT),
+            }
+        }
+    }
+
+    impl Arena {
+        pub fn center(&self) -> Vec2 {
+            self.size / 2.0
+        }
+    }
+}
+
+pub mod level {
+    use super::arena::Arena;
+    use super::controls::MovementConfig;
+    use bevy::{asset::io::file::FileAssetReader, prelude::*};
+    use rand::Rng;
+    use serde::{Deserialize, Serialize};
+    use sha2::{Digest, Sha256};
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+    };
+
+    pub const LEVELS_DIR: &str = "assets/levels";
+    pub const SPAWN_ATTEMPTS: usize = 16;
+
+    #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+    pub struct Point {
+        pub x: f32,
+        pub y: f32,
+    }
+
+    impl From<Point> for Vec2 {
+        fn from(point: Point) -> Self {
+            Vec2::new(point.x, point.y)
+        }
+    }
+
+    impl From<Vec2> for Point {
+        fn from(vec: Vec2) -> Self {
+            Point { x: vec.x, y: vec.y }
+        }
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+    pub enum ObstacleShape {
+        Rect { width: f32, height: f32 },
+        Circle { radius: f32 },
+        Bumper { radius: f32, strength: f32 },
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+    pub struct ObstacleData {
+        pub shape: ObstacleShape,
+        pub position: Point,
+    }
+
+    /// Axis aligned area, centered on `position`, where stars or enemies may spawn.
+    #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+    pub struct SpawnZone {
+        pub position: Point,
+        pub width: f32,
+        pub height: f32,
+    }
+
+    impl SpawnZone {
+        pub fn random_point(&self, margin: f32, rng: &mut impl Rng) -> Vec2 {
+            let half_width = (self.width / 2.0 - margin).max(0.0);
+            let half_height = (self.height / 2.0 - margin).max(0.0);
+
+            Vec2::new(
+                self.position.x + (rng.random::<f32>() * 2.0 - 1.0) * half_width,
+                self.position.y + (rng.random::<f32>() * 2.0 - 1.0) * half_height,
+            )
+        }
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+    pub struct LevelData {
+        pub name: String,
+        #[serde(default)]
+        pub player_starts: Vec<Point>,
+        #[serde(default)]
+        pub obstacles: Vec<ObstacleData>,
+        #[serde(default)]
+        pub star_zones: Vec<SpawnZone>,
+        #[serde(default)]
+        pub enemy_zones: Vec<SpawnZone>,
+        #[serde(default)]
+        pub movement: MovementConfig,
+    }
+
+    impl Default for LevelData {
+        fn default() -> Self {
+            Self {
+                name: "Open Arena".to_string(),
+                player_starts: Vec::new(),
+                obstacles: Vec::new(),
+                star_zones: Vec::new(),
+                enemy_zones: Vec::new(),
+                movement: MovementConfig::default(),
+            }
+        }
+    }
+
+    impl LevelData {
+        /// SHA-256 of the encoded level, equal for equal content on every machine.
+        pub fn hash(&self) -> [u8; 32] {
+            let bytes = bincode::serialize(self).expect("levels are always serializable");
+            Sha256::digest(bytes).into()
+        }
+
+        pub fn player_start(&self, arena: &Arena) -> Vec2 {
+            self.player_starts
+                .first()
+                .map(|start| (*start).into())
+                .unwrap_or(arena.center())
+        }
+
+        pub fn random_star_position(
+            &self,
+            arena: &Arena,
+            size: f32,
+            rng: &mut impl Rng,
+        ) -> Option<Vec2> {
+            self.random_position(&self.star_zones, arena, size, rng)
+        }
+
+        pub fn random_enemy_position(
+            &self,
+            arena: &Arena,
+            size: f32,
+            rng: &mut impl Rng,
+        ) -> Option<Vec2> {
+            self.random_position(&self.enemy_zones, arena, size, rng)
+        }
+
+        /// Picks a point inside one of `zones` (or the whole arena when there are none)
+        /// that is clear of obstacles, or `None` when `SPAWN_ATTEMPTS` tries all
+        /// landed inside one.
+        fn random_position(
+            &self,
+            zones: &[SpawnZone],
+            arena: &Arena,
+            size: f32,
+            rng: &mut impl Rng,
+        ) -> Option<Vec2> {
+            let whole_arena = SpawnZone {
+                position: arena.center().into(),
+                width: arena.size.x,
+                height: arena.size.y,
+            };
+
+            (0..SPAWN_ATTEMPTS).find_map(|_| {
+                let zone = if zones.is_empty() {
+                    &whole_arena
+                } else {
+                    &zones[rng.random_range(0..zones.len())]
+                };
+
+                let position = zone.random_point(size / 2.0, rng);
+                (!self.overlaps_obstacle(position, size / 2.0)).then_some(position)
+            })
+        }
+
+        pub fn overlaps_obstacle(&self, point: Vec2, radius: f32) -> bool {
+            self.obstacles.iter().any(|obstacle| {
+                obstacle
+                    .shape
+                    .circle_contact(obstacle.position.into(), point, radius)
+                    .is_some()
+            })
+        }
+    }
+
+    /// Penetration of a circle into an obstacle: `normal` points away from the obstacle.
+    #[derive(Debug, Clone, Copy)]
+    pub struct Contact {
+        pub normal: Vec2,
+        pub depth: f32,
+    }
+
+    impl ObstacleShape {
+        pub fn circle_contact(&self, center: Vec2, point: Vec2, radius: f32) -> Option<Contact> {
+            match *self {
+                ObstacleShape::Rect { width, height } => {
+                    let half_size = Vec2::new(width / 2.0, height / 2.0);
+                    let local = point - center;
+                    let closest = local.clamp(-half_size, half_size);
+
+                    if closest == local {
+                        // The circle center is inside the rectangle, push it out along the shallowest axis.
+                        let gap = half_size - local.abs();
+                        return Some(if gap.x < gap.y {
+                            Contact {
+                                normal: Vec2::new(local.x.signum(), 0.0),
+                                depth: gap.x + radius,
+                            }
+                        } else {
+                            Contact {
+                                normal: Vec2::new(0.0, local.y.signum()),
+                                depth: gap.y + radius,
+                            }
+                        });
+                    }
+
+                    let offset = local - closest;
+                    let distance = offset.length();
+                    (distance < radius).then(|| Contact {
+                        normal: offset / distance,
+                        depth: radius - distance,
+                    })
+                }
+                ObstacleShape::Circle {
+                    radius: obstacle_radius,
+                }
+                | ObstacleShape::Bumper {
+                    radius: obstacle_radius,
+                    ..
+                } => {
+                    let offset = point - center;
+                    let distance = offset.length();
+                    let min_distance = radius + obstacle_radius;
+
+                    (distance < min_distance).then(|| Contact {
+                        normal: offset.try_normalize().unwrap_or(Vec2::Y),
+                        depth: min_distance - distance,
+                    })
+                }
+            }
+        }
+    }
+
+    #[derive(Resource, Debug, Clone)]
+    pub struct LevelLibrary {
+        pub levels: Vec<LevelEntry>,
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct LevelEntry {
+        /// File the level is saved to. Levels received over the network have none,
+        /// so they are never written over a local file.
+        pub path: Option<PathBuf>,
+        pub data: LevelData,
+    }
+
+    impl LevelLibrary {
+        /// Reads every `.ron` file of the levels folder, sorted by file name.
+        /// Levels that fail to parse are reported and skipped; an empty arena is
+        /// used when nothing could be loaded.
+        pub fn load() -> Self {
+            let dir = Self::dir();
+            let mut paths: Vec<PathBuf> = fs::read_dir(&dir)
+                .map(|entries| {
+                    entries
+                        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                        .filter(|path| path.extension().is_some_and(|ext| ext == "ron"))
+                        .collect()
+                })
+                .unwrap_or_else(|err| {
+                    error!("Could not read levels folder {}: {err}", dir.display());
+                    Vec::new()
+                });
+            paths.sort();
+
+            let mut levels: Vec<LevelEntry> = paths
+                .into_iter()
+                .filter_map(|path| match Self::read_level(&path) {
+                    Ok(data) => {
+                        info!("Loaded level \"{}\" from {}", data.name, path.display());
+                        Some(LevelEntry {
+                            path: Some(path),
+                            data,
+                        })
+                    }
+                    Err(err) => {
+                        error!("Invalid level file {}: {err}", path.display());
+                        None
+                    }
+                })
+                .collect();
+
+            if levels.is_empty() {
+                levels.push(LevelEntry {
+                    path: Some(dir.join("01_arena.ron")),
+                    data: LevelData::default(),
+                });
+            }
+
+            Self { levels }
+        }
+
+        pub fn dir() -> PathBuf {
+            FileAssetReader::get_base_path().join(LEVELS_DIR)
+        }
+
+        pub fn read_level(path: &Path) -> Result<LevelData, String> {
+            let content = fs::read_to_string(path).map_err(|err| err.to_string())?;
+            ron::from_str(&content).map_err(|err| err.to_string())
+        }
+
+        pub fn write_level(path: &Path, data: &LevelData) -> Result<(), String> {
+            let content = ron::ser::to_string_pretty(data, ron::ser::PrettyConfig::default())
+                .map_err(|err| err.to_string())?;
+            fs::write(path, content).map_err(|err| err.to_string())
+        }
+
+        /// Adds an empty level backed by a new file next to the shipped ones.
+        /// The file is only written once the level is saved, and never reuses the
+        /// name of an existing file, including levels that failed to load.
+        pub fn add_level(&mut self) -> usize {
+            let dir = Self::dir();
+            let mut number = self.levels.len() + 1;
+            let path = loop {
+                let path = dir.join(format!("{number:02}_custom.ron"));
+                if !path.exists()
+                    && self
+                        .levels
+                        .iter()
+                        .all(|entry| entry.path.as_ref() != Some(&path))
+                {
+                    break path;
+                }
+                number += 1;
+            };
+            self.levels.push(LevelEntry {
+                path: Some(path),
+                data: LevelData {
+                    name: format!("Level {number}"),
+                    ..Default::default()
+                },
+            });
+
+            self.levels.len() - 1
+        }
+
+        /// Returns the index of the level with the same content hash, adding it when
+        /// it is unknown. Added levels come from the network and are kept in memory
+        /// only, so the editor can't save them over a local file.
+        pub fn find_or_add(&mut self, data: LevelData) -> usize {
+            let hash = data.hash();
+            if let Some(index) = self
+                .levels
+                .iter()
+                .position(|entry| entry.data.hash() == hash)
+            {
+                return index;
+            }
+
+            self.levels.push(LevelEntry { path: None, data });
+            self.levels.len() - 1
+        }
+
+        pub fn get(&self, selected: &SelectedLevel) -> &LevelData {
+            &self.levels[selected.index % self.levels.len()].data
+        }
+    }
+
+    #[derive(Resource, Default)]
+    pub struct SelectedLevel {
+        pub index: usize,
+    }
+
+    impl SelectedLevel {
+        pub fn next(&mut self, library: &LevelLibrary) {
+            self.index = (self.index + 1) % library.levels.len();
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const RECT: ObstacleShape = ObstacleShape::Rect {
+            width: 100.0,
+            height: 40.0,
+        };
+
+        #[test]
+        fn circle_touching_a_rect_is_pushed_away_from_it() {
+            let contact = RECT
+                .circle_contact(Vec2::ZERO, Vec2::new(0.0, 25.0), 10.0)
+                .unwrap();
+            assert_eq!(contact.normal, Vec2::Y);
+            assert_eq!(contact.depth, 5.0);
+
+            assert!(
+                RECT.circle_contact(Vec2::ZERO, Vec2::new(0.0, 31.0), 10.0)
+                    .is_none()
+            );
+        }
+
+        #[test]
+        fn circle_inside_a_rect_leaves_along_the_shallowest_axis() {
+            let contact = RECT
+                .circle_contact(Vec2::ZERO, Vec2::new(45.0, 0.0), 10.0)
+                .unwrap();
+            assert_eq!(contact.normal, Vec2::X);
+            assert_eq!(contact.depth, 15.0);
+        }
+
+        #[test]
+        fn circles_touch_when_closer_than_their_radii() {
+            let circle = ObstacleShape::Circle { radius: 20.0 };
+            let center = Vec2::new(100.0, 100.0);
+
+            let contact = circle
+                .circle_contact(center, Vec2::new(100.0, 70.0), 15.0)
+                .unwrap();
+            assert_eq!(contact.normal, Vec2::NEG_Y);
+            assert_eq!(contact.depth, 5.0);
+
+            assert!(
+                circle
+                    .circle_contact(center, Vec2::new(100.0, 64.0), 15.0)
+                    .is_none()
+            );
+        }
+    }
+}
+
+pub mod replay {
+    use super::super::{
+        messages::game_states::PlayerResult,
+        resources::{
+            controls::ControlScheme,
+            level::{LevelData, Point},
+            mode::GameMode,
+        },
+    };
+    use bevy::{asset::io::file::FileAssetReader, prelude::*};
+    use serde::{Deserialize, Serialize};
+    use sha2::{Digest, Sha256};
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+    };
+
+    pub const REPLAYS_DIR: &str = "replays";
+    pub const REPLAY_EXTENSION: &str = "replay";
+
+    /// One simulation tick: how long it lasted and where each player steered.
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+    pub struct ReplayFrame {
+        pub delta_nanos: u64,
+        pub inputs: Vec<Point>,
+    }
+
+    /// What the run claimed to end with.
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+    pub struct ReplayOutcome {
+        pub score: u32,
+        pub results: Vec<PlayerResult>,
+        /// Number of ticks simulated when `GameOver` was sent.
+        pub game_over_tick: u32,
+    }
+
+    /// Everything needed to re-run an offline run tick for tick.
+    #[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+    pub struct Replay {
+        pub seed: u64,
+        pub mode: GameMode,
+        pub control_scheme: ControlScheme,
+        pub level: LevelData,
+        /// Arena size the run was played at.
+        pub arena: Point,
+        pub frames: Vec<ReplayFrame>,
+        pub outcome: Option<ReplayOutcome>,
+    }
+
+    impl Replay {
+        /// Hex encoded SHA-256 of the whole replay, identifying it on the leaderboard.
+        pub fn hash(&self) -> String {
+            let bytes = bincode::serialize(self).expect("replays are always serializable");
+            Sha256::digest(bytes)
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect()
+        }
+
+        pub fn dir() -> PathBuf {
+            FileAssetReader::get_base_path().join(REPLAYS_DIR)
+        }
+
+        /// Writes the replay to the replays folder, named after its hash.
+        pub fn save(&self) -> Result<PathBuf, String> {
+            let dir = Self::dir();
+            fs::create_dir_all(&dir).map_err(|err| err.to_string())?;
+
+            let path = dir.join(format!("{}.{REPLAY_EXTENSION}", self.hash()));
+            let bytes = bincode::serialize(self).map_err(|err| err.to_string())?;
+            fs::write(&path, bytes).map_err(|err| err.to_string())?;
+            Ok(path)
+        }
+
+        pub fn load(path: &Path) -> Result<Self, String> {
+            let bytes = fs::read(path).map_err(|err| err.to_string())?;
+            bincode::deserialize(&bytes).map_err(|err| err.to_string())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn hash_survives_save_and_load() {
+            let replay = Replay {
+                seed: 42,
+                frames: vec![
+                    ReplayFrame {
+                        delta_nanos: 16_666_667,
+                        inputs: vec![Point { x: 1.0, y: 0.0 }],
+                    };
+                    3
+                ],
+                outcome: Some(ReplayOutcome {
+                    score: 7,
+                    results: vec![PlayerResult {
+                        index: 0,
+                        score: 7,
+                        winner: false,
+                    }],
+                    game_over_tick: 3,
+                }),
+                ..default()
+            };
+
+            let path = replay.save().unwrap();
+            let loaded = Replay::load(&path);
+            fs::remove_file(&path).unwrap();
+            let loaded = loaded.unwrap();
+
+            assert_eq!(loaded, replay);
+            assert_eq!(loaded.hash(), replay.hash());
+            assert_eq!(
+                path.file_stem().and_then(|stem| stem.to_str()),
+                Some(replay.hash().as_str())
+            );
+        }
+    }
+}
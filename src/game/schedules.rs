@@ -0,0 +1,7 @@
+use bevy::ecs::schedule::ScheduleLabel;
+
+/// One tick of the gameplay simulation: movement, bounces, collisions and spawns.
+/// Runs once per frame offline and on the host; rollback sessions run it at a fixed
+/// rate and may re-run past ticks.
+#[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GameplayStep;
@@ -1,8 +1,13 @@
+use crate::AppState;
 use bevy::prelude::*;
 
-#[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+/// Only exists during `AppState::Game`. Every run, and every resume from the pause
+/// menu, starts with a countdown during which nothing moves.
+#[derive(SubStates, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+#[source(AppState = AppState::Game)]
 pub enum SimulationState {
     #[default]
+    Countdown,
     GameRunning,
     GamePaused,
 }
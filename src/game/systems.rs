@@ -1,34 +1,80 @@
 pub mod player {
     use super::super::{
         components::{
-            player::{PLAYER_SIZE, PLAYER_SPEED, Player},
-            star::{CollectStarSound, STAR_SIZE, Star},
+            player::{
+                Boosted, Invulnerable, PLAYER_SIZE, PLAYER_SPEED, Player, PlayerControls,
+                RemoteInput, Velocity,
+            },
+            star::{STAR_SIZE, Star},
+        },
+        resources::{
+            arena::Arena,
+            controls::{ControlScheme, MovementConfig},
+            level::{LevelLibrary, SelectedLevel},
+            mode::GameMode,
+            score::Score,
         },
-        resources::score::Score,
     };
-    use bevy::{prelude::*, window::PrimaryWindow};
+    use crate::animation::{
+        components::{DespawnWhenFinished, SpriteAnimation},
+        resources::{ClipKind, SheetKind},
+    };
+    use crate::audio::{components::mixer::SoundEffect, resources::mixer::SoundKind};
+    use crate::loading::resources::GameAssets;
+    use crate::particles::{components::ParticleBurst, resources::EmitterKind};
+    use bevy::prelude::*;
+
+    pub const PLAYER_START_SPACING: f32 = 200.0;
 
     #[derive(SystemSet, Debug, Clone, Hash, PartialEq, Eq)]
     pub enum PlayerStateSet {
         Movement,
+        Collision,
         Confine,
     }
 
     pub fn spawn_player(
         mut commands: Commands,
-        window_query: Query<&Window, With<PrimaryWindow>>,
-        asset_server: Res<AssetServer>,
+        arena: Res<Arena>,
+        game_assets: Res<GameAssets>,
+        level_library: Res<LevelLibrary>,
+        selected_level: Res<SelectedLevel>,
+        game_mode: Res<GameMode>,
     ) {
-        let window = window_query.single().unwrap();
+        let level = level_library.get(&selected_level);
+        let player_count = game_mode.player_count();
 
-        commands.spawn((
-            Player {},
-            Sprite {
-                image: asset_server.load("sprites/ball_blue_large.png"),
-                ..Default::default()
-            },
-            Transform::from_xyz(window.width() / 2.0, window.height() / 2.0, 0.0),
-        ));
+        for index in 0..player_count {
+            let start = player_start(
+                level.player_starts.get(index).copied().map(Vec2::from),
+                level.player_start(&arena),
+                index,
+                player_count,
+            );
+
+            commands.spawn((
+                Player { index },
+                PlayerControls::for_player(index, player_count),
+                Velocity::default(),
+                Sprite::from_image(SheetKind::player(index).image(&game_assets.sprites)),
+                SpriteAnimation::new(SheetKind::player(index), ClipKind::SpawnIn),
+                Transform::from_xyz(start.x, start.y, 0.0),
+            ));
+        }
+    }
+
+    /// Uses the level start point for this player, or spreads the players around the
+    /// first start point when the level defines fewer starts than players.
+    pub fn player_start(
+        start: Option<Vec2>,
+        fallback: Vec2,
+        index: usize,
+        player_count: usize,
+    ) -> Vec2 {
+        start.unwrap_or_else(|| {
+            let offset = index as f32 - (player_count - 1) as f32 / 2.0;
+            fallback + Vec2::new(offset * PLAYER_START_SPACING, 0.0)
+        })
     }
 
     pub fn despawn_player(mut commands: Commands, player_query: Query<Entity, With<Player>>) {
@@ -39,51 +85,101 @@ pub mod player {
 
     pub fn player_movement(
         keyboard_input: Res<ButtonInput<KeyCode>>,
-        mut player_query: Query<&mut Transform, With<Player>>,
+        gamepad_query: Query<&Gamepad>,
+        player_query: Query<
+            (
+                &mut Transform,
+                &mut Velocity,
+                &PlayerControls,
+                Option<&RemoteInput>,
+            ),
+            With<Player>,
+        >,
+        control_scheme: Res<ControlScheme>,
+        movement_config: Res<MovementConfig>,
         time: Res<Time>,
     ) {
-        if let Ok(mut transform) = player_query.single_mut() {
-            let mut dir = Vec3::ZERO;
+        let gamepads: Vec<&Gamepad> = gamepad_query.iter().collect();
+        let delta = time.delta_secs();
 
-            if keyboard_input.pressed(KeyCode::ArrowLeft) || keyboard_input.pressed(KeyCode::KeyA) {
-                dir += Vec3::new(-1.0, 0.0, 0.0);
-            }
+        for (mut transform, mut velocity, controls, remote_input) in player_query {
+            let dir = player_direction(controls, remote_input, &keyboard_input, &gamepads);
 
-            if keyboard_input.pressed(KeyCode::ArrowRight) || keyboard_input.pressed(KeyCode::KeyD)
-            {
-                dir += Vec3::new(1.0, 0.0, 0.0);
-            }
-
-            if keyboard_input.pressed(KeyCode::ArrowDown) || keyboard_input.pressed(KeyCode::KeyS) {
-                dir += Vec3::new(0.0, -1.0, 0.0);
-            }
+            velocity.value = match *control_scheme {
+                ControlScheme::Classic => dir * PLAYER_SPEED,
+                ControlScheme::Slippery => {
+                    momentum_velocity(velocity.value, dir, &movement_config, delta)
+                }
+            };
 
-            if keyboard_input.pressed(KeyCode::ArrowUp) || keyboard_input.pressed(KeyCode::KeyW) {
-                dir += Vec3::new(0.0, 1.0, 0.0);
-            }
+            transform.translation += velocity.value.extend(0.0) * delta;
+        }
+    }
 
-            if dir.length() > 0.0 {
-                dir = dir.normalize();
-            }
+    /// Where a player steers this tick: network or replay input when present,
+    /// otherwise their own controls.
+    pub fn player_direction(
+        controls: &PlayerControls,
+        remote_input: Option<&RemoteInput>,
+        keyboard_input: &ButtonInput<KeyCode>,
+        gamepads: &[&Gamepad],
+    ) -> Vec2 {
+        match remote_input {
+            Some(remote_input) => remote_input.direction,
+            None => controls.direction(keyboard_input, gamepads),
+        }
+    }
 
-            transform.translation += dir * (PLAYER_SPEED * time.delta_secs());
+    pub fn momentum_velocity(
+        velocity: Vec2,
+        dir: Vec2,
+        config: &MovementConfig,
+        delta: f32,
+    ) -> Vec2 {
+        if dir == Vec2::ZERO {
+            let speed = (velocity.length() - config.friction * delta).max(0.0);
+            return velocity.normalize_or_zero() * speed;
         }
+
+        // Bleed off the sideways part of the velocity so the ball steers into the new direction.
+        let forward = dir * velocity.dot(dir);
+        let sideways = (velocity - forward) * (1.0 - config.turn_rate * delta).max(0.0);
+
+        (forward + sideways + dir * config.acceleration * delta).clamp_length_max(config.max_speed)
     }
 
     pub fn confine_player(
-        mut player_query: Query<&mut Transform, With<Player>>,
-        window_query: Query<&Window, With<PrimaryWindow>>,
+        player_query: Query<(&mut Transform, &mut Velocity), With<Player>>,
+        arena: Res<Arena>,
+        control_scheme: Res<ControlScheme>,
+        movement_config: Res<MovementConfig>,
     ) {
-        if let (Ok(mut player_transform), Ok(window)) =
-            (player_query.single_mut(), window_query.single())
-        {
-            let half_player_size: f32 = PLAYER_SIZE / 2.0;
-            let x_min = 0.0 + half_player_size;
-            let x_max = window.width() - half_player_size;
-            let y_min = 0.0 + half_player_size;
-            let y_max = window.height() - half_player_size;
-
+        let half_player_size: f32 = PLAYER_SIZE / 2.0;
+        let x_min = 0.0 + half_player_size;
+        let x_max = arena.size.x - half_player_size;
+        let y_min = 0.0 + half_player_size;
+        let y_max = arena.size.y - half_player_size;
+
+        let bounce = *control_scheme == ControlScheme::Slippery && movement_config.bounce_off_walls;
+        let restitution = if bounce {
+            -movement_config.wall_restitution
+        } else {
+            0.0
+        };
+
+        for (mut player_transform, mut velocity) in player_query {
             let mut translation = player_transform.translation;
+            if (translation.x < x_min && velocity.value.x < 0.0)
+                || (translation.x > x_max && velocity.value.x > 0.0)
+            {
+                velocity.value.x *= restitution;
+            }
+            if (translation.y < y_min && velocity.value.y < 0.0)
+                || (translation.y > y_max && velocity.value.y > 0.0)
+            {
+                velocity.value.y *= restitution;
+            }
+
             translation.x = translation.x.clamp(x_min, x_max);
             translation.y = translation.y.clamp(y_min, y_max);
             player_transform.translation = translation;
@@ -92,13 +188,13 @@ pub mod player {
 
     pub fn player_hit_star(
         mut commands: Commands,
-        player_query: Query<&Transform, With<Player>>,
+        player_query: Query<(&Transform, &Player)>,
         stars_query: Query<(Entity, &Transform), With<Star>>,
-        asset_serve: Res<AssetServer>,
+        game_assets: Res<GameAssets>,
         mut score: ResMut<Score>,
     ) {
-        if let Ok(player_transform) = player_query.single() {
-            for (star_entity, star_transform) in stars_query {
+        for (star_entity, star_transform) in stars_query {
+            let collector = player_query.iter().find(|(player_transform, _)| {
                 let distance = player_transform
                     .translation
                     .distance(star_transform.translation);
@@ -106,47 +202,142 @@ pub mod player {
                 let player_radius = PLAYER_SIZE / 2.0;
                 let star_radius = STAR_SIZE / 2.0;
 
-                if distance < player_radius + star_radius {
-                    commands.entity(star_entity).despawn();
-                    commands.spawn(CollectStarSound {
-                        audio: AudioPlayer(asset_serve.load("audio/laserLarge_000.ogg")),
-                        settings: PlaybackSettings::DESPAWN,
-                    });
-                    score.value += 1;
-                }
+                distance < player_radius + star_radius
+            });
+
+            if let Some((_, player)) = collector {
+                commands.entity(star_entity).despawn();
+                commands.spawn(SoundEffect::at(
+                    SoundKind::StarCollect,
+                    star_transform.translation.truncate(),
+                ));
+                commands.spawn(ParticleBurst::new(
+                    EmitterKind::StarSparkle,
+                    star_transform.translation.truncate(),
+                ));
+                commands.spawn((
+                    Sprite::from_image(SheetKind::Star.image(&game_assets.sprites)),
+                    SpriteAnimation::new(SheetKind::Star, ClipKind::Despawn),
+                    DespawnWhenFinished,
+                    *star_transform,
+                ));
+                score.value += 1;
+                score.players[player.index] += 1;
             }
         }
     }
+
+    /// Invulnerable players blink through their hurt clip, see `player_clips`.
+    pub fn tick_invulnerability(
+        mut commands: Commands,
+        player_query: Query<(Entity, &mut Invulnerable), With<Player>>,
+        time: Res<Time>,
+    ) {
+        for (entity, mut invulnerable) in player_query {
+            invulnerable.timer.tick(time.delta());
+
+            if invulnerable.timer.is_finished() {
+                commands.entity(entity).remove::<Invulnerable>();
+            }
+        }
+    }
+
+    pub fn tick_boost(
+        mut commands: Commands,
+        player_query: Query<(Entity, &mut Boosted), With<Player>>,
+        time: Res<Time>,
+    ) {
+        for (entity, mut boosted) in player_query {
+            boosted.timer.tick(time.delta());
+
+            if boosted.timer.is_finished() {
+                commands.entity(entity).remove::<Boosted>();
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const CONFIG: MovementConfig = MovementConfig {
+            acceleration: 1000.0,
+            max_speed: 500.0,
+            friction: 400.0,
+            turn_rate: 2.0,
+            bounce_off_walls: true,
+            wall_restitution: 0.5,
+        };
+
+        #[test]
+        fn momentum_accelerates_up_to_max_speed() {
+            let velocity = momentum_velocity(Vec2::ZERO, Vec2::X, &CONFIG, 0.25);
+            assert_eq!(velocity, Vec2::new(250.0, 0.0));
+
+            let velocity = momentum_velocity(velocity, Vec2::X, &CONFIG, 0.25);
+            assert_eq!(velocity, Vec2::new(500.0, 0.0));
+
+            let velocity = momentum_velocity(velocity, Vec2::X, &CONFIG, 0.25);
+            assert_eq!(velocity, Vec2::new(500.0, 0.0));
+        }
+
+        #[test]
+        fn momentum_coasts_to_a_stop_without_input() {
+            let velocity = momentum_velocity(Vec2::new(300.0, 0.0), Vec2::ZERO, &CONFIG, 0.25);
+            assert_eq!(velocity, Vec2::new(200.0, 0.0));
+
+            let velocity = momentum_velocity(Vec2::new(50.0, 0.0), Vec2::ZERO, &CONFIG, 0.25);
+            assert_eq!(velocity, Vec2::ZERO);
+        }
+
+        #[test]
+        fn momentum_bleeds_sideways_speed_when_turning() {
+            let velocity = momentum_velocity(Vec2::new(100.0, 0.0), Vec2::Y, &CONFIG, 0.25);
+            assert_eq!(velocity, Vec2::new(50.0, 250.0));
+        }
+    }
 }
 
 pub mod star {
     use super::super::{
         components::star::{NUMBER_OF_STARS, STAR_SIZE, Star},
-        resources::timers::StarSpawnTimer,
+        resources::{
+            arena::Arena,
+            level::{LevelLibrary, SelectedLevel},
+            random::GameRng,
+            timers::StarSpawnTimer,
+        },
     };
-    use bevy::{prelude::*, window::PrimaryWindow};
-    use rand::random;
+    use crate::animation::{
+        components::SpriteAnimation,
+        resources::{ClipKind, SheetKind},
+    };
+    use crate::loading::resources::GameAssets;
+    use bevy::prelude::*;
 
     pub fn spawn_stars(
         mut commands: Commands,
-        window_query: Query<&Window, With<PrimaryWindow>>,
-        asset_server: Res<AssetServer>,
+        arena: Res<Arena>,
+        game_assets: Res<GameAssets>,
+        level_library: Res<LevelLibrary>,
+        selected_level: Res<SelectedLevel>,
+        mut game_rng: ResMut<GameRng>,
     ) {
-        if let Ok(window) = window_query.single() {
-            for _ in 0..NUMBER_OF_STARS {
-                let pos_x =
-                    random::<f32>() * window.width().clamp(STAR_SIZE, window.width() - STAR_SIZE);
-                let pos_y = random::<f32>()
-                    * window
-                        .height()
-                        .clamp(STAR_SIZE, window.height() - STAR_SIZE);
+        let level = level_library.get(&selected_level);
 
-                commands.spawn((
-                    Star {},
-                    Sprite::from_image(asset_server.load("sprites/star.png")),
-                    Transform::from_xyz(pos_x, pos_y, 0.0),
-                ));
-            }
+        for _ in 0..NUMBER_OF_STARS {
+            let Some(position) = level.random_star_position(&arena, STAR_SIZE, &mut game_rng.rng)
+            else {
+                warn!("No free spot for a star in level \"{}\"", level.name);
+                continue;
+            };
+
+            commands.spawn((
+                Star {},
+                Sprite::from_image(SheetKind::Star.image(&game_assets.sprites)),
+                SpriteAnimation::new(SheetKind::Star, ClipKind::SpawnIn),
+                Transform::from_xyz(position.x, position.y, 0.0),
+            ));
         }
     }
 
@@ -158,25 +349,27 @@ pub mod star {
 
     pub fn spawn_stars_over_time(
         mut commands: Commands,
-        window_query: Query<&Window, With<PrimaryWindow>>,
-        asset_server: Res<AssetServer>,
+        arena: Res<Arena>,
+        game_assets: Res<GameAssets>,
         star_spawn_timer: Res<StarSpawnTimer>,
+        level_library: Res<LevelLibrary>,
+        selected_level: Res<SelectedLevel>,
+        mut game_rng: ResMut<GameRng>,
     ) {
         if star_spawn_timer.timer.is_finished() {
-            if let Ok(window) = window_query.single() {
-                let pos_x =
-                    random::<f32>() * window.width().clamp(STAR_SIZE, window.width() - STAR_SIZE);
-                let pos_y = random::<f32>()
-                    * window
-                        .height()
-                        .clamp(STAR_SIZE, window.height() - STAR_SIZE);
+            let level = level_library.get(&selected_level);
+            let Some(position) = level.random_star_position(&arena, STAR_SIZE, &mut game_rng.rng)
+            else {
+                warn!("No free spot for a star in level \"{}\"", level.name);
+                return;
+            };
 
-                commands.spawn((
-                    Star {},
-                    Sprite::from_image(asset_server.load("sprites/star.png")),
-                    Transform::from_xyz(pos_x, pos_y, 0.0),
-                ));
-            }
+            commands.spawn((
+                Star {},
+                Sprite::from_image(SheetKind::Star.image(&game_assets.sprites)),
+                SpriteAnimation::new(SheetKind::Star, ClipKind::SpawnIn),
+                Transform::from_xyz(position.x, position.y, 0.0),
+            ));
         }
     }
 }
@@ -185,34 +378,52 @@ pub mod enemy {
     use super::super::{
         components::{
             enemy::*,
-            player::{ExplosionSoundPlayer, PLAYER_SIZE, Player},
+            player::{Invulnerable, PLAYER_SIZE, Player},
+        },
+        messages::game_states::{GameOver, PlayerResult},
+        resources::{
+            arena::Arena,
+            level::{LevelLibrary, SelectedLevel},
+            mode::{GameMode, Lives},
+            random::GameRng,
+            score::Score,
+            timers::EnemySpawnTimer,
         },
-        messages::game_states::GameOver,
-        resources::{score::Score, timers::EnemySpawnTimer},
     };
-    use bevy::{prelude::*, window::PrimaryWindow};
-    use rand::random;
+    use crate::animation::{
+        components::{DespawnWhenFinished, SpriteAnimation},
+        resources::{ClipKind, SheetKind},
+    };
+    use crate::audio::{components::mixer::SoundEffect, resources::mixer::SoundKind};
+    use crate::camera_effects::components::CameraCue;
+    use crate::loading::resources::GameAssets;
+    use crate::particles::{components::ParticleBurst, resources::EmitterKind};
+    use bevy::prelude::*;
+    use rand::Rng;
 
     pub fn spawn_enemies(
         mut commands: Commands,
-        window_query: Query<&Window, With<PrimaryWindow>>,
-        asset_server: Res<AssetServer>,
+        arena: Res<Arena>,
+        game_assets: Res<GameAssets>,
+        level_library: Res<LevelLibrary>,
+        selected_level: Res<SelectedLevel>,
+        mut game_rng: ResMut<GameRng>,
     ) {
-        let window = window_query.single().unwrap();
+        let level = level_library.get(&selected_level);
 
         for _ in 0..NUMBER_OF_ENEMIES {
-            let random_x = random::<f32>() * window.width();
-            let random_y = random::<f32>() * window.height();
+            let size = random_size(&mut game_rng.rng);
+            let Some(position) = level.random_enemy_position(&arena, size, &mut game_rng.rng)
+            else {
+                warn!("No free spot for an enemy in level \"{}\"", level.name);
+                continue;
+            };
 
             commands.spawn((
-                Enemy {
-                    direction: Vec2::new(random::<f32>(), random::<f32>()).normalize(),
-                },
-                Sprite {
-                    image: asset_server.load("sprites/ball_red_large.png"),
-                    ..Default::default()
-                },
-                Transform::from_xyz(random_x, random_y, 0.0),
+                Enemy::new(random_direction(&mut game_rng.rng), size),
+                enemy_sprite(&game_assets, size),
+                SpriteAnimation::new(SheetKind::Enemy, ClipKind::SpawnIn),
+                Transform::from_xyz(position.x, position.y, 0.0),
             ));
         }
     }
@@ -223,117 +434,552 @@ pub mod enemy {
         }
     }
 
-    pub fn enemy_movement(enemy_query: Query<(&mut Transform, &Enemy)>, time: Res<Time>) {
-        for (mut transform, enemy) in enemy_query {
-            let dir = Vec3::new(enemy.direction.x, enemy.direction.y, 0.0);
-            transform.translation += dir * (ENEMY_SPEED * time.delta_secs());
+    pub fn enemy_movement(
+        enemy_query: Query<(&mut Transform, &mut Enemy), Without<Telegraphing>>,
+        time: Res<Time>,
+    ) {
+        for (mut transform, mut enemy) in enemy_query {
+            enemy.settle_speed(time.delta_secs());
+            transform.translation += enemy.velocity().extend(0.0) * time.delta_secs();
         }
     }
 
     pub fn update_enemy_direction(
         enemy_query: Query<(&Transform, &mut Enemy)>,
-        window_query: Query<&Window, With<PrimaryWindow>>,
-        asset_server: Res<AssetServer>,
+        arena: Res<Arena>,
         mut commands: Commands,
     ) {
-        if let Ok(window) = window_query.single() {
-            let half_enemy_size: f32 = ENEMY_SIZE / 2.0;
+        for (transform, mut enemy) in enemy_query {
+            let half_enemy_size = enemy.radius();
             let x_min = 0.0 + half_enemy_size;
-            let x_max = window.width() - half_enemy_size;
+            let x_max = arena.size.x - half_enemy_size;
             let y_min = 0.0 + half_enemy_size;
-            let y_max = window.height() - half_enemy_size;
+            let y_max = arena.size.y - half_enemy_size;
+            let translation = transform.translation;
+
+            // Sparks fly back into the arena from where the enemy touches the wall.
+            let mut wall_normal = Vec2::ZERO;
+            if translation.x <= x_min {
+                wall_normal.x = 1.0;
+            } else if translation.x >= x_max {
+                wall_normal.x = -1.0;
+            }
+            if translation.y <= y_min {
+                wall_normal.y = 1.0;
+            } else if translation.y >= y_max {
+                wall_normal.y = -1.0;
+            }
 
-            for (transform, mut enemy) in enemy_query {
-                let translation = transform.translation;
+            if wall_normal.x != 0.0 {
+                enemy.direction.x *= -1.0;
+            }
+            if wall_normal.y != 0.0 {
+                enemy.direction.y *= -1.0;
+            }
 
-                if translation.x <= x_min || translation.x >= x_max {
-                    enemy.direction.x *= -1.0;
-                    commands.spawn(BouncEnemySound::new(&asset_server));
+            if let Some(normal) = wall_normal.try_normalize() {
+                let position = translation.truncate();
+                commands.spawn(SoundEffect::at(SoundKind::Bounce, position));
+                commands.spawn(ParticleBurst::directed(
+                    EmitterKind::WallSpark,
+                    position - wall_normal * half_enemy_size,
+                    normal,
+                ));
+            }
+        }
+    }
+
+    /// Telegraphing enemies sit out, so a new enemy can't be knocked across the arena
+    /// before it goes live.
+    pub fn enemy_hit_enemy(
+        mut commands: Commands,
+        mut enemy_query: Query<(&mut Transform, &mut Enemy), Without<Telegraphing>>,
+    ) {
+        let mut bounce_position = None;
+
+        for _ in 0..ENEMY_COLLISION_ITERATIONS {
+            let mut combinations = enemy_query.iter_combinations_mut();
+            while let Some(
+                [
+                    (mut transform_a, mut enemy_a),
+                    (mut transform_b, mut enemy_b),
+                ],
+            ) = combinations.fetch_next()
+            {
+                let offset = (transform_b.translation - transform_a.translation).truncate();
+                let distance = offset.length();
+                let touching = enemy_a.radius() + enemy_b.radius();
+                if distance >= touching {
+                    continue;
                 }
 
-                if translation.y <= y_min || translation.y >= y_max {
-                    enemy.direction.y *= -1.0;
-                    commands.spawn(BouncEnemySound::new(&asset_server));
+                let normal = offset.try_normalize().unwrap_or(Vec2::X);
+                let inverse_mass_a = 1.0 / enemy_a.mass;
+                let inverse_mass_b = 1.0 / enemy_b.mass;
+                let inverse_mass_sum = inverse_mass_a + inverse_mass_b;
+
+                // Positional correction, lighter enemies are pushed further.
+                let overlap = touching - distance;
+                transform_a.translation -=
+                    (normal * overlap * inverse_mass_a / inverse_mass_sum).extend(0.0);
+                transform_b.translation +=
+                    (normal * overlap * inverse_mass_b / inverse_mass_sum).extend(0.0);
+
+                // Perfectly elastic impulse along the contact normal, only when approaching.
+                let velocity_a = enemy_a.velocity();
+                let velocity_b = enemy_b.velocity();
+                let approach_speed = (velocity_b - velocity_a).dot(normal);
+                if approach_speed < 0.0 {
+                    let impulse = -2.0 * approach_speed / inverse_mass_sum;
+                    enemy_a.set_velocity(velocity_a - normal * impulse * inverse_mass_a);
+                    enemy_b.set_velocity(velocity_b + normal * impulse * inverse_mass_b);
+                    bounce_position = Some(
+                        (transform_a.translation.truncate() + transform_b.translation.truncate())
+                            / 2.0,
+                    );
                 }
             }
         }
+
+        if let Some(position) = bounce_position {
+            commands.spawn(SoundEffect::at(SoundKind::Bounce, position));
+        }
     }
 
-    pub fn confine_enemy(
-        enemy_query: Query<&mut Transform, With<Enemy>>,
-        window_query: Query<&Window, With<PrimaryWindow>>,
-    ) {
-        if let Ok(window) = window_query.single() {
-            let half_enemy_size: f32 = ENEMY_SIZE / 2.0;
+    pub fn confine_enemy(enemy_query: Query<(&mut Transform, &Enemy)>, arena: Res<Arena>) {
+        for (mut transform, enemy) in enemy_query {
+            let half_enemy_size = enemy.radius();
             let x_min = 0.0 + half_enemy_size;
-            let x_max = window.width() - half_enemy_size;
+            let x_max = arena.size.x - half_enemy_size;
             let y_min = 0.0 + half_enemy_size;
-            let y_max = window.height() - half_enemy_size;
+            let y_max = arena.size.y - half_enemy_size;
+            let mut translation = transform.translation;
 
-            for mut transform in enemy_query {
-                let mut translation = transform.translation;
-
-                translation.x = translation.x.clamp(x_min, x_max);
-                translation.y = translation.y.clamp(y_min, y_max);
-                transform.translation = translation;
-            }
+            translation.x = translation.x.clamp(x_min, x_max);
+            translation.y = translation.y.clamp(y_min, y_max);
+            transform.translation = translation;
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn enemy_hit_player(
         mut commands: Commands,
         mut gameover_writer: MessageWriter<GameOver>,
-        mut player_query: Query<(Entity, &Transform), With<Player>>,
-        enemy_query: Query<&Transform, With<Enemy>>,
-        asset_server: Res<AssetServer>,
+        player_query: Query<(Entity, &Transform, &Player, Has<Invulnerable>)>,
+        enemy_query: Query<(&Transform, &Enemy), Without<Telegraphing>>,
+        game_assets: Res<GameAssets>,
         score: Res<Score>,
+        game_mode: Res<GameMode>,
+        mut lives: ResMut<Lives>,
     ) {
-        if let Ok((player_entity, player_transform)) = player_query.single_mut() {
-            for enemy_transform in enemy_query {
-                let distance = player_transform
-                    .translation
-                    .distance(enemy_transform.translation);
+        let mut survivors = Vec::new();
+        let mut hits = Vec::new();
 
-                let player_radius = PLAYER_SIZE / 2.0;
-                let enemy_radius = ENEMY_SIZE / 2.0;
+        for (player_entity, player_transform, player, invulnerable) in &player_query {
+            let hit = !invulnerable
+                && enemy_query.iter().any(|(enemy_transform, enemy)| {
+                    let distance = player_transform
+                        .translation
+                        .distance(enemy_transform.translation);
 
-                if distance < player_radius + enemy_radius {
-                    commands.entity(player_entity).despawn();
-                    commands.spawn(ExplosionSoundPlayer::new(&asset_server));
-                    gameover_writer.write(GameOver { score: score.value });
-                }
+                    distance < PLAYER_SIZE / 2.0 + enemy.radius()
+                });
+
+            if hit {
+                hits.push((player_entity, player_transform, player));
+            } else {
+                survivors.push(player.index);
             }
         }
+
+        // Every hit this frame costs a shared life at once, so simultaneous hits share
+        // one outcome instead of depending on query order.
+        let shared_lives = game_mode.shared_lives().is_some();
+        if shared_lives {
+            lives.value = lives.value.saturating_sub(hits.len() as u32);
+        }
+        let lives_left = shared_lives && lives.value > 0;
+        let was_hit = !hits.is_empty();
+
+        for (player_entity, player_transform, player) in hits {
+            commands.spawn(SoundEffect::at(
+                SoundKind::Explosion,
+                player_transform.translation.truncate(),
+            ));
+            commands.spawn(ParticleBurst::new(
+                EmitterKind::PlayerDebris,
+                player_transform.translation.truncate(),
+            ));
+            commands.spawn(CameraCue::Death);
+
+            if lives_left {
+                commands
+                    .entity(player_entity)
+                    .insert(Invulnerable::default());
+                survivors.push(player.index);
+                continue;
+            }
+
+            commands.entity(player_entity).despawn();
+            commands.spawn((
+                Sprite::from_image(SheetKind::player(player.index).image(&game_assets.sprites)),
+                SpriteAnimation::new(SheetKind::player(player.index), ClipKind::Despawn),
+                DespawnWhenFinished,
+                *player_transform,
+            ));
+        }
+
+        // An empty shared pool ends the run for the whole team.
+        if shared_lives && was_hit && !lives_left {
+            survivors.clear();
+        }
+
+        let player_count = player_query.iter().len();
+        let last_standing = *game_mode == GameMode::Versus && survivors.len() <= 1;
+
+        if survivors.len() < player_count && (survivors.is_empty() || last_standing) {
+            let results = (0..game_mode.player_count())
+                .map(|index| PlayerResult {
+                    index,
+                    score: score.players[index],
+                    winner: *game_mode == GameMode::Versus && survivors.contains(&index),
+                })
+                .collect();
+
+            gameover_writer.write(GameOver {
+                score: score.value,
+                results,
+            });
+        }
     }
 
     pub fn spawn_enemys_over_time(
         mut commands: Commands,
-        window_query: Query<&Window, With<PrimaryWindow>>,
-        asset_server: Res<AssetServer>,
+        arena: Res<Arena>,
+        game_assets: Res<GameAssets>,
         enemy_spawn_timer: Res<EnemySpawnTimer>,
+        level_library: Res<LevelLibrary>,
+        selected_level: Res<SelectedLevel>,
+        mut game_rng: ResMut<GameRng>,
     ) {
         if enemy_spawn_timer.timer.is_finished() {
-            let window = window_query.single().unwrap();
-
-            let random_x = random::<f32>() * window.width();
-            let random_y = random::<f32>() * window.height();
+            let size = random_size(&mut game_rng.rng);
+            let level = level_library.get(&selected_level);
+            let Some(position) = level.random_enemy_position(&arena, size, &mut game_rng.rng)
+            else {
+                warn!("No free spot for an enemy in level \"{}\"", level.name);
+                return;
+            };
 
             commands.spawn((
-                Enemy {
-                    direction: Vec2::new(random::<f32>(), random::<f32>()).normalize(),
-                },
-                Sprite {
-                    image: asset_server.load("sprites/ball_red_large.png"),
-                    ..Default::default()
-                },
-                Transform::from_xyz(random_x, random_y, 0.0),
+                Enemy::new(random_direction(&mut game_rng.rng), size),
+                Telegraphing::default(),
+                enemy_sprite(&game_assets, size),
+                SpriteAnimation::new(SheetKind::Enemy, ClipKind::SpawnIn),
+                Transform::from_xyz(position.x, position.y, 0.0),
+            ));
+        }
+    }
+
+    pub fn tick_telegraphs(
+        mut commands: Commands,
+        enemy_query: Query<(Entity, &mut Telegraphing), With<Enemy>>,
+        time: Res<Time>,
+    ) {
+        for (entity, mut telegraphing) in enemy_query {
+            telegraphing.timer.tick(time.delta());
+
+            if telegraphing.timer.is_finished() {
+                commands.entity(entity).remove::<Telegraphing>();
+            }
+        }
+    }
+
+    pub fn random_direction(rng: &mut impl Rng) -> Vec2 {
+        Vec2::new(rng.random::<f32>(), rng.random::<f32>()).normalize()
+    }
+
+    pub fn random_size(rng: &mut impl Rng) -> f32 {
+        rng.random_range(ENEMY_MIN_SIZE..=ENEMY_MAX_SIZE)
+    }
+
+    pub fn enemy_sprite(game_assets: &GameAssets, size: f32) -> Sprite {
+        Sprite {
+            image: SheetKind::Enemy.image(&game_assets.sprites),
+            custom_size: Some(Vec2::splat(size)),
+            ..Default::default()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use bevy::ecs::system::RunSystemOnce;
+
+        #[test]
+        fn telegraphing_enemy_keeps_still_when_hit() {
+            let mut world = World::new();
+            let start = Vec3::new(200.0, 200.0, 0.0);
+            let telegraphing = world
+                .spawn((
+                    Transform::from_translation(start),
+                    Enemy::new(Vec2::X, ENEMY_SIZE),
+                    Telegraphing::default(),
+                ))
+                .id();
+            world.spawn((
+                Transform::from_translation(start + Vec3::X * ENEMY_SIZE / 2.0),
+                Enemy::new(Vec2::NEG_X, ENEMY_SIZE),
             ));
+
+            world.run_system_once(enemy_hit_enemy).unwrap();
+
+            let transform = world.get::<Transform>(telegraphing).unwrap();
+            let enemy = world.get::<Enemy>(telegraphing).unwrap();
+            assert_eq!(transform.translation, start);
+            assert_eq!(enemy.direction, Vec2::X);
+            assert_eq!(enemy.speed, ENEMY_SPEED);
+        }
+    }
+}
+
+pub mod obstacle {
+    use super::super::{
+        components::{
+            enemy::{Enemy, Telegraphing},
+            obstacle::{BUMPER_COLOR, OBSTACLE_COLOR, Obstacle},
+            player::{Boosted, PLAYER_SIZE, Player, Velocity},
+        },
+        resources::level::{LevelLibrary, ObstacleShape, SelectedLevel},
+    };
+    use crate::audio::{components::mixer::SoundEffect, resources::mixer::SoundKind};
+    use bevy::prelude::*;
+
+    pub fn spawn_obstacles(
+        mut commands: Commands,
+        level_library: Res<LevelLibrary>,
+        selected_level: Res<SelectedLevel>,
+        mut meshes: ResMut<Assets<Mesh>>,
+        mut materials: ResMut<Assets<ColorMaterial>>,
+    ) {
+        for obstacle in &level_library.get(&selected_level).obstacles {
+            let transform = Transform::from_xyz(obstacle.position.x, obstacle.position.y, -1.0);
+
+            match obstacle.shape {
+                ObstacleShape::Rect { width, height } => {
+                    commands.spawn((
+                        Obstacle {
+                            shape: obstacle.shape,
+                        },
+                        Sprite {
+                            color: OBSTACLE_COLOR,
+                            custom_size: Some(Vec2::new(width, height)),
+                            ..Default::default()
+                        },
+                        transform,
+                    ));
+                }
+                ObstacleShape::Circle { radius } => {
+                    commands.spawn((
+                        Obstacle {
+                            shape: obstacle.shape,
+                        },
+                        Mesh2d(meshes.add(Circle::new(radius))),
+                        MeshMaterial2d(materials.add(OBSTACLE_COLOR)),
+                        transform,
+                    ));
+                }
+                ObstacleShape::Bumper { radius, .. } => {
+                    commands.spawn((
+                        Obstacle {
+                            shape: obstacle.shape,
+                        },
+                        Mesh2d(meshes.add(Circle::new(radius))),
+                        MeshMaterial2d(materials.add(BUMPER_COLOR)),
+                        transform,
+                    ));
+                }
+            }
+        }
+    }
+
+    pub fn despawn_obstacles(
+        mut commands: Commands,
+        obstacle_query: Query<Entity, With<Obstacle>>,
+    ) {
+        for entity in obstacle_query {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    pub fn enemy_hit_obstacle(
+        mut commands: Commands,
+        enemy_query: Query<(&mut Transform, &mut Enemy), Without<Telegraphing>>,
+        obstacle_query: Query<(&Transform, &Obstacle), Without<Enemy>>,
+        time: Res<Time>,
+    ) {
+        for (mut transform, mut enemy) in enemy_query {
+            for (obstacle_transform, obstacle) in &obstacle_query {
+                if let Some(contact) = obstacle.shape.circle_contact(
+                    obstacle_transform.translation.truncate(),
+                    transform.translation.truncate(),
+                    enemy.radius(),
+                ) {
+                    let mut push = contact.normal * contact.depth;
+                    let approaching = enemy.direction.dot(contact.normal) < 0.0;
+
+                    if let ObstacleShape::Bumper { strength, .. } = obstacle.shape {
+                        push += contact.normal * strength * time.delta_secs();
+                        if approaching {
+                            let velocity = enemy.velocity().reflect(contact.normal)
+                                + contact.normal * strength;
+                            enemy.set_velocity(velocity);
+                        }
+                    } else if approaching {
+                        enemy.direction = enemy.direction.reflect(contact.normal);
+                    }
+
+                    transform.translation += push.extend(0.0);
+
+                    if approaching {
+                        commands.spawn(SoundEffect::at(
+                            SoundKind::Bounce,
+                            transform.translation.truncate(),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn player_hit_obstacle(
+        mut commands: Commands,
+        player_query: Query<(Entity, &mut Transform, &mut Velocity), With<Player>>,
+        obstacle_query: Query<(&Transform, &Obstacle), Without<Player>>,
+        time: Res<Time>,
+    ) {
+        for (entity, mut transform, mut velocity) in player_query {
+            for (obstacle_transform, obstacle) in &obstacle_query {
+                if let Some(contact) = obstacle.shape.circle_contact(
+                    obstacle_transform.translation.truncate(),
+                    transform.translation.truncate(),
+                    PLAYER_SIZE / 2.0,
+                ) {
+                    let mut push = contact.normal * contact.depth;
+                    let approach_speed = velocity.value.dot(contact.normal);
+
+                    if let ObstacleShape::Bumper { strength, .. } = obstacle.shape {
+                        push += contact.normal * strength * time.delta_secs();
+                        if approach_speed < 0.0 {
+                            velocity.value =
+                                velocity.value.reflect(contact.normal) + contact.normal * strength;
+                            commands.entity(entity).insert(Boosted::default());
+                        }
+                    } else if approach_speed < 0.0 {
+                        // Slide along the obstacle instead of sinking into it.
+                        velocity.value -= contact.normal * approach_speed;
+                    }
+
+                    transform.translation += push.extend(0.0);
+                }
+            }
+        }
+    }
+}
+
+pub mod replay {
+    use super::super::{
+        components::player::{Player, PlayerControls, RemoteInput},
+        messages::game_states::GameOver,
+        resources::{
+            arena::Arena,
+            controls::ControlScheme,
+            level::{LevelLibrary, Point, SelectedLevel},
+            mode::GameMode,
+            network::NetworkRole,
+            random::GameRng,
+            replay::{Replay, ReplayFrame, ReplayOutcome},
+        },
+    };
+    use super::player::player_direction;
+    use bevy::prelude::*;
+
+    /// Only offline runs are recorded; networked ones can't be re-run from one app's input.
+    #[allow(clippy::too_many_arguments)]
+    pub fn start_replay(
+        mut commands: Commands,
+        arena: Res<Arena>,
+        network_role: Res<NetworkRole>,
+        game_rng: Res<GameRng>,
+        game_mode: Res<GameMode>,
+        control_scheme: Res<ControlScheme>,
+        level_library: Res<LevelLibrary>,
+        selected_level: Res<SelectedLevel>,
+    ) {
+        if *network_role != NetworkRole::Offline {
+            commands.remove_resource::<Replay>();
+            return;
+        }
+
+        commands.insert_resource(Replay {
+            seed: game_rng.seed,
+            mode: *game_mode,
+            control_scheme: *control_scheme,
+            level: level_library.get(&selected_level).clone(),
+            arena: arena.size.into(),
+            frames: Vec::new(),
+            outcome: None,
+        });
+    }
+
+    pub fn record_replay_frame(
+        mut replay: ResMut<Replay>,
+        keyboard_input: Res<ButtonInput<KeyCode>>,
+        gamepad_query: Query<&Gamepad>,
+        player_query: Query<(&Player, &PlayerControls, Option<&RemoteInput>)>,
+        time: Res<Time>,
+    ) {
+        let gamepads: Vec<&Gamepad> = gamepad_query.iter().collect();
+        let mut inputs = vec![Point::default(); replay.mode.player_count()];
+
+        for (player, controls, remote_input) in &player_query {
+            if let Some(input) = inputs.get_mut(player.index) {
+                *input =
+                    player_direction(controls, remote_input, &keyboard_input, &gamepads).into();
+            }
+        }
+
+        replay.frames.push(ReplayFrame {
+            delta_nanos: time.delta().as_nanos() as u64,
+            inputs,
+        });
+    }
+
+    pub fn finish_replay(mut replay: ResMut<Replay>, mut gameover_reader: MessageReader<GameOver>) {
+        let Some(game_over) = gameover_reader.read().next() else {
+            return;
+        };
+        if replay.outcome.is_some() {
+            return;
+        }
+
+        replay.outcome = Some(ReplayOutcome {
+            score: game_over.score,
+            results: game_over.results.clone(),
+            game_over_tick: replay.frames.len() as u32,
+        });
+
+        match replay.save() {
+            Ok(path) => info!("Saved replay to {}", path.display()),
+            Err(err) => error!("Could not save replay: {err}"),
         }
     }
 }
 
 pub mod camera {
+    use super::super::resources::arena::{ARENA_HEIGHT, ARENA_WIDTH, Arena};
     use bevy::{
+        camera::ScalingMode,
         prelude::*,
         window::{PrimaryWindow, WindowResolution},
     };
@@ -341,29 +987,64 @@ pub mod camera {
     pub fn spawn_camera(
         mut commands: Commands,
         mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+        arena: Res<Arena>,
     ) {
         let mut window = window_query.single_mut().unwrap();
 
-        window.resolution = WindowResolution::new(1280, 720);
-        //window.resizable = false;
+        window.resolution = WindowResolution::new(ARENA_WIDTH as u32, ARENA_HEIGHT as u32);
 
+        // Fit the whole arena in view whatever the window size.
         commands.spawn((
-            Camera2d::default(),
-            Transform::from_xyz(window.width() / 2.0, window.height() / 2.0, 0.0),
+            Camera2d,
+            Projection::Orthographic(OrthographicProjection {
+                scaling_mode: ScalingMode::AutoMin {
+                    min_width: arena.size.x,
+                    min_height: arena.size.y,
+                },
+                ..OrthographicProjection::default_2d()
+            }),
+            Transform::from_translation(arena.center().extend(0.0)),
         ));
     }
 
     pub fn camera_position(
         mut camera_query: Query<&mut Transform, With<Camera2d>>,
-        window_query: Query<&Window, With<PrimaryWindow>>,
+        arena: Res<Arena>,
     ) {
-        if let (Ok(mut camera), Ok(window)) = (camera_query.single_mut(), window_query.single()) {
-            camera.translation.x = window.width() / 2.0;
-            camera.translation.y = window.height() / 2.0;
+        if let Ok(mut camera) = camera_query.single_mut() {
+            camera.translation.x = arena.center().x;
+            camera.translation.y = arena.center().y;
         }
     }
 }
 
+pub mod network {
+    use super::super::resources::network::NetworkRole;
+    use bevy::prelude::*;
+
+    /// Run condition for spawning the world: clients and spectators only mirror a
+    /// remote simulation.
+    pub fn runs_simulation(network_role: Res<NetworkRole>) -> bool {
+        !matches!(*network_role, NetworkRole::Client | NetworkRole::Spectator)
+    }
+
+    /// Run condition for stepping the simulation once per frame. Rollback sessions
+    /// step it themselves at a fixed rate.
+    pub fn steps_each_frame(network_role: Res<NetworkRole>) -> bool {
+        matches!(*network_role, NetworkRole::Offline | NetworkRole::Host)
+    }
+
+    /// Run condition for showing the start countdown; everyone but spectators plays it.
+    pub fn shows_countdown(network_role: Res<NetworkRole>) -> bool {
+        *network_role != NetworkRole::Spectator
+    }
+
+    /// Run condition for pinning the camera to the arena; spectators move it freely.
+    pub fn camera_follows_window(network_role: Res<NetworkRole>) -> bool {
+        *network_role != NetworkRole::Spectator
+    }
+}
+
 pub mod timers {
     use super::super::resources::timers::{EnemySpawnTimer, StarSpawnTimer};
     use bevy::prelude::*;
@@ -380,37 +1061,105 @@ pub mod timers {
 pub mod game_state {
     use crate::AppState;
     use crate::game::resources::score::Score;
+    use crate::transition::{
+        messages::{TransitionEffect, TransitionRequest},
+        resources::{ScreenTransition, TransitionPhase},
+    };
 
     use super::super::messages::game_states::GameOver;
-    use super::super::resources::score::HighScores;
+    use super::super::resources::{
+        controls::MovementConfig,
+        level::{LevelLibrary, SelectedLevel},
+        mode::{GameMode, Lives},
+        network::NetworkRole,
+        random::{GameRng, MatchSeed},
+        score::{HighScores, RunResults},
+        timers::{EnemySpawnTimer, StarSpawnTimer},
+    };
+    use super::super::schedules::GameplayStep;
     use super::super::states::SimulationState;
     use bevy::prelude::*;
 
-    pub fn exit_game(keyboard_input: Res<ButtonInput<KeyCode>>, mut commands: Commands) {
-        if keyboard_input.just_pressed(KeyCode::Escape) {
-            commands.write_message(AppExit::Success);
+    /// Escape pauses runs this app steps itself, and leaves the ones it follows over
+    /// the network for the main menu. Quitting is left to the pause menu.
+    pub fn escape_game(
+        keyboard_input: Res<ButtonInput<KeyCode>>,
+        network_role: Res<NetworkRole>,
+        mut change_state: ResMut<NextState<SimulationState>>,
+        mut transition_writer: MessageWriter<TransitionRequest>,
+    ) {
+        if !keyboard_input.just_pressed(KeyCode::Escape) {
+            return;
         }
+
+        match *network_role {
+            NetworkRole::Offline | NetworkRole::Host => {
+                change_state.set(SimulationState::GamePaused);
+            }
+            NetworkRole::Client | NetworkRole::Rollback | NetworkRole::Spectator => {
+                transition_writer.write(TransitionRequest::new(
+                    AppState::MainMenu,
+                    TransitionEffect::Slide,
+                ));
+            }
+        }
+    }
+
+    /// Run condition: the screen is being covered to leave or restart the run, which
+    /// stays frozen behind the overlay until the state switches.
+    pub fn leaving_game(screen_transition: Res<ScreenTransition>) -> bool {
+        screen_transition
+            .active
+            .is_some_and(|active| active.phase == TransitionPhase::Out)
+    }
+
+    /// A transition from the game back into it restarts the run. Bevy only runs
+    /// `OnTransition` when a state is set to itself, so this leaves and enters the
+    /// game and its simulation state the way a switch through another screen would.
+    pub fn restart_game(world: &mut World) {
+        let simulation_state = *world.resource::<State<SimulationState>>().get();
+        let _ = world.try_run_schedule(OnExit(simulation_state));
+        let _ = world.try_run_schedule(OnExit(AppState::Game));
+
+        world.insert_resource(State::new(SimulationState::default()));
+        world.insert_resource(NextState::<SimulationState>::Unchanged);
+        let _ = world.try_run_schedule(OnEnter(AppState::Game));
+        let _ = world.try_run_schedule(OnEnter(SimulationState::default()));
     }
 
+    /// Only the first game over of a run counts; see `leaving_game`.
     pub fn handle_game_over(
+        mut commands: Commands,
         mut gameover_reader: MessageReader<GameOver>,
-        mut change_app_state: ResMut<NextState<AppState>>,
+        mut transition_writer: MessageWriter<TransitionRequest>,
     ) {
-        for game_over in gameover_reader.read() {
-            println!("Game Over: {}", game_over.score);
-            change_app_state.set(AppState::GameOver);
-        }
+        let Some(game_over) = gameover_reader.read().reduce(|first, _| first) else {
+            return;
+        };
+
+        info!("Game over with score {}", game_over.score);
+        commands.insert_resource(RunResults {
+            score: game_over.score,
+            results: game_over.results.clone(),
+        });
+        transition_writer.write(TransitionRequest::new(
+            AppState::GameOver,
+            TransitionEffect::Fade,
+        ));
     }
 
     pub fn update_high_scores(
         mut gameover_reader: MessageReader<GameOver>,
         mut high_scores: ResMut<HighScores>,
     ) {
-        for game_over in gameover_reader.read() {
-            high_scores
-                .scores
-                .push(("Player".to_string(), game_over.score));
-            println!("{:?}", ("Player".to_string(), game_over.score));
+        let Some(game_over) = gameover_reader.read().reduce(|first, _| first) else {
+            return;
+        };
+
+        for result in &game_over.results {
+            let entry = (format!("Player {}", result.index + 1), result.score);
+            debug!("New high score entry {entry:?}");
+            high_scores.scores.push(entry);
         }
     }
 
@@ -422,9 +1171,9 @@ pub mod game_state {
         if keyboard_input.just_pressed(KeyCode::Space) {
             match state.get() {
                 SimulationState::GamePaused => {
-                    change_state.set(SimulationState::GameRunning);
+                    change_state.set(SimulationState::Countdown);
                 }
-                SimulationState::GameRunning => {
+                SimulationState::Countdown | SimulationState::GameRunning => {
                     change_state.set(SimulationState::GamePaused);
                 }
             }
@@ -433,19 +1182,48 @@ pub mod game_state {
 
     pub fn transition_to_main_menu(
         app_state: Res<State<AppState>>,
-        mut change_app_state: ResMut<NextState<AppState>>,
-        mut change_game_state: ResMut<NextState<SimulationState>>,
+        mut transition_writer: MessageWriter<TransitionRequest>,
         keyboard_input: Res<ButtonInput<KeyCode>>,
     ) {
         if keyboard_input.just_pressed(KeyCode::Backspace) && *app_state.get() != AppState::MainMenu
         {
-            change_app_state.set(AppState::MainMenu);
-            change_game_state.set(SimulationState::GameRunning);
+            transition_writer.write(TransitionRequest::new(
+                AppState::MainMenu,
+                TransitionEffect::Slide,
+            ));
         }
     }
 
-    pub fn insert_score(mut commands: Commands) {
+    pub fn run_gameplay_step(world: &mut World) {
+        world.run_schedule(GameplayStep);
+    }
+
+    /// Starts every run from the same state: a known seed, fresh spawn timers and
+    /// the level's movement tuning.
+    pub fn prepare_run(
+        mut game_rng: ResMut<GameRng>,
+        mut match_seed: ResMut<MatchSeed>,
+        mut star_spawn_timer: ResMut<StarSpawnTimer>,
+        mut enemy_spawn_timer: ResMut<EnemySpawnTimer>,
+        mut movement_config: ResMut<MovementConfig>,
+        level_library: Res<LevelLibrary>,
+        selected_level: Res<SelectedLevel>,
+    ) {
+        *game_rng = match match_seed.value.take() {
+            Some(seed) => GameRng::new(seed),
+            None => GameRng::default(),
+        };
+        info!("Run seed: {}", game_rng.seed);
+        *star_spawn_timer = StarSpawnTimer::default();
+        *enemy_spawn_timer = EnemySpawnTimer::default();
+        *movement_config = level_library.get(&selected_level).movement;
+    }
+
+    pub fn insert_score(mut commands: Commands, game_mode: Res<GameMode>) {
         commands.insert_resource(Score::default());
+        commands.insert_resource(Lives {
+            value: game_mode.shared_lives().unwrap_or(0),
+        });
     }
 
     pub fn remove_score(mut commands: Commands) {
@@ -458,12 +1236,19 @@ pub mod ui {
         use super::super::super::components::ui::hud::*;
         use super::super::super::components::*;
         use super::super::super::resources;
+        use super::super::super::resources::mode::GameMode;
+        use crate::loading::resources::GameAssets;
+        use crate::widgets::components::Themed;
         use bevy::prelude::*;
 
-        pub fn spawn_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
+        pub fn spawn_hud(
+            mut commands: Commands,
+            game_assets: Res<GameAssets>,
+            game_mode: Res<GameMode>,
+        ) {
             commands
                 .spawn((
-                    HUD,
+                    Hud,
                     Node {
                         width: Val::Percent(100.0),
                         height: Val::Percent(100.0),
@@ -474,93 +1259,98 @@ pub mod ui {
                     },
                 ))
                 .with_children(|p| {
-                    p.spawn((
-                        Node {
-                            width: Val::Px(150.0),
-                            height: Val::Px(80.0),
-
-                            margin: UiRect::all(Val::Px(32.0)),
-
-                            flex_direction: FlexDirection::Row,
-                            justify_content: JustifyContent::SpaceAround,
-                            align_items: AlignItems::Center,
-
-                            ..Default::default()
-                        },
-                        BackgroundColor(Color::linear_rgba(0.15, 0.15, 0.15, 0.5)),
-                    ))
+                    p.spawn(Node {
+                        flex_direction: FlexDirection::Row,
+                        ..Default::default()
+                    })
                     .with_children(|p| {
-                        p.spawn(Node {
-                            width: Val::Px(32.0),
-                            height: Val::Px(32.0),
-                            ..Default::default()
-                        })
-                        .with_child((ImageNode {
-                            image: asset_server.load("sprites/star.png"),
-                            ..Default::default()
-                        },));
-
-                        p.spawn((
-                            Score,
-                            Text("0".into()),
-                            TextLayout {
-                                justify: Justify::Center,
-                                linebreak: LineBreak::NoWrap,
-                            },
-                            TextFont {
-                                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                                font_size: 48.0,
-                                ..Default::default()
-                            },
-                            TextColor::WHITE,
-                        ));
+                        if *game_mode == GameMode::Single {
+                            spawn_hud_box(
+                                p,
+                                game_assets.sprites.star.clone(),
+                                Color::WHITE,
+                                (Score, hud_text()),
+                            );
+                        } else {
+                            for index in 0..game_mode.player_count() {
+                                spawn_hud_box(
+                                    p,
+                                    game_assets.sprites.ball_blue.clone(),
+                                    player::PLAYER_COLORS[index],
+                                    (PlayerScore { index }, hud_text()),
+                                );
+                            }
+                        }
+
+                        if *game_mode == GameMode::Coop {
+                            spawn_hud_box(
+                                p,
+                                game_assets.sprites.ball_blue.clone(),
+                                Color::linear_rgb(1.0, 0.3, 0.3),
+                                (Lives, hud_text()),
+                            );
+                        }
                     });
 
-                    p.spawn((
-                        Node {
-                            width: Val::Px(150.0),
-                            height: Val::Px(80.0),
+                    spawn_hud_box(
+                        p,
+                        game_assets.sprites.ball_red.clone(),
+                        Color::WHITE,
+                        (Enemy, hud_text()),
+                    );
+                });
+        }
+
+        pub fn spawn_hud_box(
+            parent: &mut ChildSpawnerCommands,
+            image: Handle<Image>,
+            tint: Color,
+            text: impl Bundle,
+        ) {
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Px(150.0),
+                        height: Val::Px(80.0),
+
+                        margin: UiRect::all(Val::Px(32.0)),
 
-                            margin: UiRect::all(Val::Px(32.0)),
+                        flex_direction: FlexDirection::Row,
+                        justify_content: JustifyContent::SpaceAround,
+                        align_items: AlignItems::Center,
 
-                            flex_direction: FlexDirection::Row,
-                            justify_content: JustifyContent::SpaceAround,
-                            align_items: AlignItems::Center,
+                        ..Default::default()
+                    },
+                    Themed::HudBox,
+                ))
+                .with_children(|p| {
+                    p.spawn(Node {
+                        width: Val::Px(32.0),
+                        height: Val::Px(32.0),
+                        ..Default::default()
+                    })
+                    .with_child((ImageNode {
+                        image,
+                        color: tint,
+                        ..Default::default()
+                    },));
 
-                            ..Default::default()
-                        },
-                        BackgroundColor(Color::linear_rgba(0.15, 0.15, 0.15, 0.5)),
-                    ))
-                    .with_children(|p| {
-                        p.spawn(Node {
-                            width: Val::Px(32.0),
-                            height: Val::Px(32.0),
-                            ..Default::default()
-                        })
-                        .with_child((ImageNode {
-                            image: asset_server.load("sprites/ball_red_large.png"),
-                            ..Default::default()
-                        },));
-
-                        p.spawn((
-                            Enemy,
-                            Text("0".into()),
-                            TextLayout {
-                                justify: Justify::Center,
-                                linebreak: LineBreak::NoWrap,
-                            },
-                            TextFont {
-                                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                                font_size: 48.0,
-                                ..Default::default()
-                            },
-                            TextColor::WHITE,
-                        ));
-                    });
+                    p.spawn(text);
                 });
         }
 
-        pub fn despawn_hud(mut commands: Commands, hud_query: Query<Entity, With<HUD>>) {
+        pub fn hud_text() -> impl Bundle {
+            (
+                Themed::HudText,
+                Text("0".into()),
+                TextLayout {
+                    justify: Justify::Center,
+                    linebreak: LineBreak::NoWrap,
+                },
+            )
+        }
+
+        pub fn despawn_hud(mut commands: Commands, hud_query: Query<Entity, With<Hud>>) {
             let hud = hud_query.single().unwrap();
             commands.entity(hud).despawn();
         }
@@ -575,6 +1365,26 @@ pub mod ui {
             }
         }
 
+        pub fn update_player_score_text(
+            text_scores: Query<(&mut Text, &PlayerScore)>,
+            score: Res<resources::score::Score>,
+        ) {
+            for (mut text, player_score) in text_scores {
+                let score = score.players[player_score.index];
+                text.0 = format!("{score}");
+            }
+        }
+
+        pub fn update_lives_text(
+            mut text_lives: Query<&mut Text, With<Lives>>,
+            lives: Res<resources::mode::Lives>,
+        ) {
+            if let Ok(mut text) = text_lives.single_mut() {
+                let lives = lives.value;
+                text.0 = format!("{lives}");
+            }
+        }
+
         pub fn update_enemys_text(
             mut text_enemy: Query<&mut Text, With<Enemy>>,
             enemys_query: Query<Entity, With<enemy::Enemy>>,
@@ -587,77 +1397,22 @@ pub mod ui {
     }
 
     pub mod pause_menu {
-        pub const NORMAL_BUTTON_COLOR: Color = Color::linear_rgb(0.15, 0.15, 0.15);
-        pub const HOVERED_BUTTON_COLOR: Color = Color::linear_rgb(0.25, 0.25, 0.25);
-        pub const PRESSED_BUTTON_COLOR: Color = Color::linear_rgb(0.35, 0.75, 0.35);
-
-        use crate::{AppState, game::states::SimulationState};
+        use crate::game::states::SimulationState;
+        use crate::widgets::{builders::*, components::BackAction, messages::ButtonAction};
 
         use super::super::super::components::ui::pause_menu::*;
         use bevy::prelude::*;
 
-        pub fn spawn_pause_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+        pub fn spawn_pause_menu(mut commands: Commands) {
             commands
-                .spawn((
-                    PauseMenuContainer,
-                    Node {
-                        padding: UiRect::axes(Val::Px(64.0), Val::Px(64.0)),
-                        width: Val::Vw(100.0),
-                        height: Val::Vh(100.0),
-
-                        flex_direction: FlexDirection::Column,
-                        justify_content: JustifyContent::Center,
-                        align_items: AlignItems::Center,
-
-                        ..Default::default()
-                    },
-                ))
+                .spawn((screen(PauseMenuContainer), BackAction(ButtonAction::Resume)))
                 .with_children(|p| {
-                    let _container = p
-                        .spawn((
-                            Node {
-                                padding: UiRect::axes(Val::Px(64.0), Val::Px(64.0)),
-                                width: Val::Auto,
-                                height: Val::Auto,
-
-                                flex_direction: FlexDirection::Column,
-                                justify_content: JustifyContent::Center,
-                                align_items: AlignItems::Center,
-
-                                row_gap: Val::Px(16.0),
-
-                                ..Default::default()
-                            },
-                            BackgroundColor(Color::linear_rgba(0.15, 0.15, 0.15, 0.5)),
-                        ))
-                        .with_children(|p| {
-                            // Title
-                            p.spawn((
-                                Text::new("Pause"),
-                                TextLayout {
-                                    justify: Justify::Center,
-                                    linebreak: LineBreak::NoWrap,
-                                },
-                                TextFont {
-                                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                                    font_size: 64.0,
-                                    ..Default::default()
-                                },
-                                TextColor::WHITE,
-                            ));
-
-                            // Button Resume
-                            p.spawn(create_button(ButtonResume))
-                                .with_child(create_text_button("Resume", &asset_server));
-
-                            // Button Main Menu
-                            p.spawn(create_button(ButtonMainMenu))
-                                .with_child(create_text_button("Main Menu", &asset_server));
-
-                            // Button Quit
-                            p.spawn(create_button(ButtonQuit))
-                                .with_child(create_text_button("Quit", &asset_server));
-                        });
+                    p.spawn(panel()).with_children(|p| {
+                        p.spawn(title("Pause"));
+                        p.spawn(button(ButtonAction::Resume, "Resume"));
+                        p.spawn(button(ButtonAction::MainMenu, "Main Menu"));
+                        p.spawn(button(ButtonAction::Quit, "Quit"));
+                    });
                 });
         }
 
@@ -670,184 +1425,59 @@ pub mod ui {
             }
         }
 
-        pub fn create_button(component: impl Component) -> impl Bundle {
-            (
-                component,
-                Node {
-                    width: Val::Px(300.0),
-                    height: Val::Px(100.0),
-                    justify_content: JustifyContent::Center,
-                    align_items: AlignItems::Center,
-                    ..default()
-                },
-                BackgroundColor(NORMAL_BUTTON_COLOR),
-                Button,
-            )
-        }
-
-        pub fn create_text_button(
-            text: impl Into<String>,
-            asset_server: &Res<AssetServer>,
-        ) -> impl Bundle {
-            (
-                Text::new(text),
-                TextLayout::new_with_justify(Justify::Center),
-                TextFont {
-                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                    font_size: 32.0,
-                    ..Default::default()
-                },
-                TextColor::WHITE,
-            )
-        }
-
-        pub fn interact_with_resume_button(
-            mut button_query: Query<
-                (&Interaction, &mut BackgroundColor),
-                (Changed<Interaction>, With<ButtonResume>),
-            >,
+        pub fn handle_pause_actions(
+            mut action_reader: MessageReader<ButtonAction>,
             mut changed_state: ResMut<NextState<SimulationState>>,
         ) {
-            if let Ok((interaction, mut background)) = button_query.single_mut() {
-                match *interaction {
-                    Interaction::Hovered => {
-                        background.0 = HOVERED_BUTTON_COLOR;
-                    }
-                    Interaction::Pressed => {
-                        background.0 = PRESSED_BUTTON_COLOR;
-                        changed_state.set(SimulationState::GameRunning);
-                    }
-                    Interaction::None => {
-                        background.0 = NORMAL_BUTTON_COLOR;
-                    }
-                }
-            }
-        }
-
-        pub fn interact_with_main_menu_button(
-            mut button_query: Query<
-                (&Interaction, &mut BackgroundColor),
-                (Changed<Interaction>, With<ButtonMainMenu>),
-            >,
-            mut changed_app_state: ResMut<NextState<AppState>>,
-            mut changed_game_state: ResMut<NextState<SimulationState>>,
-        ) {
-            if let Ok((interaction, mut background)) = button_query.single_mut() {
-                match *interaction {
-                    Interaction::Hovered => {
-                        background.0 = HOVERED_BUTTON_COLOR;
-                    }
-                    Interaction::Pressed => {
-                        background.0 = PRESSED_BUTTON_COLOR;
-                        changed_game_state.set(SimulationState::GameRunning);
-                        changed_app_state.set(AppState::MainMenu);
-                    }
-                    Interaction::None => {
-                        background.0 = NORMAL_BUTTON_COLOR;
-                    }
-                }
-            }
-        }
-
-        pub fn interact_with_quit_button(
-            mut button_query: Query<
-                (&Interaction, &mut BackgroundColor),
-                (Changed<Interaction>, With<ButtonQuit>),
-            >,
-            mut commands: Commands,
-        ) {
-            if let Ok((interaction, mut background)) = button_query.single_mut() {
-                match *interaction {
-                    Interaction::Hovered => {
-                        background.0 = HOVERED_BUTTON_COLOR;
-                    }
-                    Interaction::Pressed => {
-                        background.0 = PRESSED_BUTTON_COLOR;
-                        commands.write_message(AppExit::Success);
-                    }
-                    Interaction::None => {
-                        background.0 = NORMAL_BUTTON_COLOR;
-                    }
+            for action in action_reader.read() {
+                if *action == ButtonAction::Resume {
+                    changed_state.set(SimulationState::Countdown);
                 }
             }
         }
     }
 
     pub mod gameover {
-        pub const NORMAL_BUTTON_COLOR: Color = Color::linear_rgb(0.15, 0.15, 0.15);
-        pub const HOVERED_BUTTON_COLOR: Color = Color::linear_rgb(0.25, 0.25, 0.25);
-        pub const PRESSED_BUTTON_COLOR: Color = Color::linear_rgb(0.35, 0.75, 0.35);
-
-        use crate::{AppState, game::states::SimulationState};
+        use crate::AppState;
+        use crate::transition::messages::{TransitionEffect, TransitionRequest};
+        use crate::widgets::{builders::*, components::BackAction, messages::ButtonAction};
 
         use super::super::super::components::ui::gameover::*;
+        use super::super::super::resources::score::RunResults;
         use bevy::prelude::*;
 
-        pub fn spawn_gameover_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+        pub fn spawn_gameover_menu(mut commands: Commands, run_results: Res<RunResults>) {
             commands
                 .spawn((
-                    GameOverContainer,
-                    Node {
-                        padding: UiRect::axes(Val::Px(64.0), Val::Px(64.0)),
-                        width: Val::Vw(100.0),
-                        height: Val::Vh(100.0),
-
-                        flex_direction: FlexDirection::Column,
-                        justify_content: JustifyContent::Center,
-                        align_items: AlignItems::Center,
-
-                        ..Default::default()
-                    },
+                    screen(GameOverContainer),
+                    BackAction(ButtonAction::MainMenu),
                 ))
                 .with_children(|p| {
-                    let _container = p
-                        .spawn((
-                            Node {
-                                padding: UiRect::axes(Val::Px(64.0), Val::Px(64.0)),
-                                width: Val::Auto,
-                                height: Val::Auto,
-
-                                flex_direction: FlexDirection::Column,
-                                justify_content: JustifyContent::Center,
-                                align_items: AlignItems::Center,
-
-                                row_gap: Val::Px(16.0),
-
-                                ..Default::default()
-                            },
-                            BackgroundColor(Color::linear_rgba(0.15, 0.15, 0.15, 0.5)),
-                        ))
-                        .with_children(|p| {
-                            // Title
-                            p.spawn((
-                                Text::new("GameOver"),
-                                TextLayout {
-                                    justify: Justify::Center,
-                                    linebreak: LineBreak::NoWrap,
-                                },
-                                TextFont {
-                                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                                    font_size: 64.0,
-                                    ..Default::default()
-                                },
-                                TextColor::WHITE,
-                            ));
-
-                            // Button Resume
-                            p.spawn(create_button(ButtonRestart))
-                                .with_child(create_text_button("Restart", &asset_server));
-
-                            // Button Main Menu
-                            p.spawn(create_button(ButtonMainMenu))
-                                .with_child(create_text_button("Main Menu", &asset_server));
-
-                            // Button Quit
-                            p.spawn(create_button(ButtonQuit))
-                                .with_child(create_text_button("Quit", &asset_server));
-                        });
+                    p.spawn(panel()).with_children(|p| {
+                        p.spawn(title("GameOver"));
+                        p.spawn(list(results_lines(&run_results)));
+                        p.spawn(button(ButtonAction::Restart, "Restart"));
+                        p.spawn(button(ButtonAction::MainMenu, "Main Menu"));
+                        p.spawn(button(ButtonAction::Quit, "Quit"));
+                    });
                 });
         }
 
+        pub fn results_lines(run_results: &RunResults) -> Vec<String> {
+            if run_results.results.len() <= 1 {
+                return vec![format!("Score: {}", run_results.score)];
+            }
+
+            run_results
+                .results
+                .iter()
+                .map(|result| {
+                    let winner = if result.winner { " - Winner!" } else { "" };
+                    format!("Player {}: {}{winner}", result.index + 1, result.score)
+                })
+                .collect()
+        }
+
         pub fn despawn_gameover_menu(
             mut commands: Commands,
             pause_menu: Query<Entity, With<GameOverContainer>>,
@@ -857,107 +1487,142 @@ pub mod ui {
             }
         }
 
-        pub fn create_button(component: impl Component) -> impl Bundle {
-            (
-                component,
+        pub fn handle_gameover_actions(
+            mut action_reader: MessageReader<ButtonAction>,
+            mut transition_writer: MessageWriter<TransitionRequest>,
+        ) {
+            for action in action_reader.read() {
+                if *action == ButtonAction::Restart {
+                    transition_writer.write(TransitionRequest::new(
+                        AppState::Game,
+                        TransitionEffect::Wipe,
+                    ));
+                }
+            }
+        }
+    }
+
+    pub mod countdown {
+        use super::super::super::components::ui::countdown::*;
+        use super::super::super::resources::countdown::{Countdown, GO_SECS};
+        use crate::game::states::SimulationState;
+        use crate::widgets::{
+            components::Themed,
+            resources::{UiTheme, rgba},
+        };
+        use bevy::prelude::*;
+
+        /// Seconds a new number takes to shrink from its pop-in size.
+        pub const POP_SECS: f32 = 0.25;
+
+        pub fn start_countdown(
+            mut commands: Commands,
+            overlay_query: Query<Entity, With<CountdownOverlay>>,
+        ) {
+            for entity in &overlay_query {
+                commands.entity(entity).despawn();
+            }
+
+            let countdown = Countdown::default();
+            commands.spawn((
+                CountdownOverlay,
                 Node {
-                    width: Val::Px(300.0),
-                    height: Val::Px(100.0),
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
                     justify_content: JustifyContent::Center,
                     align_items: AlignItems::Center,
-                    ..default()
+                    ..Default::default()
                 },
-                BackgroundColor(NORMAL_BUTTON_COLOR),
-                Button,
-            )
+                Pickable::IGNORE,
+                children![(
+                    CountdownText::default(),
+                    Themed::Countdown,
+                    Text::new(countdown.remaining.to_string()),
+                    UiTransform::default(),
+                )],
+            ));
+            commands.insert_resource(countdown);
         }
 
-        pub fn create_text_button(
-            text: impl Into<String>,
-            asset_server: &Res<AssetServer>,
-        ) -> impl Bundle {
-            (
-                Text::new(text),
-                TextLayout::new_with_justify(Justify::Center),
-                TextFont {
-                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                    font_size: 32.0,
-                    ..Default::default()
-                },
-                TextColor::WHITE,
-            )
+        /// Spectators usually join a run that is already under way, so they start
+        /// right away.
+        pub fn skip_countdown(mut change_state: ResMut<NextState<SimulationState>>) {
+            change_state.set(SimulationState::GameRunning);
         }
 
-        pub fn interact_with_restart_button(
-            mut button_query: Query<
-                (&Interaction, &mut BackgroundColor),
-                (Changed<Interaction>, With<ButtonRestart>),
-            >,
-            mut changed_app_state: ResMut<NextState<AppState>>,
-            mut changed_game_state: ResMut<NextState<SimulationState>>,
+        /// Counts down on apps that step the simulation themselves. Clients follow the
+        /// host's count and rollback peers count in frames, see the net module.
+        pub fn tick_countdown(
+            mut countdown: ResMut<Countdown>,
+            mut change_state: ResMut<NextState<SimulationState>>,
+            time: Res<Time<Real>>,
         ) {
-            if let Ok((interaction, mut background)) = button_query.single_mut() {
-                match *interaction {
-                    Interaction::Hovered => {
-                        background.0 = HOVERED_BUTTON_COLOR;
-                    }
-                    Interaction::Pressed => {
-                        background.0 = PRESSED_BUTTON_COLOR;
-                        changed_game_state.set(SimulationState::GameRunning);
-                        changed_app_state.set(AppState::Game);
-                    }
-                    Interaction::None => {
-                        background.0 = NORMAL_BUTTON_COLOR;
-                    }
-                }
+            if !countdown.timer.tick(time.delta()).just_finished() {
+                return;
+            }
+
+            countdown.remaining = countdown.remaining.saturating_sub(1);
+            if countdown.remaining == 0 {
+                change_state.set(SimulationState::GameRunning);
             }
         }
 
-        pub fn interact_with_main_menu_button(
-            mut button_query: Query<
-                (&Interaction, &mut BackgroundColor),
-                (Changed<Interaction>, With<ButtonMainMenu>),
-            >,
-            mut changed_app_state: ResMut<NextState<AppState>>,
-            mut changed_game_state: ResMut<NextState<SimulationState>>,
+        pub fn show_countdown(
+            mut text_query: Query<(&mut Text, &mut CountdownText)>,
+            countdown: Res<Countdown>,
         ) {
-            if let Ok((interaction, mut background)) = button_query.single_mut() {
-                match *interaction {
-                    Interaction::Hovered => {
-                        background.0 = HOVERED_BUTTON_COLOR;
-                    }
-                    Interaction::Pressed => {
-                        background.0 = PRESSED_BUTTON_COLOR;
-                        changed_game_state.set(SimulationState::GameRunning);
-                        changed_app_state.set(AppState::MainMenu);
-                    }
-                    Interaction::None => {
-                        background.0 = NORMAL_BUTTON_COLOR;
-                    }
+            let Ok((mut text, mut countdown_text)) = text_query.single_mut() else {
+                return;
+            };
+
+            countdown_text.shown_for = 0.0;
+            text.0 = match countdown.remaining {
+                0 => "Go!".to_string(),
+                remaining => remaining.to_string(),
+            };
+        }
+
+        /// Pops each number in, and fades "Go!" out once the run is under way. The
+        /// fade only scales the alpha of the theme's text colour.
+        pub fn animate_countdown(
+            mut commands: Commands,
+            overlay_query: Query<Entity, With<CountdownOverlay>>,
+            mut text_query: Query<(&mut CountdownText, &mut UiTransform, &mut TextColor)>,
+            simulation_state: Res<State<SimulationState>>,
+            ui_theme: Res<UiTheme>,
+            time: Res<Time<Real>>,
+        ) {
+            let Ok((mut countdown_text, mut transform, mut color)) = text_query.single_mut() else {
+                return;
+            };
+            countdown_text.shown_for += time.delta_secs();
+
+            let pop = (1.0 - countdown_text.shown_for / POP_SECS).max(0.0);
+            transform.scale = Vec2::splat(1.0 + pop * pop);
+
+            let text_color = rgba(ui_theme.data.palette.text);
+            if *simulation_state.get() == SimulationState::Countdown {
+                color.0 = text_color;
+                return;
+            }
+
+            let fade = 1.0 - countdown_text.shown_for / GO_SECS;
+            if fade <= 0.0 {
+                for entity in &overlay_query {
+                    commands.entity(entity).despawn();
                 }
+            } else {
+                color.0 = text_color.with_alpha(text_color.alpha() * fade);
             }
         }
 
-        pub fn interact_with_quit_button(
-            mut button_query: Query<
-                (&Interaction, &mut BackgroundColor),
-                (Changed<Interaction>, With<ButtonQuit>),
-            >,
+        pub fn hide_countdown(
             mut commands: Commands,
+            overlay_query: Query<Entity, With<CountdownOverlay>>,
         ) {
-            if let Ok((interaction, mut background)) = button_query.single_mut() {
-                match *interaction {
-                    Interaction::Hovered => {
-                        background.0 = HOVERED_BUTTON_COLOR;
-                    }
-                    Interaction::Pressed => {
-                        background.0 = PRESSED_BUTTON_COLOR;
-                        commands.write_message(AppExit::Success);
-                    }
-                    Interaction::None => {
-                        background.0 = NORMAL_BUTTON_COLOR;
-                    }
-                }
+            for entity in &overlay_query {
+                commands.entity(entity).despawn();
             }
         }
     }
@@ -0,0 +1,9 @@
+pub mod layout {
+    use bevy::prelude::*;
+
+    #[derive(Component)]
+    pub struct LeaderboardPanel;
+
+    #[derive(Component)]
+    pub struct LeaderboardText;
+}
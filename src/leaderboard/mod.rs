@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+use plugins::*;
+
+mod components;
+mod plugins;
+mod resources;
+mod systems;
+
+pub const LEADERBOARD_FLAG: &str = "--leaderboard";
+pub const NAME_FLAG: &str = "--name";
+
+/// Submits offline runs to the HTTP leaderboard given with `--leaderboard <url>`
+/// and shows the global top scores on the game over screen.
+pub struct LeaderboardPlugin;
+impl Plugin for LeaderboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(SubmissionPlugin)
+            .add_plugins(TopScoresPlugin);
+    }
+}
@@ -0,0 +1,44 @@
+use super::{
+    resources::client::Leaderboard,
+    systems::{submission::*, top_scores::*},
+};
+use crate::AppState;
+use bevy::prelude::*;
+
+pub struct SubmissionPlugin;
+impl Plugin for SubmissionPlugin {
+    fn build(&self, app: &mut App) {
+        if let Some(leaderboard) = Leaderboard::from_args() {
+            app.insert_resource(leaderboard);
+        }
+
+        app.add_systems(
+            OnEnter(AppState::GameOver),
+            queue_run_submission.run_if(resource_exists::<Leaderboard>),
+        )
+        .add_systems(
+            Update,
+            (flush_queue, poll_upload)
+                .chain()
+                .run_if(resource_exists::<Leaderboard>),
+        );
+    }
+}
+
+pub struct TopScoresPlugin;
+impl Plugin for TopScoresPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(AppState::GameOver),
+            (fetch_top_scores, spawn_leaderboard_panel).run_if(resource_exists::<Leaderboard>),
+        )
+        .add_systems(OnExit(AppState::GameOver), despawn_leaderboard_panel)
+        .add_systems(Update, poll_fetch.run_if(resource_exists::<Leaderboard>))
+        .add_systems(
+            Update,
+            update_leaderboard_text
+                .run_if(in_state(AppState::GameOver))
+                .run_if(resource_exists::<Leaderboard>),
+        );
+    }
+}
@@ -0,0 +1,178 @@
+pub mod client {
+    use super::super::{LEADERBOARD_FLAG, NAME_FLAG};
+    use crate::game::resources::mode::GameMode;
+    use bevy::{
+        asset::io::file::FileAssetReader,
+        prelude::*,
+        tasks::{IoTaskPool, Task},
+    };
+    use serde::{Deserialize, Serialize};
+    use std::{fs, path::PathBuf, time::Duration};
+
+    pub const QUEUE_FILE: &str = "leaderboard_queue.ron";
+    pub const RETRY_SECS: f32 = 30.0;
+    pub const REQUEST_TIMEOUT_SECS: u64 = 5;
+    pub const TOP_SCORES_LIMIT: usize = 10;
+    pub const DEFAULT_NAME: &str = "Anonymous";
+
+    /// One finished run, as sent to and listed by the leaderboard service.
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+    pub struct Submission {
+        pub name: String,
+        pub score: u32,
+        pub mode: GameMode,
+        pub seed: u64,
+        pub level: String,
+        pub replay_hash: String,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Default)]
+    pub enum LeaderboardStatus {
+        #[default]
+        Connecting,
+        Online,
+        Offline(String),
+    }
+
+    pub struct UploadResult {
+        /// How many submissions from the front of the queue were handled, accepted or not.
+        pub sent: usize,
+        /// Submissions the service refused, with its reason. Retrying them can't help.
+        pub rejected: Vec<(Submission, String)>,
+        /// Set when the service couldn't be reached; the rest of the queue is kept.
+        pub error: Option<String>,
+    }
+
+    /// Only present when the game was started with `--leaderboard <url>`.
+    #[derive(Resource)]
+    pub struct Leaderboard {
+        pub endpoint: String,
+        pub player_name: String,
+        /// Submissions not yet accepted by the service, mirrored to disk.
+        pub queue: Vec<Submission>,
+        pub top_scores: Vec<Submission>,
+        pub status: LeaderboardStatus,
+        pub retry_timer: Timer,
+        pub flush_requested: bool,
+        pub upload: Option<Task<UploadResult>>,
+        pub fetch: Option<Task<Result<Vec<Submission>, String>>>,
+    }
+
+    impl Leaderboard {
+        pub fn from_args() -> Option<Self> {
+            let args: Vec<String> = std::env::args().collect();
+            let value_after = |flag: &str| {
+                args.iter()
+                    .position(|arg| arg == flag)
+                    .and_then(|index| args.get(index + 1).cloned())
+            };
+
+            let endpoint = value_after(LEADERBOARD_FLAG)?;
+            Some(Self {
+                endpoint: endpoint.trim_end_matches('/').to_string(),
+                player_name: value_after(NAME_FLAG).unwrap_or_else(|| DEFAULT_NAME.to_string()),
+                queue: Self::load_queue(),
+                top_scores: Vec::new(),
+                status: LeaderboardStatus::default(),
+                retry_timer: Timer::from_seconds(RETRY_SECS, TimerMode::Repeating),
+                // Retry whatever a previous session left behind.
+                flush_requested: true,
+                upload: None,
+                fetch: None,
+            })
+        }
+
+        fn queue_path() -> PathBuf {
+            FileAssetReader::get_base_path().join(QUEUE_FILE)
+        }
+
+        fn load_queue() -> Vec<Submission> {
+            fs::read_to_string(Self::queue_path())
+                .ok()
+                .and_then(|content| ron::from_str(&content).ok())
+                .unwrap_or_default()
+        }
+
+        pub fn save_queue(&self) -> Result<(), String> {
+            let path = Self::queue_path();
+            if self.queue.is_empty() {
+                if path.exists() {
+                    fs::remove_file(&path).map_err(|err| err.to_string())?;
+                }
+                return Ok(());
+            }
+
+            let content =
+                ron::ser::to_string_pretty(&self.queue, ron::ser::PrettyConfig::default())
+                    .map_err(|err| err.to_string())?;
+            fs::write(path, content).map_err(|err| err.to_string())
+        }
+
+        pub fn push(&mut self, submission: Submission) {
+            self.queue.push(submission);
+            self.flush_requested = true;
+            if let Err(err) = self.save_queue() {
+                error!("Could not save the leaderboard queue: {err}");
+            }
+        }
+
+        pub fn start_upload(&mut self) {
+            let endpoint = self.endpoint.clone();
+            let submissions = self.queue.clone();
+            self.upload =
+                Some(IoTaskPool::get().spawn(async move { post_scores(&endpoint, &submissions) }));
+        }
+
+        pub fn start_fetch(&mut self) {
+            let endpoint = self.endpoint.clone();
+            self.fetch = Some(IoTaskPool::get().spawn(async move { get_top_scores(&endpoint) }));
+        }
+    }
+
+    fn agent() -> ureq::Agent {
+        ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .build()
+    }
+
+    /// Posts submissions in order. A refused submission is set aside and the next one
+    /// tried; a transport failure stops the upload so the rest stay queued.
+    pub fn post_scores(endpoint: &str, submissions: &[Submission]) -> UploadResult {
+        let agent = agent();
+        let url = format!("{endpoint}/scores");
+        let mut rejected = Vec::new();
+
+        for (sent, submission) in submissions.iter().enumerate() {
+            match agent.post(&url).send_json(submission) {
+                Ok(_) => {}
+                Err(ureq::Error::Status(code, response)) => {
+                    let body = response.into_string().unwrap_or_default();
+                    rejected.push((submission.clone(), format!("{code}: {}", body.trim())));
+                }
+                Err(ureq::Error::Transport(err)) => {
+                    return UploadResult {
+                        sent,
+                        rejected,
+                        error: Some(err.to_string()),
+                    };
+                }
+            }
+        }
+
+        UploadResult {
+            sent: submissions.len(),
+            rejected,
+            error: None,
+        }
+    }
+
+    pub fn get_top_scores(endpoint: &str) -> Result<Vec<Submission>, String> {
+        agent()
+            .get(&format!("{endpoint}/scores"))
+            .query("limit", &TOP_SCORES_LIMIT.to_string())
+            .call()
+            .map_err(|err| err.to_string())?
+            .into_json()
+            .map_err(|err| err.to_string())
+    }
+}
@@ -0,0 +1,176 @@
+pub mod submission {
+    use super::super::resources::client::{Leaderboard, LeaderboardStatus, Submission};
+    use crate::game::resources::replay::Replay;
+    use bevy::{
+        prelude::*,
+        tasks::{block_on, futures_lite::future},
+    };
+
+    /// Queues the run that just ended. Networked runs have no replay and aren't submitted.
+    pub fn queue_run_submission(mut leaderboard: ResMut<Leaderboard>, replay: Option<Res<Replay>>) {
+        let Some(replay) = replay else {
+            return;
+        };
+        let Some(outcome) = &replay.outcome else {
+            return;
+        };
+
+        let submission = Submission {
+            name: leaderboard.player_name.clone(),
+            score: outcome.score,
+            mode: replay.mode,
+            seed: replay.seed,
+            level: replay.level.name.clone(),
+            replay_hash: replay.hash(),
+        };
+        leaderboard.push(submission);
+    }
+
+    pub fn flush_queue(mut leaderboard: ResMut<Leaderboard>, time: Res<Time>) {
+        if leaderboard.retry_timer.tick(time.delta()).just_finished() {
+            leaderboard.flush_requested = true;
+        }
+
+        if leaderboard.flush_requested && leaderboard.upload.is_none() {
+            leaderboard.flush_requested = false;
+            if !leaderboard.queue.is_empty() {
+                leaderboard.start_upload();
+            }
+        }
+    }
+
+    pub fn poll_upload(mut leaderboard: ResMut<Leaderboard>) {
+        let Some(task) = &mut leaderboard.upload else {
+            return;
+        };
+        let Some(result) = block_on(future::poll_once(task)) else {
+            return;
+        };
+        leaderboard.upload = None;
+
+        leaderboard.queue.drain(..result.sent);
+        for (submission, reason) in &result.rejected {
+            warn!(
+                "Leaderboard rejected the run of {} scoring {}, dropping it: {reason}",
+                submission.name, submission.score
+            );
+        }
+        if let Err(err) = leaderboard.save_queue() {
+            error!("Could not save the leaderboard queue: {err}");
+        }
+
+        match result.error {
+            Some(err) => {
+                warn!(
+                    "Leaderboard unreachable, {} run(s) queued: {err}",
+                    leaderboard.queue.len()
+                );
+                leaderboard.status = LeaderboardStatus::Offline(err);
+            }
+            None => leaderboard.status = LeaderboardStatus::Online,
+        }
+
+        // Refresh the list so freshly accepted runs show up.
+        if result.sent > result.rejected.len() && leaderboard.fetch.is_none() {
+            leaderboard.start_fetch();
+        }
+    }
+}
+
+pub mod top_scores {
+    use super::super::components::layout::*;
+    use super::super::resources::client::{Leaderboard, LeaderboardStatus};
+    use crate::widgets::{builders::caption, components::Themed};
+    use bevy::{
+        prelude::*,
+        tasks::{block_on, futures_lite::future},
+    };
+
+    pub fn fetch_top_scores(mut leaderboard: ResMut<Leaderboard>) {
+        if leaderboard.fetch.is_none() {
+            leaderboard.start_fetch();
+        }
+    }
+
+    pub fn poll_fetch(mut leaderboard: ResMut<Leaderboard>) {
+        let Some(task) = &mut leaderboard.fetch else {
+            return;
+        };
+        let Some(result) = block_on(future::poll_once(task)) else {
+            return;
+        };
+        leaderboard.fetch = None;
+
+        match result {
+            Ok(top_scores) => {
+                leaderboard.top_scores = top_scores;
+                leaderboard.status = LeaderboardStatus::Online;
+            }
+            Err(err) => {
+                warn!("Could not fetch top scores: {err}");
+                leaderboard.status = LeaderboardStatus::Offline(err);
+            }
+        }
+    }
+
+    pub fn spawn_leaderboard_panel(mut commands: Commands) {
+        commands.spawn((
+            LeaderboardPanel,
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(32.0),
+                top: Val::Px(32.0),
+                padding: UiRect::all(Val::Px(16.0)),
+                ..Default::default()
+            },
+            Themed::HudBox,
+            children![(LeaderboardText, caption(""))],
+        ));
+    }
+
+    pub fn despawn_leaderboard_panel(
+        mut commands: Commands,
+        panel_query: Query<Entity, With<LeaderboardPanel>>,
+    ) {
+        for entity in &panel_query {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    pub fn update_leaderboard_text(
+        mut text_query: Query<&mut Text, With<LeaderboardText>>,
+        leaderboard: Res<Leaderboard>,
+    ) {
+        let Ok(mut text) = text_query.single_mut() else {
+            return;
+        };
+
+        let mut lines = vec!["Global top scores".to_string()];
+        for (rank, entry) in leaderboard.top_scores.iter().enumerate() {
+            lines.push(format!(
+                "{}. {} - {} ({})",
+                rank + 1,
+                entry.name,
+                entry.score,
+                entry.mode.label()
+            ));
+        }
+
+        match &leaderboard.status {
+            LeaderboardStatus::Connecting => lines.push("Connecting...".to_string()),
+            LeaderboardStatus::Online if leaderboard.top_scores.is_empty() => {
+                lines.push("No scores yet".to_string())
+            }
+            LeaderboardStatus::Online => {}
+            LeaderboardStatus::Offline(_) => lines.push("Offline".to_string()),
+        }
+        if !leaderboard.queue.is_empty() {
+            lines.push(format!(
+                "{} run(s) waiting to upload",
+                leaderboard.queue.len()
+            ));
+        }
+
+        text.0 = lines.join("\n");
+    }
+}
@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+
+pub mod animation;
+pub mod audio;
+pub mod background;
+pub mod camera_effects;
+pub mod editor;
+pub mod game;
+pub mod leaderboard;
+pub mod loading;
+pub mod main_menu;
+pub mod net;
+pub mod particles;
+pub mod settings;
+pub mod trails;
+pub mod transition;
+pub mod widgets;
+
+#[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+pub enum AppState {
+    #[default]
+    Loading,
+    MainMenu,
+    Game,
+    GameOver,
+    Editor,
+    Lobby,
+    Settings,
+}
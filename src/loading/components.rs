@@ -0,0 +1,18 @@
+pub mod layout {
+    use bevy::prelude::*;
+
+    #[derive(Component)]
+    pub struct LoadingScreen;
+
+    #[derive(Component)]
+    pub struct ProgressBar;
+
+    #[derive(Component)]
+    pub struct CategoryText;
+
+    #[derive(Component)]
+    pub struct TipText;
+
+    #[derive(Component)]
+    pub struct ErrorText;
+}
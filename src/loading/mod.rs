@@ -0,0 +1,16 @@
+use bevy::prelude::*;
+use plugins::*;
+
+mod components;
+mod plugins;
+pub mod resources;
+mod systems;
+
+/// Loads every asset up front in `AppState::Loading`, then moves on to the state the
+/// game was launched into.
+pub struct LoadingPlugin;
+impl Plugin for LoadingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(LoadingScreenPlugin);
+    }
+}
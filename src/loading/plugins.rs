@@ -0,0 +1,21 @@
+use super::systems::loading::*;
+use crate::AppState;
+use bevy::prelude::*;
+
+pub struct LoadingScreenPlugin;
+impl Plugin for LoadingScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(AppState::Loading),
+            (start_loading, spawn_loading_screen),
+        )
+        .add_systems(
+            OnExit(AppState::Loading),
+            (despawn_loading_screen, finish_loading),
+        )
+        .add_systems(
+            Update,
+            (track_loading, cycle_tips, quit_loading).run_if(in_state(AppState::Loading)),
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use crate::{
+    AppState,
+    animation::resources::SheetKind,
+    background::resources::ThemeLibrary,
+    widgets::resources::{DEFAULT_FONT, UiThemeLibrary},
+};
+use bevy::{asset::UntypedAssetId, prelude::*};
+
+pub struct FontAssets {
+    pub bold: Handle<Font>,
+    /// Font of every UI theme, so switching themes never falls back mid-menu.
+    pub ui_themes: Vec<Handle<Font>>,
+}
+
+pub struct SpriteAssets {
+    pub ball_blue: Handle<Image>,
+    pub ball_red: Handle<Image>,
+    pub star: Handle<Image>,
+    pub ball_blue_sheet: Handle<Image>,
+    pub ball_green_sheet: Handle<Image>,
+    pub ball_red_sheet: Handle<Image>,
+    pub star_sheet: Handle<Image>,
+    /// Sheets and tile images of every arena theme, so switching themes never waits
+    /// on a file.
+    pub themes: Vec<Handle<Image>>,
+}
+
+pub struct SoundAssets {
+    pub bounce: Handle<AudioSource>,
+    pub star_collect: Handle<AudioSource>,
+    pub explosion: Handle<AudioSource>,
+    pub click: Handle<AudioSource>,
+}
+
+pub struct MusicAssets {
+    pub menu: Handle<AudioSource>,
+    pub game: Handle<AudioSource>,
+    pub game_intense: Handle<AudioSource>,
+    pub game_over: Handle<AudioSource>,
+}
+
+/// Every font, sprite and sound the game uses. The handles are held for the whole
+/// session, so nothing is loaded again on first use.
+#[derive(Resource)]
+pub struct GameAssets {
+    pub fonts: FontAssets,
+    pub sprites: SpriteAssets,
+    pub sounds: SoundAssets,
+    pub music: MusicAssets,
+}
+
+impl GameAssets {
+    pub fn load(
+        asset_server: &AssetServer,
+        theme_library: &ThemeLibrary,
+        ui_theme_library: &UiThemeLibrary,
+    ) -> Self {
+        let mut theme_images: Vec<&str> = theme_library
+            .themes
+            .iter()
+            .flat_map(|theme| theme.data.image_paths())
+            .collect();
+        theme_images.sort_unstable();
+        theme_images.dedup();
+        let mut ui_theme_fonts: Vec<&str> = ui_theme_library
+            .themes
+            .iter()
+            .map(|theme| theme.data.font.as_str())
+            .filter(|font| *font != DEFAULT_FONT)
+            .collect();
+        ui_theme_fonts.sort_unstable();
+        ui_theme_fonts.dedup();
+
+        Self {
+            fonts: FontAssets {
+                bold: asset_server.load(DEFAULT_FONT),
+                ui_themes: ui_theme_fonts
+                    .into_iter()
+                    .map(|path| asset_server.load(path.to_string()))
+                    .collect(),
+            },
+            sprites: SpriteAssets {
+                ball_blue: asset_server.load("sprites/ball_blue_large.png"),
+                ball_red: asset_server.load("sprites/ball_red_large.png"),
+                star: asset_server.load("sprites/star.png"),
+                ball_blue_sheet: asset_server.load(SheetKind::Player.default_path()),
+                ball_green_sheet: asset_server.load(SheetKind::PlayerTwo.default_path()),
+                ball_red_sheet: asset_server.load(SheetKind::Enemy.default_path()),
+                star_sheet: asset_server.load(SheetKind::Star.default_path()),
+                themes: theme_images
+                    .into_iter()
+                    .map(|path| asset_server.load(path.to_string()))
+                    .collect(),
+            },
+            sounds: SoundAssets {
+                bounce: asset_server.load("audio/pluck_001.ogg"),
+                star_collect: asset_server.load("audio/laserLarge_000.ogg"),
+                explosion: asset_server.load("audio/assets_audio_explosionCrunch_000.oga"),
+                click: asset_server.load("audio/click.wav"),
+            },
+            music: MusicAssets {
+                menu: asset_server.load("music/menu.wav"),
+                game: asset_server.load("music/game.wav"),
+                game_intense: asset_server.load("music/game_intense.wav"),
+                game_over: asset_server.load("music/game_over.wav"),
+            },
+        }
+    }
+
+    /// Every handle with the category it is reported under while loading.
+    pub fn tracked(&self) -> Vec<(AssetCategory, UntypedAssetId)> {
+        let mut tracked = vec![
+            (AssetCategory::Fonts, self.fonts.bold.id().untyped()),
+            (
+                AssetCategory::Sprites,
+                self.sprites.ball_blue.id().untyped(),
+            ),
+            (AssetCategory::Sprites, self.sprites.ball_red.id().untyped()),
+            (AssetCategory::Sprites, self.sprites.star.id().untyped()),
+            (
+                AssetCategory::Sprites,
+                self.sprites.ball_blue_sheet.id().untyped(),
+            ),
+            (
+                AssetCategory::Sprites,
+                self.sprites.ball_green_sheet.id().untyped(),
+            ),
+            (
+                AssetCategory::Sprites,
+                self.sprites.ball_red_sheet.id().untyped(),
+            ),
+            (
+                AssetCategory::Sprites,
+                self.sprites.star_sheet.id().untyped(),
+            ),
+            (AssetCategory::Audio, self.sounds.bounce.id().untyped()),
+            (
+                AssetCategory::Audio,
+                self.sounds.star_collect.id().untyped(),
+            ),
+            (AssetCategory::Audio, self.sounds.explosion.id().untyped()),
+            (AssetCategory::Audio, self.sounds.click.id().untyped()),
+            (AssetCategory::Audio, self.music.menu.id().untyped()),
+            (AssetCategory::Audio, self.music.game.id().untyped()),
+            (AssetCategory::Audio, self.music.game_intense.id().untyped()),
+            (AssetCategory::Audio, self.music.game_over.id().untyped()),
+        ];
+        tracked.extend(
+            self.sprites
+                .themes
+                .iter()
+                .map(|image| (AssetCategory::Sprites, image.id().untyped())),
+        );
+        tracked.extend(
+            self.fonts
+                .ui_themes
+                .iter()
+                .map(|font| (AssetCategory::Fonts, font.id().untyped())),
+        );
+        tracked
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetCategory {
+    Fonts,
+    Sprites,
+    Audio,
+}
+
+impl AssetCategory {
+    pub const ALL: [AssetCategory; 3] = [
+        AssetCategory::Fonts,
+        AssetCategory::Sprites,
+        AssetCategory::Audio,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AssetCategory::Fonts => "Fonts",
+            AssetCategory::Sprites => "Sprites",
+            AssetCategory::Audio => "Audio",
+        }
+    }
+}
+
+pub const TIP_SECS: f32 = 3.0;
+pub const TIPS: [&str; 5] = [
+    "Collect stars to score, and keep away from the red balls.",
+    "A new enemy joins every few seconds. The music picks up with them.",
+    "Press Space during a run to pause.",
+    "Run with --editor to build your own levels.",
+    "Change the audio mix in Settings.",
+];
+
+#[derive(Resource, Debug)]
+pub struct LoadingProgress {
+    /// Path and error of every asset that failed, in the order they were noticed.
+    pub failures: Vec<(String, String)>,
+    pub tip_timer: Timer,
+    pub tip: usize,
+}
+
+impl Default for LoadingProgress {
+    fn default() -> Self {
+        Self {
+            failures: Vec::new(),
+            tip_timer: Timer::from_seconds(TIP_SECS, TimerMode::Repeating),
+            tip: 0,
+        }
+    }
+}
+
+/// State to enter once everything is loaded.
+#[derive(Resource, Debug)]
+pub struct LoadingTarget {
+    pub state: AppState,
+}
@@ -0,0 +1,174 @@
+pub mod loading {
+    use super::super::components::layout::*;
+    use super::super::resources::*;
+    use crate::{
+        background::resources::ThemeLibrary,
+        transition::messages::{TransitionEffect, TransitionRequest},
+        widgets::{
+            builders::*,
+            components::Themed,
+            resources::{UiTheme, UiThemeLibrary, rgba},
+        },
+    };
+    use bevy::{asset::LoadState, prelude::*};
+
+    pub const PROGRESS_BAR_WIDTH: f32 = 480.0;
+    pub const PROGRESS_BAR_HEIGHT: f32 = 24.0;
+
+    pub fn start_loading(
+        mut commands: Commands,
+        asset_server: Res<AssetServer>,
+        theme_library: Res<ThemeLibrary>,
+        ui_theme_library: Res<UiThemeLibrary>,
+    ) {
+        commands.insert_resource(GameAssets::load(
+            &asset_server,
+            &theme_library,
+            &ui_theme_library,
+        ));
+        commands.insert_resource(LoadingProgress::default());
+    }
+
+    pub fn finish_loading(mut commands: Commands) {
+        commands.remove_resource::<LoadingProgress>();
+    }
+
+    /// Text starts in Bevy's built-in font and switches to the theme's once it has
+    /// loaded, see `apply_ui_theme`.
+    pub fn spawn_loading_screen(mut commands: Commands) {
+        commands.spawn((
+            screen(LoadingScreen),
+            children![
+                label("Loading"),
+                (
+                    Themed::ProgressTrack,
+                    Node {
+                        width: Val::Px(PROGRESS_BAR_WIDTH),
+                        height: Val::Px(PROGRESS_BAR_HEIGHT),
+                        ..Default::default()
+                    },
+                    children![(
+                        ProgressBar,
+                        Themed::SliderFill,
+                        Node {
+                            width: Val::Percent(0.0),
+                            height: Val::Percent(100.0),
+                            ..Default::default()
+                        },
+                    )],
+                ),
+                (CategoryText, caption("")),
+                (TipText, caption(TIPS[0])),
+                (
+                    ErrorText,
+                    Themed::Error,
+                    Text::new(""),
+                    TextLayout::new_with_justify(Justify::Center),
+                ),
+            ],
+        ));
+    }
+
+    pub fn despawn_loading_screen(
+        mut commands: Commands,
+        screen_query: Query<Entity, With<LoadingScreen>>,
+    ) {
+        for entity in &screen_query {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    /// Moves on only once every asset is ready. Failed assets are logged once and
+    /// listed on screen, and loading stops there instead of starting a game with
+    /// invisible sprites or silent sounds.
+    #[allow(clippy::too_many_arguments)]
+    pub fn track_loading(
+        asset_server: Res<AssetServer>,
+        game_assets: Res<GameAssets>,
+        ui_theme: Res<UiTheme>,
+        target: Res<LoadingTarget>,
+        mut progress: ResMut<LoadingProgress>,
+        mut bar_query: Query<(&mut Node, &mut BackgroundColor), With<ProgressBar>>,
+        mut category_query: Query<&mut Text, (With<CategoryText>, Without<ErrorText>)>,
+        mut error_query: Query<&mut Text, (With<ErrorText>, Without<CategoryText>)>,
+        mut transition_writer: MessageWriter<TransitionRequest>,
+    ) {
+        let tracked = game_assets.tracked();
+        let mut counts = AssetCategory::ALL.map(|category| (category, 0, 0));
+
+        for (category, id) in &tracked {
+            let Some(count) = counts.iter_mut().find(|(kind, _, _)| kind == category) else {
+                continue;
+            };
+            count.2 += 1;
+
+            if asset_server.is_loaded_with_dependencies(*id) {
+                count.1 += 1;
+            } else if let Some(LoadState::Failed(err)) = asset_server.get_load_state(*id) {
+                let path = asset_server
+                    .get_path(*id)
+                    .map_or_else(|| format!("{id:?}"), |path| path.to_string());
+                if !progress.failures.iter().any(|(failed, _)| *failed == path) {
+                    error!("Could not load asset {path}: {err}");
+                    progress.failures.push((path, err.to_string()));
+                }
+            }
+        }
+
+        let loaded: usize = counts.iter().map(|(_, loaded, _)| loaded).sum();
+        if let Ok((mut bar, mut color)) = bar_query.single_mut() {
+            bar.width = Val::Percent(loaded as f32 / tracked.len() as f32 * 100.0);
+            if !progress.failures.is_empty() {
+                color.0 = rgba(ui_theme.data.palette.error);
+            }
+        }
+
+        if let Ok(mut text) = category_query.single_mut() {
+            text.0 = counts
+                .iter()
+                .map(|(category, loaded, total)| format!("{} {loaded}/{total}", category.label()))
+                .collect::<Vec<_>>()
+                .join("    ");
+        }
+
+        if !progress.failures.is_empty() {
+            if let Ok(mut text) = error_query.single_mut() {
+                let mut lines = vec!["Some files could not be loaded:".to_string()];
+                lines.extend(
+                    progress
+                        .failures
+                        .iter()
+                        .map(|(path, err)| format!("{path}: {err}")),
+                );
+                lines.push("Check the assets folder, then restart. Press Escape to quit.".into());
+                text.0 = lines.join("\n");
+            }
+            return;
+        }
+
+        if loaded == tracked.len() {
+            transition_writer.write(TransitionRequest::new(target.state, TransitionEffect::Fade));
+        }
+    }
+
+    pub fn cycle_tips(
+        mut progress: ResMut<LoadingProgress>,
+        mut tip_query: Query<&mut Text, With<TipText>>,
+        time: Res<Time>,
+    ) {
+        if !progress.tip_timer.tick(time.delta()).just_finished() {
+            return;
+        }
+
+        progress.tip = (progress.tip + 1) % TIPS.len();
+        if let Ok(mut text) = tip_query.single_mut() {
+            text.0 = TIPS[progress.tip].to_string();
+        }
+    }
+
+    pub fn quit_loading(mut commands: Commands, keyboard_input: Res<ButtonInput<KeyCode>>) {
+        if keyboard_input.just_pressed(KeyCode::Escape) {
+            commands.write_message(AppExit::Success);
+        }
+    }
+}
@@ -1,21 +1,38 @@
 use bevy::prelude::*;
-
-mod game;
-mod main_menu;
+use bevy_dash_ball_game::{
+    AppState, animation, audio, background, camera_effects, editor, game, leaderboard, loading,
+    main_menu, net, particles, settings, trails, transition, widgets,
+};
 
 fn main() {
+    let launch_state = if std::env::args().any(|arg| arg == editor::EDITOR_FLAG) {
+        AppState::Editor
+    } else if net::launch_requested() {
+        AppState::Lobby
+    } else {
+        AppState::MainMenu
+    };
+
     App::new()
         .add_plugins(DefaultPlugins)
         .init_state::<AppState>()
+        .insert_resource(loading::resources::LoadingTarget {
+            state: launch_state,
+        })
+        .add_plugins(loading::LoadingPlugin)
         .add_plugins(game::GamePlugin)
         .add_plugins(main_menu::MainMenuPlugin)
+        .add_plugins(editor::EditorPlugin)
+        .add_plugins(net::NetPlugin)
+        .add_plugins(leaderboard::LeaderboardPlugin)
+        .add_plugins(settings::SettingsPlugin)
+        .add_plugins(audio::GameAudioPlugin)
+        .add_plugins(particles::ParticlesPlugin)
+        .add_plugins(camera_effects::CameraEffectsPlugin)
+        .add_plugins(trails::TrailsPlugin)
+        .add_plugins(animation::AnimationPlugin)
+        .add_plugins(background::BackgroundPlugin)
+        .add_plugins(transition::TransitionPlugin)
+        .add_plugins(widgets::WidgetsPlugin)
         .run();
 }
-
-#[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
-pub enum AppState {
-    #[default]
-    MainMenu,
-    Game,
-    GameOver,
-}
@@ -3,10 +3,4 @@ pub mod layout {
 
     #[derive(Component)]
     pub struct MainMenu;
-
-    #[derive(Component)]
-    pub struct PlayButton;
-
-    #[derive(Component)]
-    pub struct QuitButton;
 }
@@ -1,25 +1,30 @@
-pub mod main_menu_state {
-    use crate::AppState;
-    use bevy::prelude::*;
-
-    pub fn transition_to_game(
-        state: Res<State<AppState>>,
-        mut changed_state: ResMut<NextState<AppState>>,
-        keyboard_input: Res<ButtonInput<KeyCode>>,
-    ) {
-        if keyboard_input.just_pressed(KeyCode::Enter) && *state.get() == AppState::MainMenu {
-            changed_state.set(AppState::Game);
-        }
-    }
-}
-
 pub mod layout {
-    use super::super::components::layout::{MainMenu, PlayButton, QuitButton};
-    use super::super::styles::*;
+    use super::super::components::layout::MainMenu;
+    use crate::game::resources::{
+        controls::ControlScheme,
+        level::{LevelLibrary, SelectedLevel},
+        mode::GameMode,
+    };
+    use crate::loading::resources::GameAssets;
+    use crate::widgets::{builders::*, messages::ButtonAction};
     use bevy::prelude::*;
 
-    pub fn spawn_main_menu(mut commands: Commands, assert_server: Res<AssetServer>) {
-        let _ = build_main_menu(&mut commands, &assert_server);
+    pub fn spawn_main_menu(
+        mut commands: Commands,
+        game_assets: Res<GameAssets>,
+        level_library: Res<LevelLibrary>,
+        selected_level: Res<SelectedLevel>,
+        control_scheme: Res<ControlScheme>,
+        game_mode: Res<GameMode>,
+    ) {
+        let level_name = &level_library.get(&selected_level).name;
+        let _ = build_main_menu(
+            &mut commands,
+            &game_assets,
+            level_name,
+            *control_scheme,
+            *game_mode,
+        );
     }
 
     pub fn despawn_main_menu(
@@ -30,22 +35,15 @@ pub mod layout {
         commands.entity(entity).despawn();
     }
 
-    pub fn build_main_menu(commands: &mut Commands, asset_server: &Res<AssetServer>) -> Entity {
-        let main_menu_entity = commands
-            .spawn((
-                Node {
-                    width: Val::Percent(100.0),
-                    height: Val::Percent(100.0),
-                    flex_direction: FlexDirection::Column,
-                    align_items: AlignItems::Center,
-                    justify_content: JustifyContent::Center,
-                    row_gap: Val::Px(12.0),
-                    column_gap: Val::Px(12.0),
-                    ..Default::default()
-                },
-                //BackgroundColor(Color::linear_rgb(1.0, 0.0, 0.0)),
-                MainMenu,
-            ))
+    pub fn build_main_menu(
+        commands: &mut Commands,
+        game_assets: &GameAssets,
+        level_name: &str,
+        control_scheme: ControlScheme,
+        game_mode: GameMode,
+    ) -> Entity {
+        commands
+            .spawn(screen(MainMenu))
             .with_children(|parent| {
                 // === TITLE ===
                 parent
@@ -63,99 +61,106 @@ pub mod layout {
                     .with_children(|p| {
                         // Image
                         p.spawn(ImageNode {
-                            image: asset_server.load("sprites/ball_blue_large.png"),
+                            image: game_assets.sprites.ball_blue.clone(),
                             ..default()
                         });
 
                         // Text
-                        p.spawn(text_title_style("Dash Ball", &asset_server));
+                        p.spawn(title("Dash Ball"));
 
                         //Image
                         p.spawn(ImageNode {
-                            image: asset_server.load("sprites/ball_red_large.png"),
+                            image: game_assets.sprites.ball_red.clone(),
                             ..default()
                         });
                     });
 
-                // === Play Button ===
-                parent
-                    .spawn((
-                        Button,
-                        button_style(),
-                        BackgroundColor(NORMAL_BUTTON_COLOR),
-                        PlayButton,
-                    ))
-                    .with_children(|p| {
-                        p.spawn(text_button_style("Play", &asset_server));
-                    });
-
-                // === Quit Button ===
-                parent
-                    .spawn((
-                        Button,
-                        button_style(),
-                        BackgroundColor(NORMAL_BUTTON_COLOR),
-                        QuitButton,
-                    ))
-                    .with_children(|p| {
-                        p.spawn(text_button_style("Quit", &asset_server));
-                    });
+                parent.spawn(button(ButtonAction::Play, "Play"));
+                parent.spawn(button(
+                    ButtonAction::CycleLevel,
+                    level_button_text(level_name),
+                ));
+                parent.spawn(button(ButtonAction::CycleMode, game_mode.label()));
+                parent.spawn(button(ButtonAction::CycleControls, control_scheme.label()));
+                parent.spawn(button(ButtonAction::Online, "Online"));
+                parent.spawn(button(ButtonAction::Settings, "Settings"));
+                parent.spawn(button(ButtonAction::Quit, "Quit"));
             })
-            .id();
+            .id()
+    }
 
-        main_menu_entity
+    pub fn level_button_text(level_name: &str) -> String {
+        format!("< {level_name} >")
     }
 }
 
 pub mod interaction {
     use crate::AppState;
+    use crate::game::resources::{
+        controls::ControlScheme,
+        level::{LevelLibrary, SelectedLevel},
+        mode::GameMode,
+    };
+    use crate::transition::messages::{TransitionEffect, TransitionRequest};
+    use crate::widgets::{components::ButtonLabel, messages::ButtonAction};
 
-    use super::super::components::layout::{PlayButton, QuitButton};
-    use super::super::styles::*;
+    use super::layout::level_button_text;
     use bevy::prelude::*;
 
-    pub fn interact_with_play_button(
-        mut button_query: Query<
-            (&Interaction, &mut BackgroundColor),
-            (Changed<Interaction>, With<PlayButton>),
-        >,
-        mut changed_state: ResMut<NextState<AppState>>,
+    pub fn handle_main_menu_actions(
+        mut action_reader: MessageReader<ButtonAction>,
+        mut transition_writer: MessageWriter<TransitionRequest>,
+        mut label_query: Query<(&mut Text, &ButtonLabel)>,
+        level_library: Res<LevelLibrary>,
+        mut selected_level: ResMut<SelectedLevel>,
+        mut game_mode: ResMut<GameMode>,
+        mut control_scheme: ResMut<ControlScheme>,
     ) {
-        if let Ok((interaction, mut background)) = button_query.single_mut() {
-            match *interaction {
-                Interaction::Hovered => {
-                    background.0 = HOVERED_BUTTON_COLOR;
+        for action in action_reader.read() {
+            match *action {
+                ButtonAction::Play => {
+                    transition_writer.write(TransitionRequest::new(
+                        AppState::Game,
+                        TransitionEffect::Wipe,
+                    ));
                 }
-                Interaction::Pressed => {
-                    background.0 = PRESSED_BUTTON_COLOR;
-                    changed_state.set(AppState::Game);
+                ButtonAction::CycleLevel => {
+                    selected_level.next(&level_library);
+                    ButtonLabel::relabel(
+                        &mut label_query,
+                        ButtonAction::CycleLevel,
+                        level_button_text(&level_library.get(&selected_level).name),
+                    );
                 }
-                Interaction::None => {
-                    background.0 = NORMAL_BUTTON_COLOR;
+                ButtonAction::CycleMode => {
+                    *game_mode = game_mode.next();
+                    ButtonLabel::relabel(
+                        &mut label_query,
+                        ButtonAction::CycleMode,
+                        game_mode.label(),
+                    );
                 }
-            }
-        }
-    }
-
-    pub fn interact_with_quit_button(
-        mut button_query: Query<
-            (&Interaction, &mut BackgroundColor),
-            (Changed<Interaction>, With<QuitButton>),
-        >,
-        mut commands: Commands,
-    ) {
-        if let Ok((interaction, mut background)) = button_query.single_mut() {
-            match *interaction {
-                Interaction::Hovered => {
-                    background.0 = HOVERED_BUTTON_COLOR;
+                ButtonAction::CycleControls => {
+                    *control_scheme = control_scheme.next();
+                    ButtonLabel::relabel(
+                        &mut label_query,
+                        ButtonAction::CycleControls,
+                        control_scheme.label(),
+                    );
                 }
-                Interaction::Pressed => {
-                    background.0 = PRESSED_BUTTON_COLOR;
-                    commands.write_message(AppExit::Success);
+                ButtonAction::Online => {
+                    transition_writer.write(TransitionRequest::new(
+                        AppState::Lobby,
+                        TransitionEffect::Slide,
+                    ));
                 }
-                Interaction::None => {
-                    background.0 = NORMAL_BUTTON_COLOR;
+                ButtonAction::Settings => {
+                    transition_writer.write(TransitionRequest::new(
+                        AppState::Settings,
+                        TransitionEffect::Slide,
+                    ));
                 }
+                _ => {}
             }
         }
     }
@@ -0,0 +1,35 @@
+pub mod layout {
+    use bevy::prelude::*;
+
+    #[derive(Component)]
+    pub struct Lobby;
+
+    #[derive(Component)]
+    pub struct AddressText;
+
+    #[derive(Component)]
+    pub struct StatusText;
+}
+
+pub mod sync {
+    use bevy::prelude::*;
+
+    /// Identifies an entity across host and clients.
+    #[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct NetId(pub u32);
+}
+
+pub mod rollback {
+    use bevy::prelude::*;
+
+    /// Key for restoring an entity in place after a rollback; only meaningful locally.
+    #[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct RollbackId(pub u32);
+}
+
+pub mod spectator {
+    use bevy::prelude::*;
+
+    #[derive(Component)]
+    pub struct SpectatorHint;
+}
@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+use plugins::*;
+
+mod components;
+mod plugins;
+mod protocol;
+mod resources;
+mod systems;
+
+pub const DEFAULT_PORT: u16 = 7777;
+pub const HOST_FLAG: &str = "--host";
+pub const JOIN_FLAG: &str = "--join";
+pub const ROLLBACK_FLAG: &str = "--rollback";
+pub const LATENCY_FLAG: &str = "--latency";
+pub const LOSS_FLAG: &str = "--loss";
+pub const DEFAULT_SPECTATOR_PORT: u16 = 7878;
+pub const BROADCAST_FLAG: &str = "--broadcast";
+pub const SPECTATE_FLAG: &str = "--spectate";
+
+/// True when the game was started with `--host [port]`, `--join <address>`,
+/// `--rollback <port> <peer address>` or `--spectate <address>`.
+pub fn launch_requested() -> bool {
+    resources::lobby::LaunchRequest::from_args().is_some()
+}
+
+pub struct NetPlugin;
+impl Plugin for NetPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(LobbyPlugin)
+            .add_plugins(SyncPlugin)
+            .add_plugins(HostPlugin)
+            .add_plugins(ClientPlugin)
+            .add_plugins(RollbackPlugin)
+            .add_plugins(SpectatorPlugin);
+    }
+}
@@ -0,0 +1,180 @@
+use super::{
+    resources::{
+        lobby::{AddressInput, LaunchRequest, LobbyStatus, PendingLaunch},
+        rollback::RollbackSession,
+        session::{ClientSession, HostSession, NetIds},
+        spectator::{SpectatorServer, SpectatorSession},
+    },
+    systems::{client::*, host::*, interaction::*, layout::*, rollback::*, spectator::*, sync::*},
+};
+use crate::{
+    AppState,
+    game::{
+        resources::countdown::Countdown, states::SimulationState, systems::game_state::leaving_game,
+    },
+};
+use bevy::prelude::*;
+
+pub struct LobbyPlugin;
+impl Plugin for LobbyPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(PendingLaunch {
+            request: LaunchRequest::from_args(),
+        })
+        .init_resource::<AddressInput>()
+        .init_resource::<LobbyStatus>()
+        .add_systems(
+            OnEnter(AppState::Lobby),
+            (spawn_lobby, handle_launch_request),
+        )
+        .add_systems(OnExit(AppState::Lobby), despawn_lobby)
+        .add_systems(OnEnter(AppState::MainMenu), shutdown_network)
+        .add_systems(
+            Update,
+            (type_address, handle_lobby_actions, update_lobby_text)
+                .chain()
+                .run_if(in_state(AppState::Lobby)),
+        );
+    }
+}
+
+pub struct HostPlugin;
+impl Plugin for HostPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(AppState::Game),
+            announce_start.run_if(resource_exists::<HostSession>),
+        )
+        .add_systems(
+            Update,
+            (host_receive, send_game_over, send_heartbeats).run_if(resource_exists::<HostSession>),
+        )
+        .add_systems(
+            Update,
+            (prepare_players, apply_remote_input, send_snapshots)
+                .chain()
+                .after(host_receive)
+                .run_if(in_state(AppState::Game))
+                .run_if(resource_exists::<HostSession>),
+        );
+    }
+}
+
+pub struct ClientPlugin;
+impl Plugin for ClientPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (client_receive, keep_connection)
+                .chain()
+                .run_if(resource_exists::<ClientSession>),
+        )
+        .add_systems(
+            Update,
+            (send_input, apply_snapshots)
+                .chain()
+                .after(keep_connection)
+                .run_if(in_state(AppState::Game))
+                .run_if(resource_exists::<ClientSession>),
+        )
+        .add_systems(
+            Update,
+            follow_host_countdown
+                .after(client_receive)
+                .run_if(in_state(SimulationState::Countdown))
+                .run_if(resource_exists::<ClientSession>)
+                .run_if(resource_exists::<Countdown>),
+        );
+    }
+}
+
+pub struct RollbackPlugin;
+impl Plugin for RollbackPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (rollback_receive, check_rollback_peer)
+                .chain()
+                .distributive_run_if(resource_exists::<RollbackSession>),
+        )
+        .add_systems(
+            Update,
+            // Frames are counted from the start of the countdown, which is played out
+            // in frames too, so both peers start the run on the same tick.
+            (sample_local_input, advance_rollback)
+                .chain()
+                .after(check_rollback_peer)
+                .run_if(in_state(AppState::Game))
+                .run_if(not(in_state(SimulationState::GamePaused)))
+                .run_if(not(leaving_game))
+                .distributive_run_if(resource_exists::<RollbackSession>),
+        )
+        .add_systems(
+            Update,
+            rollback_send
+                .after(advance_rollback)
+                .run_if(resource_exists::<RollbackSession>),
+        )
+        .add_systems(
+            OnEnter(AppState::GameOver),
+            finish_rollback_match.run_if(resource_exists::<RollbackSession>),
+        );
+    }
+}
+
+pub struct SyncPlugin;
+impl Plugin for SyncPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NetIds>().add_systems(
+            Update,
+            assign_net_ids
+                .run_if(in_state(AppState::Game))
+                .run_if(resource_exists::<HostSession>.or(resource_exists::<SpectatorServer>)),
+        );
+    }
+}
+
+pub struct SpectatorPlugin;
+impl Plugin for SpectatorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, start_broadcast)
+            .add_systems(
+                OnEnter(AppState::Game),
+                announce_run_to_spectators.run_if(resource_exists::<SpectatorServer>),
+            )
+            .add_systems(
+                Update,
+                (
+                    stream_to_spectators.run_if(in_state(AppState::Game)),
+                    send_game_over_to_spectators,
+                    accept_spectators,
+                )
+                    .chain()
+                    .after(assign_net_ids)
+                    .run_if(resource_exists::<SpectatorServer>),
+            )
+            .add_systems(
+                Update,
+                spectator_receive.run_if(resource_exists::<SpectatorSession>),
+            )
+            .add_systems(
+                Update,
+                (
+                    apply_spectator_snapshots,
+                    spectator_camera,
+                    update_spectator_hint,
+                )
+                    .chain()
+                    .after(spectator_receive)
+                    .run_if(in_state(AppState::Game))
+                    .distributive_run_if(resource_exists::<SpectatorSession>),
+            )
+            .add_systems(
+                OnEnter(AppState::Game),
+                spawn_spectator_hint.run_if(resource_exists::<SpectatorSession>),
+            )
+            .add_systems(OnExit(AppState::Game), despawn_spectator_hint)
+            .add_systems(OnEnter(AppState::MainMenu), reset_camera_zoom)
+            .add_systems(OnEnter(AppState::Lobby), reset_camera_zoom);
+    }
+}
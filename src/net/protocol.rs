@@ -0,0 +1,367 @@
+use crate::game::{
+    components::player::MAX_PLAYERS,
+    messages::game_states::PlayerResult,
+    resources::{
+        level::{LevelData, Point},
+        mode::GameMode,
+    },
+};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+/// Largest datagram either side sends or reads.
+pub const MAX_PACKET_SIZE: usize = 8192;
+/// Entities per snapshot datagram, keeping each one well under `MAX_PACKET_SIZE`.
+pub const SNAPSHOT_ENTITIES_PER_PACKET: usize = 200;
+/// Largest packet read off a stream. A longer length prefix means the stream is
+/// corrupt or hostile, and waiting for the rest would grow the buffer without end.
+pub const MAX_FRAME_SIZE: usize = 1 << 20;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum ClientPacket {
+    Join,
+    Input {
+        direction: Point,
+    },
+    /// Keeps the connection alive while no input is being sent.
+    Heartbeat,
+    Leave,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum ServerPacket {
+    Welcome {
+        player_index: usize,
+    },
+    Full,
+    Start {
+        mode: GameMode,
+        level: LevelData,
+    },
+    /// One part of a snapshot; parts with the same `time` belong together.
+    Snapshot(Snapshot),
+    GameOver {
+        score: u32,
+        results: Vec<PlayerResult>,
+    },
+    Heartbeat,
+}
+
+/// Exchanged between the two peers of a rollback match.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum PeerPacket {
+    Hello {
+        nonce: u64,
+        level: LevelData,
+    },
+    /// Local inputs for `start..start + directions.len()`, resent until acknowledged.
+    /// `ack` is the number of the sender's confirmed remote frames.
+    Input {
+        start: u32,
+        directions: Vec<Point>,
+        ack: u32,
+    },
+    Leave,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Snapshot {
+    /// Host clock, in seconds since its app started.
+    pub time: f32,
+    pub score: u32,
+    pub players: [u32; MAX_PLAYERS],
+    pub lives: u32,
+    /// Numbers left on the host's start countdown; 0 once the run is under way.
+    pub countdown: u32,
+    /// Index of this part when the snapshot was split across datagrams.
+    pub part: u32,
+    /// How many parts the snapshot was split into; 1 when it wasn't.
+    pub parts: u32,
+    pub entities: Vec<EntityState>,
+}
+
+/// Streamed over TCP to spectators.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum SpectatorPacket {
+    Start {
+        mode: GameMode,
+        level: LevelData,
+    },
+    /// The whole world, sent when a spectator connects and when a run starts.
+    Full(Snapshot),
+    Delta(SnapshotDelta),
+    GameOver {
+        score: u32,
+        results: Vec<PlayerResult>,
+    },
+}
+
+/// Changes since the previous snapshot sent on the same stream.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SnapshotDelta {
+    pub time: f32,
+    pub score: u32,
+    pub players: [u32; MAX_PLAYERS],
+    pub lives: u32,
+    pub changed: Vec<EntityState>,
+    pub removed: Vec<u32>,
+}
+
+impl Snapshot {
+    /// Splits the snapshot into parts small enough for one datagram each. Every part
+    /// carries the same time and scores and says how many parts there are, so the
+    /// receiver can tell when it has them all.
+    pub fn split(self) -> Vec<Snapshot> {
+        if self.entities.len() <= SNAPSHOT_ENTITIES_PER_PACKET {
+            return vec![Snapshot {
+                part: 0,
+                parts: 1,
+                ..self
+            }];
+        }
+
+        let chunks = self.entities.chunks(SNAPSHOT_ENTITIES_PER_PACKET);
+        let parts = chunks.len() as u32;
+        chunks
+            .enumerate()
+            .map(|(part, entities)| Snapshot {
+                time: self.time,
+                score: self.score,
+                players: self.players,
+                lives: self.lives,
+                countdown: self.countdown,
+                part: part as u32,
+                parts,
+                entities: entities.to_vec(),
+            })
+            .collect()
+    }
+
+    pub fn delta_from(&self, previous: &Snapshot) -> SnapshotDelta {
+        let changed = self
+            .entities
+            .iter()
+            .filter(|entity| {
+                !previous.entities.iter().any(|old| {
+                    old.id == entity.id
+                        && old.kind == entity.kind
+                        && old.position == entity.position
+                })
+            })
+            .copied()
+            .collect();
+        let removed = previous
+            .entities
+            .iter()
+            .filter(|old| !self.entities.iter().any(|entity| entity.id == old.id))
+            .map(|old| old.id)
+            .collect();
+
+        SnapshotDelta {
+            time: self.time,
+            score: self.score,
+            players: self.players,
+            lives: self.lives,
+            changed,
+            removed,
+        }
+    }
+
+    pub fn apply(&mut self, delta: &SnapshotDelta) {
+        self.time = delta.time;
+        self.score = delta.score;
+        self.players = delta.players;
+        self.lives = delta.lives;
+        self.entities
+            .retain(|entity| !delta.removed.contains(&entity.id));
+
+        for changed in &delta.changed {
+            match self
+                .entities
+                .iter_mut()
+                .find(|entity| entity.id == changed.id)
+            {
+                Some(entity) => *entity = *changed,
+                None => self.entities.push(*changed),
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum EntityKind {
+    Player(usize),
+    Enemy { size: f32 },
+    Star,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct EntityState {
+    pub id: u32,
+    pub kind: EntityKind,
+    pub position: Point,
+}
+
+pub fn encode<T: Serialize>(packet: &T) -> Vec<u8> {
+    bincode::serialize(packet).expect("packets are always serializable")
+}
+
+/// Encodes a packet for a single datagram, refusing ones the receiver couldn't read.
+pub fn encode_datagram<T: Serialize>(packet: &T) -> Result<Vec<u8>, String> {
+    let bytes = bincode::serialize(packet).map_err(|err| err.to_string())?;
+    if bytes.len() > MAX_PACKET_SIZE {
+        return Err(format!(
+            "packet of {} bytes is over the {MAX_PACKET_SIZE} byte limit",
+            bytes.len()
+        ));
+    }
+    Ok(bytes)
+}
+
+/// Levels travel whole inside `ServerPacket::Start` and `PeerPacket::Hello`, so they
+/// have to fit in one datagram next to the other fields of both.
+pub fn check_level_size(level: &LevelData) -> Result<(), String> {
+    let start = ServerPacket::Start {
+        mode: GameMode::Versus,
+        level: level.clone(),
+    };
+    let hello = PeerPacket::Hello {
+        nonce: u64::MAX,
+        level: level.clone(),
+    };
+    encode_datagram(&start)
+        .and(encode_datagram(&hello))
+        .map(|_| ())
+}
+
+pub fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Option<T> {
+    bincode::deserialize(bytes).ok()
+}
+
+/// Encodes a packet for a stream, prefixed with its length.
+pub fn encode_framed<T: Serialize>(packet: &T) -> Vec<u8> {
+    let bytes = encode(packet);
+    let mut framed = (bytes.len() as u32).to_le_bytes().to_vec();
+    framed.extend(bytes);
+    framed
+}
+
+/// Takes the next complete packet off the front of a stream buffer, if it has
+/// fully arrived. Undecodable packets are skipped; a length over `MAX_FRAME_SIZE`
+/// is an error, as the stream can't be trusted any more.
+pub fn decode_framed<T: DeserializeOwned>(
+    buffer: &mut Vec<u8>,
+) -> Result<Option<Option<T>>, String> {
+    let Some(header) = buffer.get(..4) else {
+        return Ok(None);
+    };
+    let size = u32::from_le_bytes([header[0], header[1], header[2], header[3]]) as usize;
+    if size > MAX_FRAME_SIZE {
+        return Err(format!(
+            "packet of {size} bytes is over the {MAX_FRAME_SIZE} byte limit"
+        ));
+    }
+    if buffer.len() < 4 + size {
+        return Ok(None);
+    }
+
+    let packet = decode(&buffer[4..4 + size]);
+    buffer.drain(..4 + size);
+    Ok(Some(packet))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(time: f32, entities: Vec<EntityState>) -> Snapshot {
+        Snapshot {
+            time,
+            score: 0,
+            players: [0; MAX_PLAYERS],
+            lives: 3,
+            countdown: 0,
+            part: 0,
+            parts: 1,
+            entities,
+        }
+    }
+
+    fn star(id: u32, x: f32) -> EntityState {
+        EntityState {
+            id,
+            kind: EntityKind::Star,
+            position: Point { x, y: 0.0 },
+        }
+    }
+
+    fn entities(snapshot: &Snapshot) -> Vec<(u32, EntityKind, Point)> {
+        let mut entities: Vec<_> = snapshot
+            .entities
+            .iter()
+            .map(|entity| (entity.id, entity.kind, entity.position))
+            .collect();
+        entities.sort_by_key(|(id, ..)| *id);
+        entities
+    }
+
+    #[test]
+    fn delta_applied_to_previous_snapshot_gives_the_current_one() {
+        let previous = snapshot(1.0, vec![star(0, 0.0), star(1, 10.0), star(2, 20.0)]);
+        let mut current = snapshot(2.0, vec![star(1, 15.0), star(2, 20.0), star(3, 30.0)]);
+        current.score = 4;
+        current.players[0] = 4;
+        current.lives = 2;
+
+        let delta = current.delta_from(&previous);
+        assert_eq!(delta.removed, vec![0]);
+        assert_eq!(delta.changed.len(), 2);
+
+        let mut rebuilt = previous.clone();
+        rebuilt.apply(&delta);
+        assert_eq!(rebuilt.time, current.time);
+        assert_eq!(rebuilt.score, current.score);
+        assert_eq!(rebuilt.players, current.players);
+        assert_eq!(rebuilt.lives, current.lives);
+        assert_eq!(entities(&rebuilt), entities(&current));
+    }
+
+    #[test]
+    fn split_parts_each_fit_in_a_datagram() {
+        let count = SNAPSHOT_ENTITIES_PER_PACKET as u32 * 3 + 7;
+        let whole = snapshot(
+            1.0,
+            (0..count)
+                .map(|id| EntityState {
+                    id,
+                    kind: EntityKind::Enemy { size: 30.0 },
+                    position: Point {
+                        x: id as f32,
+                        y: -(id as f32),
+                    },
+                })
+                .collect(),
+        );
+
+        let parts = whole.clone().split();
+        assert_eq!(parts.len(), 4);
+        for (index, part) in parts.iter().enumerate() {
+            assert_eq!(part.part, index as u32);
+            assert_eq!(part.parts, 4);
+            assert_eq!(part.time, whole.time);
+            assert!(encode_datagram(&ServerPacket::Snapshot(part.clone())).is_ok());
+        }
+
+        let ids: Vec<u32> = parts
+            .iter()
+            .flat_map(|part| part.entities.iter().map(|entity| entity.id))
+            .collect();
+        assert_eq!(ids, (0..count).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn small_snapshot_is_not_split() {
+        let parts = snapshot(1.0, vec![star(0, 0.0)]).split();
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].parts, 1);
+    }
+}
@@ -0,0 +1,953 @@
+pub mod lobby {
+    use super::super::{
+        DEFAULT_PORT, DEFAULT_SPECTATOR_PORT, HOST_FLAG, JOIN_FLAG, ROLLBACK_FLAG, SPECTATE_FLAG,
+    };
+    use bevy::prelude::*;
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum LaunchRequest {
+        Host { port: u16 },
+        Join { address: String },
+        Rollback { port: u16, peer: String },
+        Spectate { address: String },
+    }
+
+    impl LaunchRequest {
+        /// Parses `--host [port]`, `--join <address>`, `--rollback <port> <peer address>`
+        /// or `--spectate <address>` from the command line.
+        pub fn from_args() -> Option<Self> {
+            let args: Vec<String> = std::env::args().collect();
+            let value_after = |flag: &str| {
+                args.iter()
+                    .position(|arg| arg == flag)
+                    .map(|index| args.get(index + 1).cloned())
+            };
+
+            if let Some(index) = args.iter().position(|arg| arg == ROLLBACK_FLAG) {
+                let port = args
+                    .get(index + 1)
+                    .and_then(|port| port.parse().ok())
+                    .unwrap_or(DEFAULT_PORT);
+                let peer = args
+                    .get(index + 2)
+                    .cloned()
+                    .unwrap_or_else(|| format!("127.0.0.1:{}", port + 1));
+                return Some(LaunchRequest::Rollback { port, peer });
+            }
+
+            if let Some(address) = value_after(SPECTATE_FLAG) {
+                return Some(LaunchRequest::Spectate {
+                    address: address
+                        .unwrap_or_else(|| format!("127.0.0.1:{DEFAULT_SPECTATOR_PORT}")),
+                });
+            }
+
+            if let Some(port) = value_after(HOST_FLAG) {
+                let port = port
+                    .and_then(|port| port.parse().ok())
+                    .unwrap_or(DEFAULT_PORT);
+                return Some(LaunchRequest::Host { port });
+            }
+
+            value_after(JOIN_FLAG).map(|address| LaunchRequest::Join {
+                address: address.unwrap_or_else(|| format!("127.0.0.1:{DEFAULT_PORT}")),
+            })
+        }
+    }
+
+    /// Command line request, handled the first time the lobby opens.
+    #[derive(Resource)]
+    pub struct PendingLaunch {
+        pub request: Option<LaunchRequest>,
+    }
+
+    #[derive(Resource)]
+    pub struct AddressInput {
+        pub text: String,
+    }
+
+    impl Default for AddressInput {
+        fn default() -> Self {
+            Self {
+                text: format!("127.0.0.1:{DEFAULT_PORT}"),
+            }
+        }
+    }
+
+    impl AddressInput {
+        pub fn port(&self) -> u16 {
+            self.text
+                .rsplit(':')
+                .next()
+                .and_then(|port| port.parse().ok())
+                .unwrap_or(DEFAULT_PORT)
+        }
+    }
+
+    #[derive(Resource, Default)]
+    pub struct LobbyStatus {
+        pub text: String,
+    }
+}
+
+pub mod session {
+    use super::super::protocol::{
+        ClientPacket, MAX_PACKET_SIZE, ServerPacket, Snapshot, decode, encode_datagram,
+    };
+    use bevy::prelude::*;
+    use std::{
+        collections::VecDeque,
+        io,
+        net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs, UdpSocket},
+    };
+
+    pub const TIMEOUT_SECS: f32 = 5.0;
+    /// How often each side proves it is still there, in every state.
+    pub const HEARTBEAT_SECS: f32 = 1.0;
+    pub const SNAPSHOT_INTERVAL: f32 = 1.0 / 30.0;
+    pub const JOIN_RETRY_SECS: f32 = 0.5;
+    /// How far behind the host clients render, so there is always a snapshot to blend towards.
+    pub const INTERPOLATION_DELAY: f32 = 0.1;
+    pub const SNAPSHOT_BUFFER_SIZE: usize = 32;
+
+    pub struct RemoteClient {
+        pub address: SocketAddr,
+        pub player_index: usize,
+        pub last_heard: f32,
+        pub input: Vec2,
+    }
+
+    /// Source of `NetId`s for entities seen by remote apps.
+    #[derive(Resource, Default)]
+    pub struct NetIds {
+        pub next: u32,
+    }
+
+    /// Snapshots received from a remote simulation, in host time order.
+    #[derive(Default)]
+    pub struct SnapshotBuffer {
+        pub snapshots: VecDeque<Snapshot>,
+        /// Parts of a split snapshot that is still missing some. It is dropped when a
+        /// newer snapshot starts arriving, so a lost part never shows half a world.
+        pub pending: Vec<Snapshot>,
+        /// Estimated difference between the remote clock and ours.
+        pub clock_offset: Option<f32>,
+    }
+
+    impl SnapshotBuffer {
+        pub fn push(&mut self, snapshot: Snapshot, now: f32) {
+            let Some(snapshot) = self.assemble(snapshot) else {
+                return;
+            };
+
+            let offset = snapshot.time - now;
+            self.clock_offset = Some(match self.clock_offset {
+                Some(current) => current + (offset - current) * 0.1,
+                None => offset,
+            });
+
+            if self
+                .snapshots
+                .back()
+                .is_some_and(|last| last.time >= snapshot.time)
+            {
+                return;
+            }
+
+            self.snapshots.push_back(snapshot);
+            if self.snapshots.len() > SNAPSHOT_BUFFER_SIZE {
+                self.snapshots.pop_front();
+            }
+        }
+
+        /// Collects the parts of a split snapshot, returning it once all have arrived.
+        /// Whole snapshots pass straight through.
+        fn assemble(&mut self, part: Snapshot) -> Option<Snapshot> {
+            if part.parts <= 1 {
+                return Some(part);
+            }
+
+            if let Some(first) = self.pending.first() {
+                if part.time < first.time {
+                    return None;
+                }
+                if part.time > first.time {
+                    self.pending.clear();
+                }
+            }
+            if self.pending.iter().any(|pending| pending.part == part.part) {
+                return None;
+            }
+
+            let parts = part.parts as usize;
+            self.pending.push(part);
+            if self.pending.len() < parts {
+                return None;
+            }
+
+            self.pending.sort_by_key(|pending| pending.part);
+            let mut pending = std::mem::take(&mut self.pending).into_iter();
+            let mut snapshot = pending.next()?;
+            for rest in pending {
+                snapshot.entities.extend(rest.entities);
+            }
+            snapshot.part = 0;
+            snapshot.parts = 1;
+            Some(snapshot)
+        }
+
+        pub fn clear(&mut self) {
+            self.snapshots.clear();
+            self.pending.clear();
+        }
+
+        /// The two snapshots around the moment being rendered, and how far between
+        /// them that moment is.
+        pub fn interpolate(&mut self, now: f32) -> Option<(&Snapshot, &Snapshot, f32)> {
+            let render_time = now + self.clock_offset? - INTERPOLATION_DELAY;
+
+            while self.snapshots.len() > 2 && self.snapshots[1].time <= render_time {
+                self.snapshots.pop_front();
+            }
+
+            let (from, to) = match (self.snapshots.front(), self.snapshots.get(1)) {
+                (Some(from), Some(to)) if from.time <= render_time => (from, to),
+                (Some(only), _) => (only, only),
+                _ => return None,
+            };
+            let blend = if to.time > from.time {
+                ((render_time - from.time) / (to.time - from.time)).clamp(0.0, 1.0)
+            } else {
+                1.0
+            };
+
+            Some((from, to, blend))
+        }
+    }
+
+    /// Resolves `address`, preferring IPv4 since hosts listen on IPv4.
+    pub fn resolve(address: &str) -> io::Result<SocketAddr> {
+        let addresses: Vec<SocketAddr> = address.to_socket_addrs()?.collect();
+        addresses
+            .iter()
+            .find(|address| address.is_ipv4())
+            .or(addresses.first())
+            .copied()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "unknown address"))
+    }
+
+    /// Binds a non-blocking socket in the same address family as `remote`.
+    pub fn bind_for(remote: SocketAddr, port: u16) -> io::Result<UdpSocket> {
+        let unspecified = match remote {
+            SocketAddr::V4(_) => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            SocketAddr::V6(_) => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+        };
+        let socket = UdpSocket::bind((unspecified, port))?;
+        socket.set_nonblocking(true)?;
+        Ok(socket)
+    }
+
+    #[derive(Resource)]
+    pub struct HostSession {
+        pub socket: UdpSocket,
+        pub clients: Vec<RemoteClient>,
+        pub snapshot_timer: Timer,
+        pub heartbeat_timer: Timer,
+    }
+
+    impl HostSession {
+        pub fn bind(port: u16) -> io::Result<Self> {
+            let socket = UdpSocket::bind(("0.0.0.0", port))?;
+            socket.set_nonblocking(true)?;
+
+            Ok(Self {
+                socket,
+                clients: Vec::new(),
+                snapshot_timer: Timer::from_seconds(SNAPSHOT_INTERVAL, TimerMode::Repeating),
+                heartbeat_timer: Timer::from_seconds(HEARTBEAT_SECS, TimerMode::Repeating),
+            })
+        }
+
+        pub fn receive(&self) -> Option<(SocketAddr, ClientPacket)> {
+            let mut buffer = [0; MAX_PACKET_SIZE];
+            loop {
+                match self.socket.recv_from(&mut buffer) {
+                    Ok((size, address)) => {
+                        if let Some(packet) = decode(&buffer[..size]) {
+                            return Some((address, packet));
+                        }
+                    }
+                    Err(_) => return None,
+                }
+            }
+        }
+
+        pub fn send(&self, address: SocketAddr, packet: &ServerPacket) {
+            let result = encode_datagram(packet).and_then(|bytes| {
+                self.socket
+                    .send_to(&bytes, address)
+                    .map_err(|err| err.to_string())
+            });
+            if let Err(err) = result {
+                warn!("Could not send packet to {address}: {err}");
+            }
+        }
+
+        pub fn broadcast(&self, packet: &ServerPacket) {
+            for client in &self.clients {
+                self.send(client.address, packet);
+            }
+        }
+    }
+
+    #[derive(Resource)]
+    pub struct ClientSession {
+        pub socket: UdpSocket,
+        pub server: SocketAddr,
+        pub player_index: Option<usize>,
+        pub last_heard: f32,
+        pub join_timer: Timer,
+        pub heartbeat_timer: Timer,
+        pub snapshots: SnapshotBuffer,
+    }
+
+    impl ClientSession {
+        pub fn connect(address: &str, now: f32) -> io::Result<Self> {
+            let server = resolve(address)?;
+            let socket = bind_for(server, 0)?;
+
+            Ok(Self {
+                socket,
+                server,
+                player_index: None,
+                last_heard: now,
+                join_timer: Timer::from_seconds(JOIN_RETRY_SECS, TimerMode::Repeating),
+                heartbeat_timer: Timer::from_seconds(HEARTBEAT_SECS, TimerMode::Repeating),
+                snapshots: SnapshotBuffer::default(),
+            })
+        }
+
+        pub fn receive(&self) -> Option<ServerPacket> {
+            let mut buffer = [0; MAX_PACKET_SIZE];
+            loop {
+                match self.socket.recv_from(&mut buffer) {
+                    Ok((size, address)) => {
+                        if address != self.server {
+                            continue;
+                        }
+                        if let Some(packet) = decode(&buffer[..size]) {
+                            return Some(packet);
+                        }
+                    }
+                    Err(_) => return None,
+                }
+            }
+        }
+
+        pub fn send(&self, packet: &ClientPacket) {
+            let result = encode_datagram(packet).and_then(|bytes| {
+                self.socket
+                    .send_to(&bytes, self.server)
+                    .map_err(|err| err.to_string())
+            });
+            if let Err(err) = result {
+                warn!("Could not send packet to {}: {err}", self.server);
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::super::super::protocol::{
+            EntityKind, EntityState, SNAPSHOT_ENTITIES_PER_PACKET,
+        };
+        use super::*;
+        use crate::game::{components::player::MAX_PLAYERS, resources::level::Point};
+
+        const SPLIT_ENTITIES: u32 = SNAPSHOT_ENTITIES_PER_PACKET as u32 * 2 + 50;
+
+        fn snapshot(time: f32, count: u32) -> Snapshot {
+            Snapshot {
+                time,
+                score: 0,
+                players: [0; MAX_PLAYERS],
+                lives: 3,
+                countdown: 0,
+                part: 0,
+                parts: 1,
+                entities: (0..count)
+                    .map(|id| EntityState {
+                        id,
+                        kind: EntityKind::Star,
+                        position: Point::default(),
+                    })
+                    .collect(),
+            }
+        }
+
+        #[test]
+        fn split_snapshot_is_buffered_once_every_part_arrives() {
+            let mut parts = snapshot(1.0, SPLIT_ENTITIES).split();
+            assert_eq!(parts.len(), 3);
+            parts.reverse();
+            let last = parts.pop().unwrap();
+
+            let mut buffer = SnapshotBuffer::default();
+            for part in parts {
+                buffer.push(part, 0.0);
+                assert!(buffer.snapshots.is_empty());
+            }
+            buffer.push(last, 0.0);
+
+            assert_eq!(buffer.snapshots.len(), 1);
+            let snapshot = &buffer.snapshots[0];
+            assert_eq!(snapshot.parts, 1);
+            let ids: Vec<u32> = snapshot.entities.iter().map(|entity| entity.id).collect();
+            assert_eq!(ids, (0..SPLIT_ENTITIES).collect::<Vec<_>>());
+        }
+
+        #[test]
+        fn incomplete_snapshot_is_dropped_for_a_newer_one() {
+            let mut buffer = SnapshotBuffer::default();
+            let mut old = snapshot(1.0, SPLIT_ENTITIES).split();
+            old.pop();
+            for part in old {
+                buffer.push(part, 0.0);
+            }
+            for part in snapshot(2.0, SPLIT_ENTITIES).split() {
+                buffer.push(part, 0.0);
+            }
+
+            assert_eq!(buffer.snapshots.len(), 1);
+            assert_eq!(buffer.snapshots[0].time, 2.0);
+            assert!(buffer.pending.is_empty());
+        }
+    }
+}
+
+pub mod rollback {
+    use super::super::{
+        LATENCY_FLAG, LOSS_FLAG,
+        components::rollback::RollbackId,
+        protocol::{MAX_PACKET_SIZE, PeerPacket, decode, encode_datagram},
+    };
+    use super::session::{bind_for, resolve};
+    use crate::game::{
+        components::{
+            enemy::{Enemy, Telegraphing},
+            player::{Boosted, Invulnerable, Velocity},
+        },
+        messages::game_states::GameOver,
+        resources::{
+            countdown::{COUNTDOWN_FROM, COUNTDOWN_STEP_SECS},
+            level::LevelData,
+            mode::Lives,
+            random::GameRng,
+            score::Score,
+        },
+    };
+    use bevy::prelude::*;
+    use rand::random;
+    use std::{
+        collections::VecDeque,
+        io,
+        net::{SocketAddr, UdpSocket},
+    };
+
+    pub const ROLLBACK_STEP: f32 = 1.0 / 60.0;
+    /// Frames counted down before the first simulated one, the same on both peers.
+    pub const COUNTDOWN_FRAMES: u32 =
+        (COUNTDOWN_FROM as f32 * COUNTDOWN_STEP_SECS / ROLLBACK_STEP).round() as u32;
+    /// How many frames the local simulation may run ahead of the peer's confirmed input.
+    pub const MAX_PREDICTION_FRAMES: u32 = 8;
+    pub const MAX_INPUTS_PER_PACKET: u32 = 32;
+    pub const HELLO_RETRY_SECS: f32 = 0.5;
+
+    /// Artificial network trouble applied to outgoing packets, for testing.
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct LinkConditions {
+        /// Seconds each packet is held back before being sent.
+        pub latency: f32,
+        /// Chance, between 0 and 1, that a packet is dropped.
+        pub packet_loss: f32,
+    }
+
+    impl LinkConditions {
+        /// Parses `--latency <ms>` and `--loss <percent>` from the command line.
+        pub fn from_args() -> Self {
+            let args: Vec<String> = std::env::args().collect();
+            let value_after = |flag: &str| {
+                args.iter()
+                    .position(|arg| arg == flag)
+                    .and_then(|index| args.get(index + 1))
+                    .and_then(|value| value.parse::<f32>().ok())
+                    .unwrap_or(0.0)
+            };
+
+            Self {
+                latency: value_after(LATENCY_FLAG).max(0.0) / 1000.0,
+                packet_loss: (value_after(LOSS_FLAG) / 100.0).clamp(0.0, 1.0),
+            }
+        }
+    }
+
+    pub struct DelayedPacket {
+        pub send_at: f32,
+        pub bytes: Vec<u8>,
+    }
+
+    #[derive(Clone)]
+    pub enum SavedKind {
+        Player {
+            index: usize,
+            velocity: Velocity,
+            invulnerable: Option<Invulnerable>,
+            boosted: Option<Boosted>,
+        },
+        Enemy {
+            enemy: Enemy,
+            telegraphing: Option<Telegraphing>,
+        },
+        Star,
+    }
+
+    #[derive(Clone)]
+    pub struct SavedEntity {
+        pub id: RollbackId,
+        pub kind: SavedKind,
+        pub transform: Transform,
+    }
+
+    /// Everything the simulation reads, captured at the start of `frame`.
+    pub struct WorldSnapshot {
+        pub frame: u32,
+        pub entities: Vec<SavedEntity>,
+        pub score: Score,
+        pub lives: Lives,
+        pub star_timer: Timer,
+        pub enemy_timer: Timer,
+        pub rng: GameRng,
+        pub clock: Time,
+    }
+
+    #[derive(Resource)]
+    pub struct RollbackSession {
+        pub socket: UdpSocket,
+        pub peer: SocketAddr,
+        pub conditions: LinkConditions,
+        pub outgoing: Vec<DelayedPacket>,
+        pub nonce: u64,
+        /// Level offered to the peer; player 1 plays the level of player 0.
+        pub level: LevelData,
+        /// Set once both peers have said hello.
+        pub local_player: Option<usize>,
+        pub hello_timer: Timer,
+        pub peer_started: bool,
+        pub last_heard: f32,
+        /// Next frame to simulate.
+        pub frame: u32,
+        pub accumulator: f32,
+        pub local_direction: Vec2,
+        pub local_inputs: Vec<Vec2>,
+        pub remote_inputs: Vec<Option<Vec2>>,
+        /// Remote input is known for every frame before this one.
+        pub confirmed: u32,
+        /// Remote input each simulated frame was run with.
+        pub predicted: Vec<Vec2>,
+        /// Number of our frames the peer has confirmed.
+        pub peer_ack: u32,
+        pub rollback_to: Option<u32>,
+        pub snapshots: VecDeque<WorldSnapshot>,
+        /// Next `RollbackId` to hand out.
+        pub next_id: u32,
+        /// Time the simulation has run, in whole steps. Installed as `Time` for each
+        /// step, so `elapsed` keeps counting across frames and rollbacks.
+        pub clock: Time,
+        /// A game over that happened on a frame not yet confirmed.
+        pub pending_game_over: Option<(u32, GameOver)>,
+        pub finished: bool,
+    }
+
+    impl RollbackSession {
+        pub fn bind(
+            port: u16,
+            peer: &str,
+            level: LevelData,
+            conditions: LinkConditions,
+            now: f32,
+        ) -> io::Result<Self> {
+            let peer = resolve(peer)?;
+            let socket = bind_for(peer, port)?;
+
+            Ok(Self {
+                socket,
+                peer,
+                conditions,
+                outgoing: Vec::new(),
+                nonce: random(),
+                level,
+                local_player: None,
+                hello_timer: Timer::from_seconds(HELLO_RETRY_SECS, TimerMode::Repeating),
+                peer_started: false,
+                last_heard: now,
+                frame: 0,
+                accumulator: 0.0,
+                local_direction: Vec2::ZERO,
+                local_inputs: Vec::new(),
+                remote_inputs: Vec::new(),
+                confirmed: 0,
+                predicted: Vec::new(),
+                peer_ack: 0,
+                rollback_to: None,
+                snapshots: VecDeque::new(),
+                next_id: 0,
+                clock: Time::default(),
+                pending_game_over: None,
+                finished: false,
+            })
+        }
+
+        pub fn receive(&self) -> Option<PeerPacket> {
+            let mut buffer = [0; MAX_PACKET_SIZE];
+            loop {
+                match self.socket.recv_from(&mut buffer) {
+                    Ok((size, address)) => {
+                        if address != self.peer {
+                            continue;
+                        }
+                        if let Some(packet) = decode(&buffer[..size]) {
+                            return Some(packet);
+                        }
+                    }
+                    Err(_) => return None,
+                }
+            }
+        }
+
+        /// Queues a packet, dropping or delaying it according to the link conditions.
+        pub fn send(&mut self, packet: &PeerPacket, now: f32) {
+            if random::<f32>() < self.conditions.packet_loss {
+                return;
+            }
+
+            let bytes = match encode_datagram(packet) {
+                Ok(bytes) => bytes,
+                Err(err) => {
+                    warn!("Could not send packet to {}: {err}", self.peer);
+                    return;
+                }
+            };
+            self.outgoing.push(DelayedPacket {
+                send_at: now + self.conditions.latency,
+                bytes,
+            });
+            self.flush(now);
+        }
+
+        pub fn flush(&mut self, now: f32) {
+            let (due, waiting) = self
+                .outgoing
+                .drain(..)
+                .partition(|packet| packet.send_at <= now);
+            self.outgoing = waiting;
+
+            for packet in due {
+                if let Err(err) = self.socket.send_to(&packet.bytes, self.peer) {
+                    warn!("Could not send packet to {}: {err}", self.peer);
+                }
+            }
+        }
+
+        /// Tells the peer we are gone, bypassing the link conditions.
+        pub fn leave(&self) {
+            let result = encode_datagram(&PeerPacket::Leave).and_then(|bytes| {
+                self.socket
+                    .send_to(&bytes, self.peer)
+                    .map_err(|err| err.to_string())
+            });
+            if let Err(err) = result {
+                warn!("Could not send packet to {}: {err}", self.peer);
+            }
+        }
+
+        /// Known remote input for `frame`, or a prediction that the peer keeps doing
+        /// whatever they did last.
+        pub fn remote_input(&self, frame: u32) -> Vec2 {
+            self.remote_inputs
+                .get(frame as usize)
+                .copied()
+                .flatten()
+                .or_else(|| {
+                    self.confirmed
+                        .checked_sub(1)
+                        .and_then(|last| self.remote_inputs[last as usize])
+                })
+                .unwrap_or(Vec2::ZERO)
+        }
+
+        /// Stores a remote input and schedules a rollback when it contradicts the
+        /// prediction a simulated frame was run with.
+        pub fn record_remote_input(&mut self, frame: u32, direction: Vec2) {
+            let index = frame as usize;
+            if self.remote_inputs.len() <= index {
+                self.remote_inputs.resize(index + 1, None);
+            }
+            if self.remote_inputs[index].is_some() {
+                return;
+            }
+            self.remote_inputs[index] = Some(direction);
+
+            while self
+                .remote_inputs
+                .get(self.confirmed as usize)
+                .is_some_and(Option::is_some)
+            {
+                self.confirmed += 1;
+            }
+
+            if self
+                .predicted
+                .get(index)
+                .is_some_and(|predicted| *predicted != direction)
+            {
+                self.rollback_to = Some(self.rollback_to.map_or(frame, |from| from.min(frame)));
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn session() -> RollbackSession {
+            RollbackSession::bind(
+                0,
+                "127.0.0.1:9",
+                LevelData::default(),
+                LinkConditions::default(),
+                0.0,
+            )
+            .unwrap()
+        }
+
+        #[test]
+        fn missing_remote_input_repeats_the_last_confirmed_one() {
+            let mut session = session();
+            assert_eq!(session.remote_input(0), Vec2::ZERO);
+
+            session.record_remote_input(0, Vec2::X);
+            session.record_remote_input(1, Vec2::Y);
+            session.record_remote_input(3, Vec2::NEG_X);
+
+            assert_eq!(session.confirmed, 2);
+            assert_eq!(session.remote_input(1), Vec2::Y);
+            assert_eq!(session.remote_input(2), Vec2::Y);
+            assert_eq!(session.remote_input(3), Vec2::NEG_X);
+            assert_eq!(session.remote_input(4), Vec2::Y);
+        }
+
+        #[test]
+        fn wrong_prediction_rolls_back_to_the_earliest_frame() {
+            let mut session = session();
+            session.predicted = vec![Vec2::ZERO; 4];
+
+            session.record_remote_input(0, Vec2::ZERO);
+            assert_eq!(session.rollback_to, None);
+
+            session.record_remote_input(3, Vec2::X);
+            session.record_remote_input(1, Vec2::X);
+            assert_eq!(session.rollback_to, Some(1));
+        }
+    }
+}
+
+pub mod spectator {
+    use super::super::{
+        BROADCAST_FLAG, DEFAULT_SPECTATOR_PORT,
+        protocol::{Snapshot, SpectatorPacket, decode_framed, encode_framed},
+    };
+    use super::session::{SNAPSHOT_INTERVAL, SnapshotBuffer};
+    use bevy::prelude::*;
+    use std::{
+        io::{self, Read, Write},
+        net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+        time::Duration,
+    };
+
+    /// A spectator whose unsent backlog grows past this many bytes is dropped.
+    pub const MAX_BACKLOG: usize = 1 << 20;
+    pub const CONNECT_TIMEOUT_SECS: u64 = 2;
+    pub const FREE_CAMERA_SPEED: f32 = 600.0;
+    pub const FOCUS_SMOOTHING: f32 = 8.0;
+    pub const ZOOM_STEP: f32 = 0.1;
+    pub const MIN_ZOOM: f32 = 0.25;
+    pub const MAX_ZOOM: f32 = 4.0;
+
+    /// Port to broadcast on, from `--broadcast [port]`.
+    pub fn broadcast_port_from_args() -> Option<u16> {
+        let args: Vec<String> = std::env::args().collect();
+        args.iter()
+            .position(|arg| arg == BROADCAST_FLAG)
+            .map(|index| {
+                args.get(index + 1)
+                    .and_then(|port| port.parse().ok())
+                    .unwrap_or(DEFAULT_SPECTATOR_PORT)
+            })
+    }
+
+    pub struct Spectator {
+        pub stream: TcpStream,
+        pub address: SocketAddr,
+        pub outgoing: Vec<u8>,
+    }
+
+    impl Spectator {
+        /// Writes as much of the backlog as the socket takes without blocking.
+        fn flush(&mut self) -> io::Result<()> {
+            while !self.outgoing.is_empty() {
+                match self.stream.write(&self.outgoing) {
+                    Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+                    Ok(written) => {
+                        self.outgoing.drain(..written);
+                    }
+                    Err(err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                    Err(err) => return Err(err),
+                }
+            }
+
+            if self.outgoing.len() > MAX_BACKLOG {
+                return Err(io::Error::other("spectator is too far behind"));
+            }
+            Ok(())
+        }
+    }
+
+    /// Streams the running game to any number of spectators.
+    #[derive(Resource)]
+    pub struct SpectatorServer {
+        pub listener: TcpListener,
+        pub spectators: Vec<Spectator>,
+        /// Last snapshot streamed, deltas are computed against it.
+        pub last_sent: Option<Snapshot>,
+        pub snapshot_timer: Timer,
+    }
+
+    impl SpectatorServer {
+        pub fn bind(port: u16) -> io::Result<Self> {
+            let listener = TcpListener::bind(("0.0.0.0", port))?;
+            listener.set_nonblocking(true)?;
+
+            Ok(Self {
+                listener,
+                spectators: Vec::new(),
+                last_sent: None,
+                snapshot_timer: Timer::from_seconds(SNAPSHOT_INTERVAL, TimerMode::Repeating),
+            })
+        }
+
+        /// Accepts waiting connections, returning the index of each new spectator.
+        pub fn accept(&mut self) -> Vec<usize> {
+            let mut accepted = Vec::new();
+
+            while let Ok((stream, address)) = self.listener.accept() {
+                if stream.set_nonblocking(true).is_err() {
+                    continue;
+                }
+                let _ = stream.set_nodelay(true);
+
+                info!("Spectator connected from {address}");
+                accepted.push(self.spectators.len());
+                self.spectators.push(Spectator {
+                    stream,
+                    address,
+                    outgoing: Vec::new(),
+                });
+            }
+
+            accepted
+        }
+
+        pub fn send(&mut self, index: usize, packet: &SpectatorPacket) {
+            self.spectators[index]
+                .outgoing
+                .extend(encode_framed(packet));
+        }
+
+        pub fn broadcast(&mut self, packet: &SpectatorPacket) {
+            let bytes = encode_framed(packet);
+            for spectator in &mut self.spectators {
+                spectator.outgoing.extend_from_slice(&bytes);
+            }
+        }
+
+        pub fn flush(&mut self) {
+            self.spectators
+                .retain_mut(|spectator| match spectator.flush() {
+                    Ok(()) => true,
+                    Err(err) => {
+                        info!("Spectator {} left: {err}", spectator.address);
+                        false
+                    }
+                });
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum CameraFocus {
+        #[default]
+        Free,
+        Player(usize),
+    }
+
+    #[derive(Resource)]
+    pub struct SpectatorSession {
+        pub stream: TcpStream,
+        pub address: String,
+        pub incoming: Vec<u8>,
+        /// The broadcaster's world as rebuilt from the full snapshot and deltas.
+        pub world: Option<Snapshot>,
+        pub snapshots: SnapshotBuffer,
+        pub focus: CameraFocus,
+    }
+
+    impl SpectatorSession {
+        pub fn connect(address: &str) -> io::Result<Self> {
+            let server = address
+                .to_socket_addrs()?
+                .next()
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "unknown address"))?;
+            let stream =
+                TcpStream::connect_timeout(&server, Duration::from_secs(CONNECT_TIMEOUT_SECS))?;
+            stream.set_nonblocking(true)?;
+            let _ = stream.set_nodelay(true);
+
+            Ok(Self {
+                stream,
+                address: address.to_string(),
+                incoming: Vec::new(),
+                world: None,
+                snapshots: SnapshotBuffer::default(),
+                focus: CameraFocus::default(),
+            })
+        }
+
+        /// Reads everything that has arrived; fails once the broadcaster is gone.
+        pub fn receive(&mut self) -> io::Result<Vec<SpectatorPacket>> {
+            let mut buffer = [0; 4096];
+            loop {
+                match self.stream.read(&mut buffer) {
+                    Ok(0) => return Err(io::ErrorKind::ConnectionAborted.into()),
+                    Ok(size) => self.incoming.extend_from_slice(&buffer[..size]),
+                    Err(err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                    Err(err) => return Err(err),
+                }
+            }
+
+            let mut packets = Vec::new();
+            while let Some(packet) = decode_framed(&mut self.incoming).map_err(io::Error::other)? {
+                packets.extend(packet);
+            }
+            Ok(packets)
+        }
+    }
+}
@@ -0,0 +1,1777 @@
+pub mod layout {
+    use super::super::components::layout::*;
+    use super::super::resources::lobby::{AddressInput, LobbyStatus};
+    use crate::widgets::{builders::*, components::BackAction, messages::ButtonAction};
+    use bevy::prelude::*;
+
+    pub fn spawn_lobby(mut commands: Commands) {
+        commands
+            .spawn((screen(Lobby), BackAction(ButtonAction::MainMenu)))
+            .with_children(|parent| {
+                parent.spawn(title("Online"));
+
+                parent.spawn((AddressText, label("")));
+                parent.spawn((StatusText, caption("")));
+
+                parent.spawn(row()).with_children(|p| {
+                    p.spawn(button(ButtonAction::Host, "Host"));
+                    p.spawn(button(ButtonAction::Join, "Join"));
+                    p.spawn(button(ButtonAction::Watch, "Watch"));
+                });
+
+                parent.spawn(button(ButtonAction::Start, "Start"));
+                parent.spawn(button(ButtonAction::MainMenu, "Back"));
+            });
+    }
+
+    pub fn despawn_lobby(mut commands: Commands, lobby_query: Query<Entity, With<Lobby>>) {
+        if let Ok(entity) = lobby_query.single() {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    pub fn update_lobby_text(
+        mut address_text: Query<&mut Text, (With<AddressText>, Without<StatusText>)>,
+        mut status_text: Query<&mut Text, (With<StatusText>, Without<AddressText>)>,
+        address_input: Res<AddressInput>,
+        lobby_status: Res<LobbyStatus>,
+    ) {
+        if let Ok(mut text) = address_text.single_mut() {
+            text.0 = format!("Address: {}_", address_input.text);
+        }
+        if let Ok(mut text) = status_text.single_mut() {
+            text.0 = lobby_status.text.clone();
+        }
+    }
+}
+
+pub mod interaction {
+    use super::super::{
+        protocol::check_level_size,
+        resources::{
+            lobby::{AddressInput, LaunchRequest, LobbyStatus, PendingLaunch},
+            rollback::{LinkConditions, RollbackSession},
+            session::{ClientSession, HostSession},
+            spectator::SpectatorSession,
+        },
+    };
+    use crate::{
+        AppState,
+        game::resources::{
+            level::{LevelLibrary, SelectedLevel},
+            mode::GameMode,
+            network::NetworkRole,
+        },
+        transition::messages::{TransitionEffect, TransitionRequest},
+        widgets::messages::ButtonAction,
+    };
+    use bevy::{
+        input::keyboard::{Key, KeyboardInput},
+        prelude::*,
+    };
+
+    pub fn type_address(
+        mut keyboard_reader: MessageReader<KeyboardInput>,
+        mut address_input: ResMut<AddressInput>,
+    ) {
+        for input in keyboard_reader.read() {
+            if !input.state.is_pressed() {
+                continue;
+            }
+
+            match &input.logical_key {
+                Key::Backspace => {
+                    address_input.text.pop();
+                }
+                Key::Character(characters) => {
+                    address_input.text.extend(
+                        characters
+                            .chars()
+                            .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | ':' | '-')),
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+
+    pub fn start_hosting(
+        commands: &mut Commands,
+        port: u16,
+        game_mode: &mut GameMode,
+        network_role: &mut NetworkRole,
+        lobby_status: &mut LobbyStatus,
+    ) {
+        match HostSession::bind(port) {
+            Ok(session) => {
+                commands.insert_resource(session);
+                commands.remove_resource::<ClientSession>();
+                commands.remove_resource::<RollbackSession>();
+                commands.remove_resource::<SpectatorSession>();
+                *network_role = NetworkRole::Host;
+                if *game_mode == GameMode::Single {
+                    *game_mode = GameMode::Coop;
+                }
+                lobby_status.text = format!(
+                    "Hosting {} on port {port}, waiting for players...",
+                    game_mode.label()
+                );
+            }
+            Err(err) => lobby_status.text = format!("Could not host on port {port}: {err}"),
+        }
+    }
+
+    pub fn start_joining(
+        commands: &mut Commands,
+        address: &str,
+        now: f32,
+        network_role: &mut NetworkRole,
+        lobby_status: &mut LobbyStatus,
+    ) {
+        match ClientSession::connect(address, now) {
+            Ok(session) => {
+                commands.insert_resource(session);
+                commands.remove_resource::<HostSession>();
+                commands.remove_resource::<RollbackSession>();
+                commands.remove_resource::<SpectatorSession>();
+                *network_role = NetworkRole::Client;
+                lobby_status.text = format!("Connecting to {address}...");
+            }
+            Err(err) => lobby_status.text = format!("Could not join {address}: {err}"),
+        }
+    }
+
+    pub fn start_spectating(
+        commands: &mut Commands,
+        address: &str,
+        network_role: &mut NetworkRole,
+        lobby_status: &mut LobbyStatus,
+    ) {
+        match SpectatorSession::connect(address) {
+            Ok(session) => {
+                commands.insert_resource(session);
+                commands.remove_resource::<HostSession>();
+                commands.remove_resource::<ClientSession>();
+                commands.remove_resource::<RollbackSession>();
+                *network_role = NetworkRole::Spectator;
+                lobby_status.text = format!("Watching {address}, waiting for a run to start...");
+            }
+            Err(err) => lobby_status.text = format!("Could not watch {address}: {err}"),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn start_rollback(
+        commands: &mut Commands,
+        port: u16,
+        peer: &str,
+        level_library: &LevelLibrary,
+        selected_level: &SelectedLevel,
+        now: f32,
+        network_role: &mut NetworkRole,
+        lobby_status: &mut LobbyStatus,
+    ) {
+        let conditions = LinkConditions::from_args();
+        let level = level_library.get(selected_level).clone();
+        if let Err(err) = check_level_size(&level) {
+            lobby_status.text = format!("This level is too large to play online: {err}");
+            return;
+        }
+
+        match RollbackSession::bind(port, peer, level, conditions, now) {
+            Ok(session) => {
+                commands.insert_resource(session);
+                commands.remove_resource::<HostSession>();
+                commands.remove_resource::<ClientSession>();
+                commands.remove_resource::<SpectatorSession>();
+                *network_role = NetworkRole::Rollback;
+                lobby_status.text = format!(
+                    "Rollback versus on port {port}, waiting for {peer}...\n\
+                     latency {:.0} ms, packet loss {:.0}%",
+                    conditions.latency * 1000.0,
+                    conditions.packet_loss * 100.0
+                );
+            }
+            Err(err) => lobby_status.text = format!("Could not open port {port}: {err}"),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn handle_launch_request(
+        mut commands: Commands,
+        mut pending_launch: ResMut<PendingLaunch>,
+        mut address_input: ResMut<AddressInput>,
+        mut game_mode: ResMut<GameMode>,
+        mut network_role: ResMut<NetworkRole>,
+        mut lobby_status: ResMut<LobbyStatus>,
+        level_library: Res<LevelLibrary>,
+        selected_level: Res<SelectedLevel>,
+        time: Res<Time>,
+    ) {
+        match pending_launch.request.take() {
+            Some(LaunchRequest::Host { port }) => start_hosting(
+                &mut commands,
+                port,
+                &mut game_mode,
+                &mut network_role,
+                &mut lobby_status,
+            ),
+            Some(LaunchRequest::Join { address }) => {
+                start_joining(
+                    &mut commands,
+                    &address,
+                    time.elapsed_secs(),
+                    &mut network_role,
+                    &mut lobby_status,
+                );
+                address_input.text = address;
+            }
+            Some(LaunchRequest::Rollback { port, peer }) => {
+                start_rollback(
+                    &mut commands,
+                    port,
+                    &peer,
+                    &level_library,
+                    &selected_level,
+                    time.elapsed_secs(),
+                    &mut network_role,
+                    &mut lobby_status,
+                );
+                address_input.text = peer;
+            }
+            Some(LaunchRequest::Spectate { address }) => {
+                start_spectating(
+                    &mut commands,
+                    &address,
+                    &mut network_role,
+                    &mut lobby_status,
+                );
+                address_input.text = address;
+            }
+            None => {}
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn handle_lobby_actions(
+        mut commands: Commands,
+        mut action_reader: MessageReader<ButtonAction>,
+        address_input: Res<AddressInput>,
+        mut game_mode: ResMut<GameMode>,
+        mut network_role: ResMut<NetworkRole>,
+        mut lobby_status: ResMut<LobbyStatus>,
+        mut transition_writer: MessageWriter<TransitionRequest>,
+        level_library: Res<LevelLibrary>,
+        selected_level: Res<SelectedLevel>,
+        time: Res<Time>,
+    ) {
+        for action in action_reader.read() {
+            match action {
+                ButtonAction::Host => start_hosting(
+                    &mut commands,
+                    address_input.port(),
+                    &mut game_mode,
+                    &mut network_role,
+                    &mut lobby_status,
+                ),
+                ButtonAction::Join => start_joining(
+                    &mut commands,
+                    &address_input.text,
+                    time.elapsed_secs(),
+                    &mut network_role,
+                    &mut lobby_status,
+                ),
+                ButtonAction::Watch => start_spectating(
+                    &mut commands,
+                    &address_input.text,
+                    &mut network_role,
+                    &mut lobby_status,
+                ),
+                ButtonAction::Start => match *network_role {
+                    NetworkRole::Host => {
+                        match check_level_size(level_library.get(&selected_level)) {
+                            Ok(()) => {
+                                transition_writer.write(TransitionRequest::new(
+                                    AppState::Game,
+                                    TransitionEffect::Wipe,
+                                ));
+                            }
+                            Err(err) => {
+                                lobby_status.text =
+                                    format!("This level is too large to play online: {err}")
+                            }
+                        }
+                    }
+                    NetworkRole::Client => {
+                        lobby_status.text = "Only the host can start the match".to_string()
+                    }
+                    NetworkRole::Spectator => {
+                        lobby_status.text = "Spectators follow the broadcaster's runs".to_string()
+                    }
+                    NetworkRole::Rollback => {
+                        lobby_status.text =
+                            "Rollback matches start once the peer answers".to_string()
+                    }
+                    NetworkRole::Offline => {
+                        lobby_status.text = "Host or join a match first".to_string()
+                    }
+                },
+                _ => {}
+            }
+        }
+    }
+
+    /// Leaving to the main menu ends any online session.
+    pub fn shutdown_network(
+        mut commands: Commands,
+        client_session: Option<Res<ClientSession>>,
+        rollback_session: Option<Res<RollbackSession>>,
+        mut network_role: ResMut<NetworkRole>,
+        mut lobby_status: ResMut<LobbyStatus>,
+    ) {
+        if let Some(session) = client_session {
+            session.send(&super::super::protocol::ClientPacket::Leave);
+        }
+        if let Some(session) = rollback_session {
+            session.leave();
+        }
+
+        commands.remove_resource::<ClientSession>();
+        commands.remove_resource::<HostSession>();
+        commands.remove_resource::<RollbackSession>();
+        commands.remove_resource::<SpectatorSession>();
+        *network_role = NetworkRole::Offline;
+        lobby_status.text.clear();
+    }
+}
+
+pub mod sync {
+    use super::super::{
+        components::sync::NetId,
+        protocol::{EntityKind, EntityState, Snapshot},
+        resources::session::{NetIds, SnapshotBuffer},
+    };
+    use crate::animation::{
+        components::SpriteAnimation,
+        resources::{ClipKind, SheetKind},
+    };
+    use crate::game::{
+        components::{enemy::Enemy, player::Player, star::Star},
+        resources::{countdown::Countdown, mode::Lives, score::Score},
+        systems::enemy::enemy_sprite,
+    };
+    use crate::loading::resources::GameAssets;
+    use bevy::prelude::*;
+    use std::collections::HashMap;
+
+    /// Everything a snapshot needs to describe one networked entity.
+    pub type SnapshotQuery<'w, 's> = Query<
+        'w,
+        's,
+        (
+            &'static NetId,
+            &'static Transform,
+            Option<&'static Player>,
+            Option<&'static Enemy>,
+            Has<Star>,
+        ),
+    >;
+
+    type UntaggedEntity = (
+        Without<NetId>,
+        Or<(Added<Player>, Added<Enemy>, Added<Star>)>,
+    );
+
+    /// Tags new gameplay entities so remote apps can follow them.
+    pub fn assign_net_ids(
+        mut commands: Commands,
+        mut net_ids: ResMut<NetIds>,
+        new_entities: Query<Entity, UntaggedEntity>,
+    ) {
+        for entity in &new_entities {
+            commands.entity(entity).insert(NetId(net_ids.next));
+            net_ids.next = net_ids.next.wrapping_add(1);
+        }
+    }
+
+    pub fn capture_snapshot(
+        entity_query: &SnapshotQuery,
+        score: &Score,
+        lives: &Lives,
+        countdown: Option<&Countdown>,
+        time: f32,
+    ) -> Snapshot {
+        let entities = entity_query
+            .iter()
+            .filter_map(|(id, transform, player, enemy, star)| {
+                let kind = match (player, enemy, star) {
+                    (Some(player), _, _) => EntityKind::Player(player.index),
+                    (_, Some(enemy), _) => EntityKind::Enemy { size: enemy.size },
+                    (_, _, true) => EntityKind::Star,
+                    _ => return None,
+                };
+
+                Some(EntityState {
+                    id: id.0,
+                    kind,
+                    position: transform.translation.truncate().into(),
+                })
+            })
+            .collect();
+
+        Snapshot {
+            time,
+            score: score.value,
+            players: score.players,
+            lives: lives.value,
+            countdown: countdown.map_or(0, |countdown| countdown.remaining),
+            part: 0,
+            parts: 1,
+            entities,
+        }
+    }
+
+    /// Renders the remote world slightly in the past, blending the two snapshots
+    /// around that moment so movement stays smooth between packets.
+    pub fn sync_proxies(
+        commands: &mut Commands,
+        buffer: &mut SnapshotBuffer,
+        proxy_query: &mut Query<(Entity, &NetId, &mut Transform)>,
+        game_assets: &GameAssets,
+        score: &mut Score,
+        lives: &mut Lives,
+        now: f32,
+    ) {
+        let Some((from, to, blend)) = buffer.interpolate(now) else {
+            return;
+        };
+
+        score.value = to.score;
+        score.players = to.players;
+        lives.value = to.lives;
+
+        let previous: HashMap<u32, Vec2> = from
+            .entities
+            .iter()
+            .map(|entity| (entity.id, entity.position.into()))
+            .collect();
+        let mut proxies: HashMap<u32, Entity> = HashMap::new();
+
+        for (entity, id, mut transform) in proxy_query.iter_mut() {
+            let Some(state) = to.entities.iter().find(|state| state.id == id.0) else {
+                commands.entity(entity).despawn();
+                continue;
+            };
+
+            let target = Vec2::from(state.position);
+            let position = previous
+                .get(&id.0)
+                .map(|start| start.lerp(target, blend))
+                .unwrap_or(target);
+            transform.translation = position.extend(transform.translation.z);
+            proxies.insert(id.0, entity);
+        }
+
+        for state in to
+            .entities
+            .iter()
+            .filter(|state| !proxies.contains_key(&state.id))
+        {
+            spawn_proxy(
+                commands,
+                game_assets,
+                state.id,
+                state.kind,
+                state.position.into(),
+            );
+        }
+    }
+
+    fn spawn_proxy(
+        commands: &mut Commands,
+        game_assets: &GameAssets,
+        id: u32,
+        kind: EntityKind,
+        position: Vec2,
+    ) {
+        let transform = Transform::from_xyz(position.x, position.y, 0.0);
+
+        match kind {
+            EntityKind::Player(index) => {
+                commands.spawn((
+                    NetId(id),
+                    Player { index },
+                    Sprite::from_image(SheetKind::player(index).image(&game_assets.sprites)),
+                    SpriteAnimation::new(SheetKind::player(index), ClipKind::SpawnIn),
+                    transform,
+                ));
+            }
+            EntityKind::Enemy { size } => {
+                commands.spawn((
+                    NetId(id),
+                    Enemy::new(Vec2::X, size),
+                    enemy_sprite(game_assets, size),
+                    SpriteAnimation::new(SheetKind::Enemy, ClipKind::SpawnIn),
+                    transform,
+                ));
+            }
+            EntityKind::Star => {
+                commands.spawn((
+                    NetId(id),
+                    Star {},
+                    Sprite::from_image(SheetKind::Star.image(&game_assets.sprites)),
+                    SpriteAnimation::new(SheetKind::Star, ClipKind::SpawnIn),
+                    transform,
+                ));
+            }
+        }
+    }
+}
+
+pub mod host {
+    use super::super::{
+        protocol::{ClientPacket, ServerPacket},
+        resources::{
+            lobby::LobbyStatus,
+            session::{HostSession, RemoteClient, TIMEOUT_SECS},
+        },
+    };
+    use super::sync::{SnapshotQuery, capture_snapshot};
+    use crate::AppState;
+    use crate::game::{
+        components::player::{MAX_PLAYERS, Player, PlayerControls, RemoteInput},
+        messages::game_states::GameOver,
+        resources::{
+            countdown::Countdown,
+            level::{LevelLibrary, SelectedLevel},
+            mode::{GameMode, Lives},
+            score::Score,
+        },
+    };
+    use bevy::prelude::*;
+
+    pub fn host_receive(
+        mut session: ResMut<HostSession>,
+        mut lobby_status: ResMut<LobbyStatus>,
+        app_state: Res<State<AppState>>,
+        game_mode: Res<GameMode>,
+        level_library: Res<LevelLibrary>,
+        selected_level: Res<SelectedLevel>,
+        time: Res<Time>,
+    ) {
+        let now = time.elapsed_secs();
+
+        while let Some((address, packet)) = session.receive() {
+            let known = session
+                .clients
+                .iter()
+                .position(|client| client.address == address);
+
+            match (packet, known) {
+                (ClientPacket::Join, Some(index)) => {
+                    let player_index = session.clients[index].player_index;
+                    session.clients[index].last_heard = now;
+                    session.send(address, &ServerPacket::Welcome { player_index });
+                }
+                (ClientPacket::Join, None) => {
+                    let player_index = (1..MAX_PLAYERS).find(|index| {
+                        !session
+                            .clients
+                            .iter()
+                            .any(|client| client.player_index == *index)
+                    });
+
+                    let Some(player_index) = player_index else {
+                        session.send(address, &ServerPacket::Full);
+                        continue;
+                    };
+
+                    session.clients.push(RemoteClient {
+                        address,
+                        player_index,
+                        last_heard: now,
+                        input: Vec2::ZERO,
+                    });
+                    session.send(address, &ServerPacket::Welcome { player_index });
+                    lobby_status.text =
+                        format!("Player {} joined from {address}", player_index + 1);
+
+                    if *app_state.get() == AppState::Game {
+                        session.send(
+                            address,
+                            &ServerPacket::Start {
+                                mode: *game_mode,
+                                level: level_library.get(&selected_level).clone(),
+                            },
+                        );
+                    }
+                }
+                (ClientPacket::Input { direction }, Some(index)) => {
+                    let client = &mut session.clients[index];
+                    client.last_heard = now;
+                    client.input = Vec2::from(direction).clamp_length_max(1.0);
+                }
+                (ClientPacket::Heartbeat, Some(index)) => {
+                    session.clients[index].last_heard = now;
+                }
+                (ClientPacket::Leave, Some(index)) => {
+                    let client = session.clients.remove(index);
+                    lobby_status.text = format!("Player {} left", client.player_index + 1);
+                }
+                _ => {}
+            }
+        }
+
+        let before = session.clients.len();
+        session
+            .clients
+            .retain(|client| now - client.last_heard < TIMEOUT_SECS);
+        if session.clients.len() < before {
+            lobby_status.text = "A player timed out".to_string();
+        }
+    }
+
+    pub fn announce_start(
+        session: Res<HostSession>,
+        game_mode: Res<GameMode>,
+        level_library: Res<LevelLibrary>,
+        selected_level: Res<SelectedLevel>,
+    ) {
+        session.broadcast(&ServerPacket::Start {
+            mode: *game_mode,
+            level: level_library.get(&selected_level).clone(),
+        });
+    }
+
+    /// Hands remote players over to network input; the host keeps the first player.
+    pub fn prepare_players(
+        mut commands: Commands,
+        player_query: Query<(Entity, &Player), Added<Player>>,
+    ) {
+        for (entity, player) in &player_query {
+            if player.index == 0 {
+                commands
+                    .entity(entity)
+                    .insert(PlayerControls::for_player(0, 1));
+            } else {
+                commands.entity(entity).insert(RemoteInput::default());
+            }
+        }
+    }
+
+    pub fn apply_remote_input(
+        session: Res<HostSession>,
+        player_query: Query<(&Player, &mut RemoteInput)>,
+    ) {
+        for (player, mut remote_input) in player_query {
+            remote_input.direction = session
+                .clients
+                .iter()
+                .find(|client| client.player_index == player.index)
+                .map(|client| client.input)
+                .unwrap_or(Vec2::ZERO);
+        }
+    }
+
+    pub fn send_snapshots(
+        mut session: ResMut<HostSession>,
+        entity_query: SnapshotQuery,
+        score: Res<Score>,
+        lives: Res<Lives>,
+        countdown: Option<Res<Countdown>>,
+        time: Res<Time>,
+    ) {
+        if !session.snapshot_timer.tick(time.delta()).just_finished() || session.clients.is_empty()
+        {
+            return;
+        }
+
+        let snapshot = capture_snapshot(
+            &entity_query,
+            &score,
+            &lives,
+            countdown.as_deref(),
+            time.elapsed_secs(),
+        );
+        for part in snapshot.split() {
+            session.broadcast(&ServerPacket::Snapshot(part));
+        }
+    }
+
+    /// Lets clients know the host is still there outside of a run too.
+    pub fn send_heartbeats(mut session: ResMut<HostSession>, time: Res<Time>) {
+        if session.heartbeat_timer.tick(time.delta()).just_finished() {
+            session.broadcast(&ServerPacket::Heartbeat);
+        }
+    }
+
+    pub fn send_game_over(session: Res<HostSession>, mut gameover_reader: MessageReader<GameOver>) {
+        for game_over in gameover_reader.read() {
+            session.broadcast(&ServerPacket::GameOver {
+                score: game_over.score,
+                results: game_over.results.clone(),
+            });
+        }
+    }
+}
+
+pub mod client {
+    use super::super::{
+        components::sync::NetId,
+        protocol::{ClientPacket, ServerPacket},
+        resources::{
+            lobby::LobbyStatus,
+            session::{ClientSession, TIMEOUT_SECS},
+        },
+    };
+    use super::sync::sync_proxies;
+    use crate::loading::resources::GameAssets;
+    use crate::{
+        AppState,
+        game::{
+            components::player::PlayerControls,
+            messages::game_states::GameOver,
+            resources::{
+                countdown::Countdown,
+                level::{LevelLibrary, SelectedLevel},
+                mode::{GameMode, Lives},
+                score::Score,
+            },
+            states::SimulationState,
+        },
+        transition::messages::{TransitionEffect, TransitionRequest},
+    };
+    use bevy::prelude::*;
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn client_receive(
+        mut session: ResMut<ClientSession>,
+        mut lobby_status: ResMut<LobbyStatus>,
+        mut gameover_writer: MessageWriter<GameOver>,
+        mut level_library: ResMut<LevelLibrary>,
+        mut selected_level: ResMut<SelectedLevel>,
+        mut game_mode: ResMut<GameMode>,
+        mut transition_writer: MessageWriter<TransitionRequest>,
+        time: Res<Time>,
+    ) {
+        let now = time.elapsed_secs();
+
+        while let Some(packet) = session.receive() {
+            session.last_heard = now;
+
+            match packet {
+                ServerPacket::Welcome { player_index } => {
+                    if session.player_index.is_none() {
+                        lobby_status.text = format!(
+                            "Joined as Player {}, waiting for the host to start",
+                            player_index + 1
+                        );
+                    }
+                    session.player_index = Some(player_index);
+                }
+                ServerPacket::Full => {
+                    lobby_status.text = "The match is full".to_string();
+                }
+                ServerPacket::Start { mode, level } => {
+                    selected_level.index = level_library.find_or_add(level);
+                    *game_mode = mode;
+                    session.snapshots.clear();
+                    transition_writer.write(TransitionRequest::new(
+                        AppState::Game,
+                        TransitionEffect::Wipe,
+                    ));
+                }
+                ServerPacket::Snapshot(snapshot) => session.snapshots.push(snapshot, now),
+                ServerPacket::GameOver { score, results } => {
+                    gameover_writer.write(GameOver { score, results });
+                }
+                ServerPacket::Heartbeat => {}
+            }
+        }
+    }
+
+    pub fn keep_connection(
+        mut session: ResMut<ClientSession>,
+        mut lobby_status: ResMut<LobbyStatus>,
+        mut transition_writer: MessageWriter<TransitionRequest>,
+        time: Res<Time>,
+    ) {
+        if session.player_index.is_none() {
+            if session.join_timer.tick(time.delta()).just_finished() {
+                session.send(&ClientPacket::Join);
+            }
+        } else if session.heartbeat_timer.tick(time.delta()).just_finished() {
+            session.send(&ClientPacket::Heartbeat);
+        }
+
+        if time.elapsed_secs() - session.last_heard > TIMEOUT_SECS {
+            lobby_status.text = if session.player_index.is_some() {
+                "Connection to the host lost".to_string()
+            } else {
+                "No answer from the host".to_string()
+            };
+            session.player_index = None;
+            session.last_heard = time.elapsed_secs();
+            transition_writer.write(TransitionRequest::new(
+                AppState::Lobby,
+                TransitionEffect::Fade,
+            ));
+        }
+    }
+
+    pub fn send_input(
+        session: Res<ClientSession>,
+        keyboard_input: Res<ButtonInput<KeyCode>>,
+        gamepad_query: Query<&Gamepad>,
+    ) {
+        let gamepads: Vec<&Gamepad> = gamepad_query.iter().collect();
+        let direction = PlayerControls::for_player(0, 1).direction(&keyboard_input, &gamepads);
+
+        session.send(&ClientPacket::Input {
+            direction: direction.into(),
+        });
+    }
+
+    pub fn apply_snapshots(
+        mut commands: Commands,
+        mut session: ResMut<ClientSession>,
+        mut proxy_query: Query<(Entity, &NetId, &mut Transform)>,
+        game_assets: Res<GameAssets>,
+        mut score: ResMut<Score>,
+        mut lives: ResMut<Lives>,
+        time: Res<Time>,
+    ) {
+        sync_proxies(
+            &mut commands,
+            &mut session.snapshots,
+            &mut proxy_query,
+            &game_assets,
+            &mut score,
+            &mut lives,
+            time.elapsed_secs(),
+        );
+    }
+
+    /// Shows the host's start countdown, and starts the run when the host does.
+    pub fn follow_host_countdown(
+        session: Res<ClientSession>,
+        mut countdown: ResMut<Countdown>,
+        mut change_state: ResMut<NextState<SimulationState>>,
+    ) {
+        let Some(snapshot) = session.snapshots.snapshots.back() else {
+            return;
+        };
+
+        if countdown.remaining != snapshot.countdown {
+            countdown.remaining = snapshot.countdown;
+        }
+        if snapshot.countdown == 0 {
+            change_state.set(SimulationState::GameRunning);
+        }
+    }
+}
+
+pub mod rollback {
+    use super::super::{
+        components::rollback::RollbackId,
+        protocol::PeerPacket,
+        resources::{
+            lobby::LobbyStatus,
+            rollback::{
+                COUNTDOWN_FRAMES, MAX_INPUTS_PER_PACKET, MAX_PREDICTION_FRAMES, ROLLBACK_STEP,
+                RollbackSession, SavedEntity, SavedKind, WorldSnapshot,
+            },
+            session::TIMEOUT_SECS,
+        },
+    };
+    use crate::loading::resources::GameAssets;
+    use crate::{
+        AppState,
+        animation::{
+            components::{DespawnWhenFinished, SpriteAnimation},
+            resources::{ClipKind, SheetKind},
+        },
+        audio::components::mixer::SoundEffect,
+        camera_effects::components::CameraCue,
+        game::{
+            components::{
+                enemy::{Enemy, Telegraphing},
+                player::{
+                    Boosted, Invulnerable, MAX_PLAYERS, Player, PlayerControls, RemoteInput,
+                    Velocity,
+                },
+                star::Star,
+            },
+            messages::game_states::GameOver,
+            resources::{
+                countdown::{COUNTDOWN_FROM, Countdown},
+                level::{LevelLibrary, SelectedLevel},
+                mode::{GameMode, Lives},
+                network::NetworkRole,
+                random::{GameRng, MatchSeed},
+                score::Score,
+                timers::{EnemySpawnTimer, StarSpawnTimer},
+            },
+            schedules::GameplayStep,
+            states::SimulationState,
+        },
+        particles::components::ParticleBurst,
+        transition::messages::{TransitionEffect, TransitionRequest},
+    };
+    use bevy::{ecs::message::Messages, prelude::*};
+    use std::{
+        collections::{HashMap, HashSet},
+        time::Duration,
+    };
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn rollback_receive(
+        mut commands: Commands,
+        mut session: ResMut<RollbackSession>,
+        mut network_role: ResMut<NetworkRole>,
+        mut lobby_status: ResMut<LobbyStatus>,
+        mut level_library: ResMut<LevelLibrary>,
+        mut selected_level: ResMut<SelectedLevel>,
+        mut game_mode: ResMut<GameMode>,
+        mut match_seed: ResMut<MatchSeed>,
+        mut transition_writer: MessageWriter<TransitionRequest>,
+        time: Res<Time>,
+    ) {
+        while let Some(packet) = session.receive() {
+            session.last_heard = time.elapsed_secs();
+
+            match packet {
+                PeerPacket::Hello { nonce, level } => {
+                    if session.local_player.is_some() || nonce == session.nonce {
+                        continue;
+                    }
+
+                    // Both peers derive the same seed, player order and level from the
+                    // two hellos, so nothing else has to be negotiated.
+                    let local_player = if session.nonce > nonce { 0 } else { 1 };
+                    let level = if local_player == 0 {
+                        session.level.clone()
+                    } else {
+                        level
+                    };
+
+                    session.local_player = Some(local_player);
+                    match_seed.value = Some(session.nonce ^ nonce);
+                    selected_level.index = level_library.find_or_add(level);
+                    *game_mode = GameMode::Versus;
+                    lobby_status.text = format!("Playing as Player {}", local_player + 1);
+                    transition_writer.write(TransitionRequest::new(
+                        AppState::Game,
+                        TransitionEffect::Wipe,
+                    ));
+                }
+                PeerPacket::Input {
+                    start,
+                    directions,
+                    ack,
+                } => {
+                    session.peer_started = true;
+                    session.peer_ack = session.peer_ack.max(ack);
+                    for (frame, direction) in (start..).zip(directions) {
+                        session.record_remote_input(frame, direction.into());
+                    }
+                }
+                PeerPacket::Leave => {
+                    if !session.finished {
+                        lobby_status.text = "The other player left".to_string();
+                        transition_writer.write(TransitionRequest::new(
+                            AppState::Lobby,
+                            TransitionEffect::Fade,
+                        ));
+                        *network_role = NetworkRole::Offline;
+                    }
+                    commands.remove_resource::<RollbackSession>();
+                    return;
+                }
+            }
+        }
+    }
+
+    pub fn rollback_send(mut session: ResMut<RollbackSession>, time: Res<Time>) {
+        let now = time.elapsed_secs();
+
+        if !session.peer_started && session.hello_timer.tick(time.delta()).just_finished() {
+            let hello = PeerPacket::Hello {
+                nonce: session.nonce,
+                level: session.level.clone(),
+            };
+            session.send(&hello, now);
+        }
+
+        if session.local_player.is_some() {
+            // Resend everything the peer hasn't confirmed yet, so a lost packet only
+            // delays an input instead of losing it.
+            let end = session.local_inputs.len() as u32;
+            let start = session
+                .peer_ack
+                .max(end.saturating_sub(MAX_INPUTS_PER_PACKET))
+                .min(end);
+            let input = PeerPacket::Input {
+                start,
+                directions: session.local_inputs[start as usize..]
+                    .iter()
+                    .map(|direction| (*direction).into())
+                    .collect(),
+                ack: session.confirmed,
+            };
+            session.send(&input, now);
+        }
+
+        session.flush(now);
+    }
+
+    pub fn check_rollback_peer(
+        mut commands: Commands,
+        session: Res<RollbackSession>,
+        mut network_role: ResMut<NetworkRole>,
+        mut lobby_status: ResMut<LobbyStatus>,
+        mut transition_writer: MessageWriter<TransitionRequest>,
+        time: Res<Time>,
+    ) {
+        if session.local_player.is_some()
+            && !session.finished
+            && time.elapsed_secs() - session.last_heard > TIMEOUT_SECS
+        {
+            lobby_status.text = "Connection to the other player lost".to_string();
+            commands.remove_resource::<RollbackSession>();
+            *network_role = NetworkRole::Offline;
+            transition_writer.write(TransitionRequest::new(
+                AppState::Lobby,
+                TransitionEffect::Fade,
+            ));
+        }
+    }
+
+    pub fn sample_local_input(
+        mut session: ResMut<RollbackSession>,
+        keyboard_input: Res<ButtonInput<KeyCode>>,
+        gamepad_query: Query<&Gamepad>,
+    ) {
+        let gamepads: Vec<&Gamepad> = gamepad_query.iter().collect();
+        session.local_direction =
+            PlayerControls::for_player(0, 1).direction(&keyboard_input, &gamepads);
+    }
+
+    /// The match is over for the network; the game over screen and whatever is
+    /// started from it are played offline. The session stays around so the peer
+    /// still receives our last inputs.
+    pub fn finish_rollback_match(
+        mut session: ResMut<RollbackSession>,
+        mut network_role: ResMut<NetworkRole>,
+    ) {
+        session.finished = true;
+        *network_role = NetworkRole::Offline;
+    }
+
+    /// Steps the simulation at a fixed rate with local input and predicted remote
+    /// input, first rewinding and re-simulating from the oldest frame whose
+    /// prediction turned out wrong.
+    pub fn advance_rollback(world: &mut World) {
+        let delta = world.resource::<Time>().delta_secs();
+        let frame_time = *world.resource::<Time>();
+
+        world.resource_scope(|world, mut session: Mut<RollbackSession>| {
+            let Some(local_player) = session.local_player else {
+                return;
+            };
+            if session.finished {
+                return;
+            }
+
+            if let Some(from) = session.rollback_to.take()
+                && from < session.frame
+            {
+                let snapshot = session.snapshots.iter().position(|s| s.frame == from);
+                match snapshot {
+                    Some(index) => {
+                        restore_world(world, &session.snapshots[index]);
+                        session.clock = session.snapshots[index].clock;
+                        session.snapshots.truncate(index);
+                        if session
+                            .pending_game_over
+                            .as_ref()
+                            .is_some_and(|(frame, _)| *frame >= from)
+                        {
+                            session.pending_game_over = None;
+                        }
+
+                        // Sounds, particles, camera cues and despawn animations were
+                        // already played the first time these frames ran.
+                        let effects = effect_entities(world);
+                        for frame in from..session.frame {
+                            simulate_frame(world, &mut session, frame, local_player);
+                        }
+                        for entity in effect_entities(world).difference(&effects) {
+                            world.despawn(*entity);
+                        }
+                    }
+                    None => error!("No snapshot left for frame {from}, the peers may desync"),
+                }
+            }
+
+            // Both peers count the same frames down, so the run starts on a shared tick.
+            let counting_down =
+                *world.resource::<State<SimulationState>>().get() == SimulationState::Countdown;
+
+            session.accumulator =
+                (session.accumulator + delta).min(ROLLBACK_STEP * MAX_PREDICTION_FRAMES as f32);
+            while session.accumulator >= ROLLBACK_STEP
+                && session.frame < session.confirmed + MAX_PREDICTION_FRAMES
+                && session.pending_game_over.is_none()
+                && !(counting_down && session.frame >= COUNTDOWN_FRAMES)
+            {
+                session.accumulator -= ROLLBACK_STEP;
+                let frame = session.frame;
+                let direction = session.local_direction;
+                session.local_inputs.push(direction);
+                simulate_frame(world, &mut session, frame, local_player);
+                session.frame += 1;
+            }
+
+            if counting_down {
+                let frames_left = COUNTDOWN_FRAMES.saturating_sub(session.frame);
+                let remaining = frames_left.div_ceil(COUNTDOWN_FRAMES / COUNTDOWN_FROM);
+                if let Some(mut countdown) = world.get_resource_mut::<Countdown>()
+                    && countdown.remaining != remaining
+                {
+                    countdown.remaining = remaining;
+                }
+                if frames_left == 0 {
+                    world
+                        .resource_mut::<NextState<SimulationState>>()
+                        .set(SimulationState::GameRunning);
+                }
+            }
+
+            // A mispredicted frame is never older than the oldest unconfirmed one.
+            let oldest = session.confirmed.min(session.frame);
+            session
+                .snapshots
+                .retain(|snapshot| snapshot.frame >= oldest);
+
+            // Only end the match once every input leading up to it is confirmed, so
+            // both peers agree on the result.
+            if session
+                .pending_game_over
+                .as_ref()
+                .is_some_and(|(frame, _)| *frame < session.confirmed)
+                && let Some((_, game_over)) = session.pending_game_over.take()
+            {
+                session.finished = true;
+                world.write_message(game_over);
+            }
+        });
+
+        *world.resource_mut::<Time>() = frame_time;
+    }
+
+    fn simulate_frame(
+        world: &mut World,
+        session: &mut RollbackSession,
+        frame: u32,
+        local_player: usize,
+    ) {
+        let snapshot = save_world(world, &mut session.next_id, session.clock, frame);
+        session.snapshots.push_back(snapshot);
+
+        let remote = session.remote_input(frame);
+        let index = frame as usize;
+        if index < session.predicted.len() {
+            session.predicted[index] = remote;
+        } else {
+            session.predicted.push(remote);
+        }
+        let local = session.local_inputs[index];
+
+        let players: Vec<(Entity, usize)> = world
+            .query::<(Entity, &Player)>()
+            .iter(world)
+            .map(|(entity, player)| (entity, player.index))
+            .collect();
+        for (entity, player_index) in players {
+            let direction = if player_index == local_player {
+                local
+            } else {
+                remote
+            };
+            world.entity_mut(entity).insert(RemoteInput { direction });
+        }
+
+        // Countdown frames only exchange input.
+        if frame < COUNTDOWN_FRAMES {
+            return;
+        }
+
+        session
+            .clock
+            .advance_by(Duration::from_secs_f32(ROLLBACK_STEP));
+        *world.resource_mut::<Time>() = session.clock;
+        world.run_schedule(GameplayStep);
+
+        // Held back until confirmed, see `advance_rollback`.
+        let game_over = world.resource_mut::<Messages<GameOver>>().drain().next();
+        if session.pending_game_over.is_none()
+            && let Some(game_over) = game_over
+        {
+            session.pending_game_over = Some((frame, game_over));
+        }
+    }
+
+    fn effect_entities(world: &mut World) -> HashSet<Entity> {
+        world
+            .query_filtered::<Entity, Or<(
+                With<SoundEffect>,
+                With<ParticleBurst>,
+                With<CameraCue>,
+                With<DespawnWhenFinished>,
+            )>>()
+            .iter(world)
+            .collect()
+    }
+
+    fn save_world(world: &mut World, next_id: &mut u32, clock: Time, frame: u32) -> WorldSnapshot {
+        let untagged: Vec<Entity> = world
+            .query_filtered::<Entity, (
+                Or<(With<Player>, With<Enemy>, With<Star>)>,
+                Without<RollbackId>,
+            )>()
+            .iter(world)
+            .collect();
+        for entity in untagged {
+            world.entity_mut(entity).insert(RollbackId(*next_id));
+            *next_id += 1;
+        }
+
+        let entities = world
+            .query::<(
+                &RollbackId,
+                &Transform,
+                Option<&Player>,
+                Option<&Velocity>,
+                Option<&Invulnerable>,
+                Option<&Boosted>,
+                Option<&Enemy>,
+                Option<&Telegraphing>,
+            )>()
+            .iter(world)
+            .map(
+                |(id, transform, player, velocity, invulnerable, boosted, enemy, telegraphing)| {
+                    let kind = match (player, enemy) {
+                        (Some(player), _) => SavedKind::Player {
+                            index: player.index,
+                            velocity: velocity.copied().unwrap_or_default(),
+                            invulnerable: invulnerable.cloned(),
+                            boosted: boosted.cloned(),
+                        },
+                        (_, Some(enemy)) => SavedKind::Enemy {
+                            enemy: *enemy,
+                            telegraphing: telegraphing.cloned(),
+                        },
+                        _ => SavedKind::Star,
+                    };
+
+                    SavedEntity {
+                        id: *id,
+                        kind,
+                        transform: *transform,
+                    }
+                },
+            )
+            .collect();
+
+        WorldSnapshot {
+            frame,
+            entities,
+            score: world.resource::<Score>().clone(),
+            lives: world.resource::<Lives>().clone(),
+            star_timer: world.resource::<StarSpawnTimer>().timer.clone(),
+            enemy_timer: world.resource::<EnemySpawnTimer>().timer.clone(),
+            rng: world.resource::<GameRng>().clone(),
+            clock,
+        }
+    }
+
+    /// Writes the saved state back onto the entities that still exist, so their
+    /// trails and animations carry on, and only spawns or despawns the difference.
+    fn restore_world(world: &mut World, snapshot: &WorldSnapshot) {
+        let saved: HashMap<RollbackId, &SavedEntity> = snapshot
+            .entities
+            .iter()
+            .map(|saved| (saved.id, saved))
+            .collect();
+
+        let current: Vec<(Entity, RollbackId)> = world
+            .query::<(Entity, &RollbackId)>()
+            .iter(world)
+            .map(|(entity, id)| (entity, *id))
+            .collect();
+        let mut restored = HashSet::new();
+        for (entity, id) in current {
+            let Some(saved) = saved.get(&id) else {
+                world.despawn(entity);
+                continue;
+            };
+
+            let mut entity = world.entity_mut(entity);
+            entity.insert(saved.transform);
+            match &saved.kind {
+                SavedKind::Player {
+                    velocity,
+                    invulnerable,
+                    boosted,
+                    ..
+                } => {
+                    entity.insert(*velocity);
+                    match invulnerable {
+                        Some(invulnerable) => entity.insert(invulnerable.clone()),
+                        None => entity.remove::<Invulnerable>(),
+                    };
+                    match boosted {
+                        Some(boosted) => entity.insert(boosted.clone()),
+                        None => entity.remove::<Boosted>(),
+                    };
+                }
+                SavedKind::Enemy {
+                    enemy,
+                    telegraphing,
+                } => {
+                    entity.insert(*enemy);
+                    match telegraphing {
+                        Some(telegraphing) => entity.insert(telegraphing.clone()),
+                        None => entity.remove::<Telegraphing>(),
+                    };
+                }
+                SavedKind::Star => {}
+            }
+            restored.insert(id);
+        }
+
+        let sprites = &world.resource::<GameAssets>().sprites;
+        let (balls, ball_red, star) = (
+            std::array::from_fn::<_, MAX_PLAYERS, _>(|index| {
+                SheetKind::player(index).image(sprites)
+            }),
+            SheetKind::Enemy.image(sprites),
+            SheetKind::Star.image(sprites),
+        );
+        let player_count = world.resource::<GameMode>().player_count();
+
+        for saved in &snapshot.entities {
+            if restored.contains(&saved.id) {
+                continue;
+            }
+
+            match &saved.kind {
+                SavedKind::Player {
+                    index,
+                    velocity,
+                    invulnerable,
+                    boosted,
+                } => {
+                    let mut player = world.spawn((
+                        saved.id,
+                        Player { index: *index },
+                        PlayerControls::for_player(*index, player_count),
+                        *velocity,
+                        Sprite::from_image(balls[*index].clone()),
+                        SpriteAnimation::new(SheetKind::player(*index), ClipKind::Idle),
+                        saved.transform,
+                    ));
+                    if let Some(invulnerable) = invulnerable {
+                        player.insert(invulnerable.clone());
+                    }
+                    if let Some(boosted) = boosted {
+                        player.insert(boosted.clone());
+                    }
+                }
+                SavedKind::Enemy {
+                    enemy,
+                    telegraphing,
+                } => {
+                    let mut spawned = world.spawn((
+                        saved.id,
+                        *enemy,
+                        Sprite {
+                            image: ball_red.clone(),
+                            custom_size: Some(Vec2::splat(enemy.size)),
+                            ..Default::default()
+                        },
+                        SpriteAnimation::new(SheetKind::Enemy, ClipKind::Idle),
+                        saved.transform,
+                    ));
+                    if let Some(telegraphing) = telegraphing {
+                        spawned.insert(telegraphing.clone());
+                    }
+                }
+                SavedKind::Star => {
+                    world.spawn((
+                        saved.id,
+                        Star {},
+                        Sprite::from_image(star.clone()),
+                        SpriteAnimation::new(SheetKind::Star, ClipKind::Idle),
+                        saved.transform,
+                    ));
+                }
+            }
+        }
+
+        world.insert_resource(snapshot.score.clone());
+        world.insert_resource(snapshot.lives.clone());
+        world.resource_mut::<StarSpawnTimer>().timer = snapshot.star_timer.clone();
+        world.resource_mut::<EnemySpawnTimer>().timer = snapshot.enemy_timer.clone();
+        world.insert_resource(snapshot.rng.clone());
+    }
+}
+
+pub mod spectator {
+    use super::super::{
+        components::{spectator::SpectatorHint, sync::NetId},
+        protocol::SpectatorPacket,
+        resources::{
+            lobby::LobbyStatus,
+            spectator::{
+                CameraFocus, FOCUS_SMOOTHING, FREE_CAMERA_SPEED, MAX_ZOOM, MIN_ZOOM,
+                SpectatorServer, SpectatorSession, ZOOM_STEP, broadcast_port_from_args,
+            },
+        },
+    };
+    use super::sync::{SnapshotQuery, capture_snapshot, sync_proxies};
+    use crate::loading::resources::GameAssets;
+    use crate::widgets::components::Themed;
+    use crate::{
+        AppState,
+        game::{
+            components::player::Player,
+            messages::game_states::GameOver,
+            resources::{
+                countdown::Countdown,
+                level::{LevelLibrary, SelectedLevel},
+                mode::{GameMode, Lives},
+                network::NetworkRole,
+                score::Score,
+            },
+        },
+        transition::messages::{TransitionEffect, TransitionRequest},
+    };
+    use bevy::{input::mouse::AccumulatedMouseScroll, prelude::*};
+
+    pub fn start_broadcast(mut commands: Commands) {
+        let Some(port) = broadcast_port_from_args() else {
+            return;
+        };
+
+        match SpectatorServer::bind(port) {
+            Ok(server) => {
+                info!("Broadcasting to spectators on port {port}");
+                commands.insert_resource(server);
+            }
+            Err(err) => error!("Could not broadcast on port {port}: {err}"),
+        }
+    }
+
+    /// Late joiners get the current run and a full snapshot straight away.
+    #[allow(clippy::too_many_arguments)]
+    pub fn accept_spectators(
+        mut server: ResMut<SpectatorServer>,
+        entity_query: SnapshotQuery,
+        app_state: Res<State<AppState>>,
+        game_mode: Res<GameMode>,
+        level_library: Res<LevelLibrary>,
+        selected_level: Res<SelectedLevel>,
+        score: Option<Res<Score>>,
+        lives: Res<Lives>,
+        countdown: Option<Res<Countdown>>,
+        time: Res<Time>,
+    ) {
+        let accepted = server.accept();
+
+        if *app_state.get() == AppState::Game
+            && let Some(score) = score
+        {
+            let start = SpectatorPacket::Start {
+                mode: *game_mode,
+                level: level_library.get(&selected_level).clone(),
+            };
+            let full = SpectatorPacket::Full(capture_snapshot(
+                &entity_query,
+                &score,
+                &lives,
+                countdown.as_deref(),
+                time.elapsed_secs(),
+            ));
+
+            for index in accepted {
+                server.send(index, &start);
+                server.send(index, &full);
+            }
+        }
+
+        server.flush();
+    }
+
+    pub fn announce_run_to_spectators(
+        mut server: ResMut<SpectatorServer>,
+        game_mode: Res<GameMode>,
+        level_library: Res<LevelLibrary>,
+        selected_level: Res<SelectedLevel>,
+    ) {
+        server.last_sent = None;
+        server.broadcast(&SpectatorPacket::Start {
+            mode: *game_mode,
+            level: level_library.get(&selected_level).clone(),
+        });
+    }
+
+    pub fn stream_to_spectators(
+        mut server: ResMut<SpectatorServer>,
+        entity_query: SnapshotQuery,
+        score: Res<Score>,
+        lives: Res<Lives>,
+        countdown: Option<Res<Countdown>>,
+        time: Res<Time>,
+    ) {
+        if !server.snapshot_timer.tick(time.delta()).just_finished() {
+            return;
+        }
+
+        let snapshot = capture_snapshot(
+            &entity_query,
+            &score,
+            &lives,
+            countdown.as_deref(),
+            time.elapsed_secs(),
+        );
+        let packet = match &server.last_sent {
+            Some(previous) => SpectatorPacket::Delta(snapshot.delta_from(previous)),
+            None => SpectatorPacket::Full(snapshot.clone()),
+        };
+        server.broadcast(&packet);
+        server.last_sent = Some(snapshot);
+    }
+
+    pub fn send_game_over_to_spectators(
+        mut server: ResMut<SpectatorServer>,
+        mut gameover_reader: MessageReader<GameOver>,
+    ) {
+        for game_over in gameover_reader.read() {
+            server.broadcast(&SpectatorPacket::GameOver {
+                score: game_over.score,
+                results: game_over.results.clone(),
+            });
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn spectator_receive(
+        mut commands: Commands,
+        mut session: ResMut<SpectatorSession>,
+        mut network_role: ResMut<NetworkRole>,
+        mut lobby_status: ResMut<LobbyStatus>,
+        mut gameover_writer: MessageWriter<GameOver>,
+        mut level_library: ResMut<LevelLibrary>,
+        mut selected_level: ResMut<SelectedLevel>,
+        mut game_mode: ResMut<GameMode>,
+        mut transition_writer: MessageWriter<TransitionRequest>,
+        time: Res<Time>,
+    ) {
+        let now = time.elapsed_secs();
+
+        let packets = match session.receive() {
+            Ok(packets) => packets,
+            Err(err) => {
+                lobby_status.text = format!("The broadcast from {} ended: {err}", session.address);
+                commands.remove_resource::<SpectatorSession>();
+                *network_role = NetworkRole::Offline;
+                transition_writer.write(TransitionRequest::new(
+                    AppState::Lobby,
+                    TransitionEffect::Fade,
+                ));
+                return;
+            }
+        };
+
+        for packet in packets {
+            match packet {
+                SpectatorPacket::Start { mode, level } => {
+                    selected_level.index = level_library.find_or_add(level);
+                    *game_mode = mode;
+                    session.world = None;
+                    session.snapshots.clear();
+                    transition_writer.write(TransitionRequest::new(
+                        AppState::Game,
+                        TransitionEffect::Wipe,
+                    ));
+                }
+                SpectatorPacket::Full(snapshot) => {
+                    session.snapshots.push(snapshot.clone(), now);
+                    session.world = Some(snapshot);
+                }
+                SpectatorPacket::Delta(delta) => {
+                    // Deltas are useless until the full snapshot they build on arrived.
+                    let Some(world) = session.world.as_mut() else {
+                        continue;
+                    };
+                    world.apply(&delta);
+                    let snapshot = world.clone();
+                    session.snapshots.push(snapshot, now);
+                }
+                SpectatorPacket::GameOver { score, results } => {
+                    gameover_writer.write(GameOver { score, results });
+                }
+            }
+        }
+    }
+
+    pub fn apply_spectator_snapshots(
+        mut commands: Commands,
+        mut session: ResMut<SpectatorSession>,
+        mut proxy_query: Query<(Entity, &NetId, &mut Transform)>,
+        game_assets: Res<GameAssets>,
+        mut score: ResMut<Score>,
+        mut lives: ResMut<Lives>,
+        time: Res<Time>,
+    ) {
+        sync_proxies(
+            &mut commands,
+            &mut session.snapshots,
+            &mut proxy_query,
+            &game_assets,
+            &mut score,
+            &mut lives,
+            time.elapsed_secs(),
+        );
+    }
+
+    /// `F` cycles between the free camera and following each player; moving the
+    /// camera by hand switches back to free.
+    pub fn spectator_camera(
+        mut camera_query: Query<(&mut Transform, &mut Projection), With<Camera2d>>,
+        player_query: Query<(&Transform, &Player), Without<Camera2d>>,
+        mut session: ResMut<SpectatorSession>,
+        keyboard_input: Res<ButtonInput<KeyCode>>,
+        mouse_scroll: Res<AccumulatedMouseScroll>,
+        time: Res<Time>,
+    ) {
+        let Ok((mut camera, mut projection)) = camera_query.single_mut() else {
+            return;
+        };
+
+        let mut players: Vec<usize> = player_query
+            .iter()
+            .map(|(_, player)| player.index)
+            .collect();
+        players.sort();
+
+        if keyboard_input.just_pressed(KeyCode::KeyF) {
+            session.focus = match session.focus {
+                CameraFocus::Free => players
+                    .first()
+                    .copied()
+                    .map_or(CameraFocus::Free, CameraFocus::Player),
+                CameraFocus::Player(current) => players
+                    .iter()
+                    .find(|index| **index > current)
+                    .copied()
+                    .map_or(CameraFocus::Free, CameraFocus::Player),
+            };
+        }
+
+        let mut pan = Vec2::ZERO;
+        if keyboard_input.any_pressed([KeyCode::KeyW, KeyCode::ArrowUp]) {
+            pan.y += 1.0;
+        }
+        if keyboard_input.any_pressed([KeyCode::KeyS, KeyCode::ArrowDown]) {
+            pan.y -= 1.0;
+        }
+        if keyboard_input.any_pressed([KeyCode::KeyA, KeyCode::ArrowLeft]) {
+            pan.x -= 1.0;
+        }
+        if keyboard_input.any_pressed([KeyCode::KeyD, KeyCode::ArrowRight]) {
+            pan.x += 1.0;
+        }
+
+        let mut zoom = 1.0;
+        if let Projection::Orthographic(orthographic) = &mut *projection {
+            orthographic.scale = (orthographic.scale * (1.0 - mouse_scroll.delta.y * ZOOM_STEP))
+                .clamp(MIN_ZOOM, MAX_ZOOM);
+            zoom = orthographic.scale;
+        }
+
+        if pan != Vec2::ZERO {
+            session.focus = CameraFocus::Free;
+            camera.translation +=
+                (pan.normalize() * FREE_CAMERA_SPEED * zoom * time.delta_secs()).extend(0.0);
+        }
+
+        if let CameraFocus::Player(index) = session.focus {
+            match player_query
+                .iter()
+                .find(|(_, player)| player.index == index)
+            {
+                Some((target, _)) => {
+                    let blend = (FOCUS_SMOOTHING * time.delta_secs()).min(1.0);
+                    let position = camera
+                        .translation
+                        .truncate()
+                        .lerp(target.translation.truncate(), blend);
+                    camera.translation = position.extend(camera.translation.z);
+                }
+                // The followed player is out, keep the camera where it is.
+                None => session.focus = CameraFocus::Free,
+            }
+        }
+    }
+
+    pub fn spawn_spectator_hint(mut commands: Commands) {
+        commands.spawn((
+            SpectatorHint,
+            Themed::Caption,
+            Text::new(""),
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(12.0),
+                left: Val::Px(12.0),
+                ..Default::default()
+            },
+        ));
+    }
+
+    pub fn update_spectator_hint(
+        mut hint_query: Query<&mut Text, With<SpectatorHint>>,
+        session: Res<SpectatorSession>,
+    ) {
+        if let Ok(mut text) = hint_query.single_mut() {
+            let focus = match session.focus {
+                CameraFocus::Free => "free camera".to_string(),
+                CameraFocus::Player(index) => format!("following Player {}", index + 1),
+            };
+            text.0 = format!(
+                "Spectating {} - {focus}\nF: follow player  WASD: move  Scroll: zoom",
+                session.address
+            );
+        }
+    }
+
+    pub fn despawn_spectator_hint(
+        mut commands: Commands,
+        hint_query: Query<Entity, With<SpectatorHint>>,
+    ) {
+        for entity in hint_query {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    /// Undoes the spectator's pan and zoom once back in the menus.
+    pub fn reset_camera_zoom(mut projection_query: Query<&mut Projection, With<Camera2d>>) {
+        for mut projection in &mut projection_query {
+            if let Projection::Orthographic(orthographic) = &mut *projection {
+                orthographic.scale = 1.0;
+            }
+        }
+    }
+}
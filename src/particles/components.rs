@@ -0,0 +1,42 @@
+use super::resources::EmitterKind;
+use bevy::prelude::*;
+
+/// Spawn this to emit a burst. It is consumed the next time particles are emitted.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ParticleBurst {
+    pub kind: EmitterKind,
+    pub position: Vec2,
+    /// Centre of the emitter's spread; `None` for emitters that spray all around.
+    pub direction: Option<Vec2>,
+}
+
+impl ParticleBurst {
+    pub fn new(kind: EmitterKind, position: Vec2) -> Self {
+        Self {
+            kind,
+            position,
+            direction: None,
+        }
+    }
+
+    pub fn directed(kind: EmitterKind, position: Vec2, direction: Vec2) -> Self {
+        Self {
+            kind,
+            position,
+            direction: Some(direction),
+        }
+    }
+}
+
+/// A pooled sprite. Hidden while it sits in `ParticlePool::free`.
+#[derive(Component, Default)]
+pub struct Particle {
+    pub velocity: Vec2,
+    pub age: f32,
+    pub lifetime: f32,
+    pub start_size: f32,
+    pub end_size: f32,
+    pub start_color: LinearRgba,
+    pub end_color: LinearRgba,
+    pub drag: f32,
+}
@@ -0,0 +1,16 @@
+use bevy::prelude::*;
+use plugins::*;
+
+pub mod components;
+mod plugins;
+pub mod resources;
+mod systems;
+
+/// Short-lived sprites for visual feedback, emitted from the bursts described in
+/// `particles.ron`.
+pub struct ParticlesPlugin;
+impl Plugin for ParticlesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(EmitterPlugin);
+    }
+}
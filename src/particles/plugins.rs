@@ -0,0 +1,22 @@
+use super::{
+    resources::{ParticleEmitters, ParticlePool},
+    systems::*,
+};
+use crate::{AppState, game::states::SimulationState};
+use bevy::prelude::*;
+
+pub struct EmitterPlugin;
+impl Plugin for EmitterPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ParticleEmitters::load())
+            .init_resource::<ParticlePool>()
+            .add_systems(Startup, spawn_particle_pool)
+            .add_systems(
+                Update,
+                (emit_particles, update_particles)
+                    .chain()
+                    .run_if(not(in_state(SimulationState::GamePaused))),
+            )
+            .add_systems(OnExit(AppState::Game), clear_particles);
+    }
+}
@@ -0,0 +1,60 @@
+use bevy::{asset::io::file::FileAssetReader, prelude::*};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs, path::PathBuf};
+
+pub const EMITTERS_FILE: &str = "particles.ron";
+/// Particles that can be alive at once; bursts beyond it are cut short.
+pub const POOL_SIZE: usize = 512;
+pub const PARTICLE_Z: f32 = 5.0;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EmitterKind {
+    StarSparkle,
+    WallSpark,
+    PlayerDebris,
+}
+
+/// How one burst looks. Pairs are random ranges, or start and end values over a
+/// particle's life.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct EmitterDef {
+    pub count: u32,
+    /// Angle in degrees the particles are spread over, around the burst's direction.
+    pub spread: f32,
+    pub speed: (f32, f32),
+    pub lifetime: (f32, f32),
+    pub size: (f32, f32),
+    pub start_color: (f32, f32, f32, f32),
+    pub end_color: (f32, f32, f32, f32),
+    /// Fraction of the velocity lost per second.
+    pub drag: f32,
+}
+
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ParticleEmitters {
+    pub emitters: HashMap<EmitterKind, EmitterDef>,
+}
+
+impl ParticleEmitters {
+    /// A missing or broken file is reported and leaves every emitter empty.
+    pub fn load() -> Self {
+        let path = Self::path();
+        fs::read_to_string(&path)
+            .map_err(|err| err.to_string())
+            .and_then(|content| ron::from_str(&content).map_err(|err| err.to_string()))
+            .unwrap_or_else(|err| {
+                error!("Could not load particle emitters {}: {err}", path.display());
+                Self::default()
+            })
+    }
+
+    pub fn path() -> PathBuf {
+        FileAssetReader::get_base_path().join(EMITTERS_FILE)
+    }
+}
+
+/// Pre-spawned particle entities, so bursts never spawn or despawn anything.
+#[derive(Resource, Default)]
+pub struct ParticlePool {
+    pub free: Vec<Entity>,
+}
@@ -0,0 +1,117 @@
+use super::{
+    components::{Particle, ParticleBurst},
+    resources::*,
+};
+use bevy::prelude::*;
+use rand::Rng;
+
+pub fn spawn_particle_pool(mut commands: Commands, mut pool: ResMut<ParticlePool>) {
+    pool.free.reserve(POOL_SIZE);
+    for _ in 0..POOL_SIZE {
+        let entity = commands
+            .spawn((
+                Particle::default(),
+                Sprite::default(),
+                Transform::default(),
+                Visibility::Hidden,
+            ))
+            .id();
+        pool.free.push(entity);
+    }
+}
+
+pub fn emit_particles(
+    mut commands: Commands,
+    burst_query: Query<(Entity, &ParticleBurst)>,
+    mut particle_query: Query<(&mut Particle, &mut Transform, &mut Visibility)>,
+    emitters: Res<ParticleEmitters>,
+    mut pool: ResMut<ParticlePool>,
+) {
+    let mut rng = rand::rng();
+
+    for (burst_entity, burst) in &burst_query {
+        commands.entity(burst_entity).despawn();
+        let Some(def) = emitters.emitters.get(&burst.kind) else {
+            continue;
+        };
+
+        let base_angle = burst
+            .direction
+            .map_or(0.0, |direction| direction.to_angle());
+        let half_spread = def.spread.to_radians() / 2.0;
+
+        for _ in 0..def.count {
+            let Some(entity) = pool.free.pop() else {
+                return;
+            };
+            let Ok((mut particle, mut transform, mut visibility)) = particle_query.get_mut(entity)
+            else {
+                continue;
+            };
+
+            let angle = base_angle + rng.random_range(-half_spread..=half_spread);
+            let speed = rng.random_range(def.speed.0..=def.speed.1);
+            *particle = Particle {
+                velocity: Vec2::from_angle(angle) * speed,
+                age: 0.0,
+                lifetime: rng.random_range(def.lifetime.0..=def.lifetime.1),
+                start_size: def.size.0,
+                end_size: def.size.1,
+                start_color: LinearRgba::from_f32_array(def.start_color.into()),
+                end_color: LinearRgba::from_f32_array(def.end_color.into()),
+                drag: def.drag,
+            };
+            transform.translation = burst.position.extend(PARTICLE_Z);
+            *visibility = Visibility::Visible;
+        }
+    }
+}
+
+pub fn update_particles(
+    mut particle_query: Query<(
+        Entity,
+        &mut Particle,
+        &mut Transform,
+        &mut Sprite,
+        &mut Visibility,
+    )>,
+    mut pool: ResMut<ParticlePool>,
+    time: Res<Time>,
+) {
+    let delta = time.delta_secs();
+
+    for (entity, mut particle, mut transform, mut sprite, mut visibility) in &mut particle_query {
+        if *visibility == Visibility::Hidden {
+            continue;
+        }
+
+        particle.age += delta;
+        if particle.age >= particle.lifetime {
+            *visibility = Visibility::Hidden;
+            pool.free.push(entity);
+            continue;
+        }
+
+        let slowdown = (1.0 - particle.drag * delta).max(0.0);
+        particle.velocity *= slowdown;
+        transform.translation += (particle.velocity * delta).extend(0.0);
+
+        let t = particle.age / particle.lifetime;
+        let size = particle.start_size.lerp(particle.end_size, t);
+        sprite.custom_size = Some(Vec2::splat(size));
+        sprite.color = particle.start_color.mix(&particle.end_color, t).into();
+    }
+}
+
+/// Hides every live particle, e.g. when a run ends.
+pub fn clear_particles(
+    mut particle_query: Query<(Entity, &mut Visibility), With<Particle>>,
+    mut pool: ResMut<ParticlePool>,
+) {
+    for (entity, mut visibility) in &mut particle_query {
+        if *visibility != Visibility::Hidden {
+            *visibility = Visibility::Hidden;
+            pool.free.push(entity);
+        }
+    }
+}
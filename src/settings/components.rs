@@ -0,0 +1,12 @@
+pub mod layout {
+    use crate::audio::resources::mixer::AudioBus;
+    use bevy::prelude::*;
+
+    #[derive(Component)]
+    pub struct SettingsMenu;
+
+    #[derive(Component)]
+    pub struct VolumeSlider {
+        pub bus: AudioBus,
+    }
+}
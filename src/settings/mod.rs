@@ -0,0 +1,16 @@
+use bevy::prelude::*;
+use plugins::*;
+
+mod components;
+mod plugins;
+pub mod resources;
+mod systems;
+
+/// Player preferences saved to `settings.ron`, and the screen to change them.
+pub struct SettingsPlugin;
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(SettingsLayoutPlugin)
+            .add_plugins(SettingsInteractionPlugin);
+    }
+}
@@ -0,0 +1,25 @@
+use super::{
+    resources::Settings,
+    systems::{interaction::*, layout::*},
+};
+use crate::AppState;
+use bevy::prelude::*;
+
+pub struct SettingsLayoutPlugin;
+impl Plugin for SettingsLayoutPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Settings::load())
+            .add_systems(OnEnter(AppState::Settings), spawn_settings_menu)
+            .add_systems(OnExit(AppState::Settings), despawn_settings_menu);
+    }
+}
+
+pub struct SettingsInteractionPlugin;
+impl Plugin for SettingsInteractionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            handle_settings_actions.run_if(in_state(AppState::Settings)),
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use bevy::{asset::io::file::FileAssetReader, prelude::*};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+
+pub const SETTINGS_FILE: &str = "settings.ron";
+pub const VOLUME_STEP: f32 = 0.1;
+
+/// Missing fields fall back to their defaults, so older files keep loading.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct Settings {
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub sfx_volume: f32,
+    pub ui_volume: f32,
+    /// Screen shake, zoom punches and hit-stop.
+    pub camera_effects: bool,
+    /// File name of the arena theme, without extension.
+    pub theme: String,
+    /// File name of the UI theme, without extension.
+    pub ui_theme: String,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            master_volume: 0.8,
+            music_volume: 0.6,
+            sfx_volume: 1.0,
+            ui_volume: 1.0,
+            camera_effects: true,
+            theme: String::new(),
+            ui_theme: String::new(),
+        }
+    }
+}
+
+impl Settings {
+    pub fn path() -> PathBuf {
+        FileAssetReader::get_base_path().join(SETTINGS_FILE)
+    }
+
+    pub fn load() -> Self {
+        let path = Self::path();
+        let Ok(content) = fs::read_to_string(&path) else {
+            return Self::default();
+        };
+
+        ron::from_str(&content).unwrap_or_else(|err| {
+            error!("Invalid settings file {}: {err}", path.display());
+            Self::default()
+        })
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let content = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|err| err.to_string())?;
+        fs::write(Self::path(), content).map_err(|err| err.to_string())
+    }
+}
+
+/// Volume moved by `step`, snapped to whole steps and kept between muted and full.
+pub fn step_volume(volume: f32, step: f32) -> f32 {
+    (((volume + step) / VOLUME_STEP).round() * VOLUME_STEP).clamp(0.0, 1.0)
+}
@@ -0,0 +1,152 @@
+pub mod layout {
+    use super::super::components::layout::*;
+    use super::super::resources::Settings;
+    use crate::audio::resources::mixer::AudioBus;
+    use crate::background::resources::{SelectedTheme, ThemeLibrary};
+    use crate::widgets::{
+        builders::*,
+        components::BackAction,
+        messages::ButtonAction,
+        resources::{SelectedUiTheme, UiThemeLibrary},
+    };
+    use bevy::prelude::*;
+
+    pub fn spawn_settings_menu(
+        mut commands: Commands,
+        settings: Res<Settings>,
+        theme_library: Res<ThemeLibrary>,
+        selected_theme: Res<SelectedTheme>,
+        ui_theme_library: Res<UiThemeLibrary>,
+        selected_ui_theme: Res<SelectedUiTheme>,
+    ) {
+        commands
+            .spawn((screen(SettingsMenu), BackAction(ButtonAction::MainMenu)))
+            .with_children(|parent| {
+                parent.spawn(title("Settings"));
+
+                for bus in AudioBus::ALL {
+                    parent.spawn((
+                        slider(
+                            bus.label(),
+                            bus.setting(&settings),
+                            ButtonAction::VolumeDown(bus),
+                            ButtonAction::VolumeUp(bus),
+                        ),
+                        VolumeSlider { bus },
+                    ));
+                }
+
+                parent.spawn(toggle(
+                    ButtonAction::ToggleCameraEffects,
+                    "Screen Effects",
+                    settings.camera_effects,
+                ));
+                parent.spawn(button(
+                    ButtonAction::CycleTheme,
+                    theme_text(&theme_library, &selected_theme),
+                ));
+                parent.spawn(button(
+                    ButtonAction::CycleUiTheme,
+                    ui_theme_text(&ui_theme_library, &selected_ui_theme),
+                ));
+                parent.spawn(button(ButtonAction::MainMenu, "Back"));
+            });
+    }
+
+    pub fn despawn_settings_menu(
+        mut commands: Commands,
+        menu_query: Query<Entity, With<SettingsMenu>>,
+    ) {
+        for entity in &menu_query {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    pub fn theme_text(theme_library: &ThemeLibrary, selected_theme: &SelectedTheme) -> String {
+        format!("< {} >", theme_library.get(selected_theme).data.name)
+    }
+
+    pub fn ui_theme_text(
+        ui_theme_library: &UiThemeLibrary,
+        selected_ui_theme: &SelectedUiTheme,
+    ) -> String {
+        format!("UI: {}", ui_theme_library.get(selected_ui_theme).data.name)
+    }
+}
+
+pub mod interaction {
+    use super::super::components::layout::*;
+    use super::super::resources::{Settings, VOLUME_STEP, step_volume};
+    use super::layout::{theme_text, ui_theme_text};
+    use crate::background::resources::{SelectedTheme, ThemeLibrary};
+    use crate::widgets::{
+        components::{ActionButton, ButtonLabel, Slider, Toggle},
+        messages::ButtonAction,
+        resources::{SelectedUiTheme, UiThemeLibrary},
+    };
+    use bevy::prelude::*;
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn handle_settings_actions(
+        mut action_reader: MessageReader<ButtonAction>,
+        mut slider_query: Query<(&mut Slider, &VolumeSlider)>,
+        mut toggle_query: Query<(&mut Toggle, &ActionButton)>,
+        mut label_query: Query<(&mut Text, &ButtonLabel)>,
+        theme_library: Res<ThemeLibrary>,
+        mut selected_theme: ResMut<SelectedTheme>,
+        ui_theme_library: Res<UiThemeLibrary>,
+        mut selected_ui_theme: ResMut<SelectedUiTheme>,
+        mut settings: ResMut<Settings>,
+    ) {
+        for action in action_reader.read() {
+            match *action {
+                ButtonAction::VolumeDown(bus) | ButtonAction::VolumeUp(bus) => {
+                    let step = match action {
+                        ButtonAction::VolumeUp(_) => VOLUME_STEP,
+                        _ => -VOLUME_STEP,
+                    };
+                    let volume = bus.setting_mut(&mut settings);
+                    *volume = step_volume(*volume, step);
+
+                    for (mut slider, volume_slider) in &mut slider_query {
+                        if volume_slider.bus == bus {
+                            slider.value = *volume;
+                        }
+                    }
+                }
+                ButtonAction::ToggleCameraEffects => {
+                    settings.camera_effects = !settings.camera_effects;
+
+                    for (mut toggle, button) in &mut toggle_query {
+                        if button.0 == ButtonAction::ToggleCameraEffects {
+                            toggle.on = settings.camera_effects;
+                        }
+                    }
+                }
+                ButtonAction::CycleTheme => {
+                    selected_theme.next(&theme_library);
+                    settings.theme = theme_library.get(&selected_theme).id.clone();
+                    ButtonLabel::relabel(
+                        &mut label_query,
+                        ButtonAction::CycleTheme,
+                        theme_text(&theme_library, &selected_theme),
+                    );
+                }
+                ButtonAction::CycleUiTheme => {
+                    selected_ui_theme.next(&ui_theme_library);
+                    settings.ui_theme = ui_theme_library.get(&selected_ui_theme).id.clone();
+                    ButtonLabel::relabel(
+                        &mut label_query,
+                        ButtonAction::CycleUiTheme,
+                        ui_theme_text(&ui_theme_library, &selected_ui_theme),
+                    );
+                }
+                _ => continue,
+            }
+
+            if let Err(err) = settings.save() {
+                error!("Could not save settings: {err}");
+            }
+        }
+    }
+}
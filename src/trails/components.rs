@@ -0,0 +1,17 @@
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+/// Recent positions of a ball, newest first. The speed is measured from them, so
+/// trails also work for balls mirrored from a remote simulation.
+#[derive(Component, Default)]
+pub struct Trail {
+    pub points: VecDeque<Vec2>,
+    pub speed: f32,
+}
+
+/// One of the dots a trail is drawn with, `index` counting back from the ball.
+#[derive(Component)]
+pub struct TrailDot {
+    pub owner: Entity,
+    pub index: usize,
+}
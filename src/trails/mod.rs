@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+use plugins::*;
+
+pub mod components;
+mod plugins;
+pub mod resources;
+mod systems;
+
+/// Fading trails behind the player and enemy balls.
+pub struct TrailsPlugin;
+impl Plugin for TrailsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(TrailPlugin);
+    }
+}
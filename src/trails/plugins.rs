@@ -0,0 +1,18 @@
+use super::systems::trail::*;
+use crate::game::states::SimulationState;
+use bevy::prelude::*;
+
+pub struct TrailPlugin;
+impl Plugin for TrailPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, create_trail_assets).add_systems(
+            Update,
+            (
+                attach_trails,
+                record_trails.run_if(not(in_state(SimulationState::GamePaused))),
+                draw_trails,
+            )
+                .chain(),
+        );
+    }
+}
@@ -0,0 +1,48 @@
+use bevy::prelude::*;
+
+pub const TRAIL_DOTS: usize = 12;
+pub const TRAIL_HISTORY: usize = 32;
+/// Seconds of movement a trail covers, so faster balls leave longer trails.
+pub const TRAIL_SECS: f32 = 0.12;
+pub const MAX_TRAIL_LENGTH: f32 = 160.0;
+/// A jump longer than this is a respawn or correction, not movement.
+pub const TELEPORT_DISTANCE: f32 = 200.0;
+pub const TRAIL_Z: f32 = -0.5;
+/// How quickly the measured speed follows the actual one, per second.
+pub const SPEED_SMOOTHING: f32 = 12.0;
+pub const DOT_TEXTURE_SIZE: u32 = 32;
+
+/// Look of a trail at its head; dots shrink and fade towards the tail.
+#[derive(Debug, Clone, Copy)]
+pub struct TrailStyle {
+    pub color: Color,
+    pub width: f32,
+    pub length_scale: f32,
+}
+
+pub const ENEMY_TRAIL: TrailStyle = TrailStyle {
+    color: Color::linear_rgba(1.0, 0.25, 0.2, 0.5),
+    width: 40.0,
+    length_scale: 1.0,
+};
+pub const PLAYER_TRAIL: TrailStyle = TrailStyle {
+    color: Color::linear_rgba(0.4, 0.6, 1.0, 0.5),
+    width: 40.0,
+    length_scale: 1.0,
+};
+pub const DASH_TRAIL: TrailStyle = TrailStyle {
+    color: Color::linear_rgba(0.5, 0.9, 1.0, 0.8),
+    width: 52.0,
+    length_scale: 1.6,
+};
+pub const INVULNERABLE_TRAIL: TrailStyle = TrailStyle {
+    color: Color::linear_rgba(1.0, 1.0, 1.0, 0.35),
+    width: 28.0,
+    length_scale: 0.6,
+};
+
+/// Soft round texture the trail dots are drawn with.
+#[derive(Resource)]
+pub struct TrailAssets {
+    pub dot: Handle<Image>,
+}
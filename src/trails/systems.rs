@@ -0,0 +1,145 @@
+pub mod trail {
+    use super::super::{components::*, resources::*};
+    use crate::game::components::{
+        enemy::Enemy,
+        player::{Boosted, Invulnerable, Player},
+    };
+    use bevy::{
+        asset::RenderAssetUsages,
+        prelude::*,
+        render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+    };
+
+    type NewBall = Or<(Added<Player>, Added<Enemy>)>;
+    type TrailOwner = (&'static Trail, Has<Player>, Has<Invulnerable>, Has<Boosted>);
+    type IsBall = Or<(With<Player>, With<Enemy>)>;
+
+    pub fn create_trail_assets(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+        let size = DOT_TEXTURE_SIZE;
+        let centre = (size as f32 - 1.0) / 2.0;
+        let data = (0..size * size)
+            .flat_map(|i| {
+                let offset = Vec2::new((i % size) as f32, (i / size) as f32) - centre;
+                let falloff = (1.0 - offset.length() / centre).clamp(0.0, 1.0);
+                [255, 255, 255, (falloff.sqrt() * 255.0) as u8]
+            })
+            .collect();
+
+        let image = Image::new(
+            Extent3d {
+                width: size,
+                height: size,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::RENDER_WORLD,
+        );
+
+        commands.insert_resource(TrailAssets {
+            dot: images.add(image),
+        });
+    }
+
+    pub fn attach_trails(
+        mut commands: Commands,
+        ball_query: Query<Entity, NewBall>,
+        trail_assets: Res<TrailAssets>,
+    ) {
+        for owner in &ball_query {
+            commands.entity(owner).insert(Trail::default());
+
+            for index in 0..TRAIL_DOTS {
+                commands.spawn((
+                    TrailDot { owner, index },
+                    Sprite::from_image(trail_assets.dot.clone()),
+                    Transform::from_xyz(0.0, 0.0, TRAIL_Z),
+                    Visibility::Hidden,
+                ));
+            }
+        }
+    }
+
+    pub fn record_trails(mut trail_query: Query<(&mut Trail, &Transform)>, time: Res<Time>) {
+        let delta = time.delta_secs();
+        if delta <= 0.0 {
+            return;
+        }
+
+        for (mut trail, transform) in &mut trail_query {
+            let position = transform.translation.truncate();
+            let moved = trail.points.front().map(|last| last.distance(position));
+            if moved.is_some_and(|moved| moved > TELEPORT_DISTANCE) {
+                trail.points.clear();
+                trail.speed = 0.0;
+            } else if let Some(moved) = moved {
+                let blend = (SPEED_SMOOTHING * delta).min(1.0);
+                trail.speed += (moved / delta - trail.speed) * blend;
+            }
+
+            trail.points.push_front(position);
+            trail.points.truncate(TRAIL_HISTORY);
+        }
+    }
+
+    /// Spreads each trail's dots over the part of its history that fits the trail
+    /// length.
+    pub fn draw_trails(
+        mut commands: Commands,
+        mut dot_query: Query<(
+            Entity,
+            &TrailDot,
+            &mut Transform,
+            &mut Sprite,
+            &mut Visibility,
+        )>,
+        owner_query: Query<TrailOwner, IsBall>,
+    ) {
+        for (entity, dot, mut transform, mut sprite, mut visibility) in &mut dot_query {
+            let Ok((trail, is_player, invulnerable, boosted)) = owner_query.get(dot.owner) else {
+                commands.entity(entity).despawn();
+                continue;
+            };
+
+            let speed = trail.speed;
+            let style = match (is_player, invulnerable, boosted) {
+                (false, _, _) => ENEMY_TRAIL,
+                (true, true, _) => INVULNERABLE_TRAIL,
+                (true, false, true) => DASH_TRAIL,
+                (true, false, false) => PLAYER_TRAIL,
+            };
+
+            let length = (speed * TRAIL_SECS * style.length_scale).min(MAX_TRAIL_LENGTH);
+            let t = (dot.index + 1) as f32 / TRAIL_DOTS as f32;
+            let Some(position) = point_along(trail, length * t) else {
+                *visibility = Visibility::Hidden;
+                continue;
+            };
+
+            let fade = 1.0 - t;
+            transform.translation = position.extend(TRAIL_Z);
+            sprite.custom_size = Some(Vec2::splat(style.width * (0.3 + 0.7 * fade)));
+            sprite.color = style.color.with_alpha(style.color.alpha() * fade);
+            *visibility = Visibility::Visible;
+        }
+    }
+
+    /// Position `distance` back along the trail, or `None` when the trail is shorter.
+    fn point_along(trail: &Trail, distance: f32) -> Option<Vec2> {
+        if distance <= 0.0 {
+            return None;
+        }
+
+        let mut travelled = 0.0;
+        for (newer, older) in trail.points.iter().zip(trail.points.iter().skip(1)) {
+            let step = newer.distance(*older);
+            if travelled + step >= distance {
+                return Some(newer.lerp(*older, (distance - travelled) / step));
+            }
+            travelled += step;
+        }
+
+        None
+    }
+}
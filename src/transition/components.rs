@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+/// The panel covering the screen while a transition runs.
+#[derive(Component)]
+pub struct TransitionOverlay;
@@ -0,0 +1,27 @@
+use crate::AppState;
+use bevy::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionEffect {
+    /// The screen fades to black and back.
+    Fade,
+    /// A panel slides in from the left and out to the right.
+    Slide,
+    /// A panel wipes down from the top and back up.
+    Wipe,
+}
+
+/// Asks for an animated switch to `to`, which may be the current state to restart
+/// it. Made while another transition runs, it waits for that one to finish; only the
+/// first such request is kept.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct TransitionRequest {
+    pub to: AppState,
+    pub effect: TransitionEffect,
+}
+
+impl TransitionRequest {
+    pub fn new(to: AppState, effect: TransitionEffect) -> Self {
+        Self { to, effect }
+    }
+}
@@ -0,0 +1,17 @@
+use bevy::prelude::*;
+use plugins::*;
+
+pub mod components;
+pub mod messages;
+mod plugins;
+pub mod resources;
+mod systems;
+
+/// Animated screen changes: a `TransitionRequest` covers the screen, switches the
+/// app state and uncovers the new screen.
+pub struct TransitionPlugin;
+impl Plugin for TransitionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(ScreenTransitionPlugin);
+    }
+}
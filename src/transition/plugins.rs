@@ -0,0 +1,14 @@
+use super::{messages::TransitionRequest, resources::ScreenTransition, systems::transition::*};
+use bevy::prelude::*;
+
+pub struct ScreenTransitionPlugin;
+impl Plugin for ScreenTransitionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ScreenTransition>()
+            .add_message::<TransitionRequest>()
+            // Requests are picked up before `Update`, so systems that check for a
+            // running transition see it on the frame after the request.
+            .add_systems(PreUpdate, start_transitions)
+            .add_systems(Update, (run_transition, draw_transition).chain());
+    }
+}
@@ -0,0 +1,42 @@
+use super::messages::{TransitionEffect, TransitionRequest};
+use crate::AppState;
+use bevy::prelude::*;
+
+pub const OUT_SECS: f32 = 0.3;
+pub const IN_SECS: f32 = 0.3;
+pub const OVERLAY_COLOR: Color = Color::linear_rgb(0.01, 0.01, 0.02);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionPhase {
+    /// Covering the old screen; the state switches when this ends.
+    Out,
+    /// Uncovering the new screen.
+    In,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ActiveTransition {
+    pub to: AppState,
+    pub effect: TransitionEffect,
+    pub phase: TransitionPhase,
+    pub elapsed: f32,
+}
+
+impl ActiveTransition {
+    /// How much of the screen is covered, from 0 to 1, eased.
+    pub fn coverage(&self) -> f32 {
+        let t = match self.phase {
+            TransitionPhase::Out => self.elapsed / OUT_SECS,
+            TransitionPhase::In => 1.0 - self.elapsed / IN_SECS,
+        }
+        .clamp(0.0, 1.0);
+        t * t * (3.0 - 2.0 * t)
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct ScreenTransition {
+    pub active: Option<ActiveTransition>,
+    /// First request for another state made while `active` ran, started after it.
+    pub queued: Option<TransitionRequest>,
+}
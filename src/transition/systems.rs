@@ -0,0 +1,119 @@
+pub mod transition {
+    use super::super::{
+        components::TransitionOverlay,
+        messages::{TransitionEffect, TransitionRequest},
+        resources::*,
+    };
+    use crate::AppState;
+    use bevy::prelude::*;
+
+    pub fn start_transitions(
+        mut commands: Commands,
+        mut request_reader: MessageReader<TransitionRequest>,
+        mut screen_transition: ResMut<ScreenTransition>,
+    ) {
+        for request in request_reader.read() {
+            match screen_transition.active {
+                Some(active) => {
+                    if active.to != request.to && screen_transition.queued.is_none() {
+                        screen_transition.queued = Some(*request);
+                    }
+                }
+                None => begin_transition(&mut commands, &mut screen_transition, request),
+            }
+        }
+
+        if screen_transition.active.is_none()
+            && let Some(request) = screen_transition.queued.take()
+        {
+            begin_transition(&mut commands, &mut screen_transition, &request);
+        }
+    }
+
+    fn begin_transition(
+        commands: &mut Commands,
+        screen_transition: &mut ScreenTransition,
+        request: &TransitionRequest,
+    ) {
+        screen_transition.active = Some(ActiveTransition {
+            to: request.to,
+            effect: request.effect,
+            phase: TransitionPhase::Out,
+            elapsed: 0.0,
+        });
+        commands.spawn((
+            TransitionOverlay,
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..Default::default()
+            },
+            BackgroundColor(OVERLAY_COLOR.with_alpha(0.0)),
+            GlobalZIndex(i32::MAX),
+        ));
+    }
+
+    /// Switches the state once the old screen is covered, and removes the overlay
+    /// once the new one is uncovered. Switching to the current state only runs its
+    /// `OnTransition` schedule; screens that can restart hook into that.
+    pub fn run_transition(
+        mut commands: Commands,
+        overlay_query: Query<Entity, With<TransitionOverlay>>,
+        mut screen_transition: ResMut<ScreenTransition>,
+        mut change_app_state: ResMut<NextState<AppState>>,
+        time: Res<Time<Real>>,
+    ) {
+        let Some(active) = &mut screen_transition.active else {
+            return;
+        };
+        active.elapsed += time.delta_secs();
+
+        match active.phase {
+            TransitionPhase::Out if active.elapsed >= OUT_SECS => {
+                change_app_state.set(active.to);
+                active.phase = TransitionPhase::In;
+                active.elapsed = 0.0;
+            }
+            TransitionPhase::In if active.elapsed >= IN_SECS => {
+                screen_transition.active = None;
+                for entity in &overlay_query {
+                    commands.entity(entity).despawn();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn draw_transition(
+        mut overlay_query: Query<(&mut Node, &mut BackgroundColor), With<TransitionOverlay>>,
+        screen_transition: Res<ScreenTransition>,
+    ) {
+        let Some(active) = &screen_transition.active else {
+            return;
+        };
+        let Ok((mut node, mut background)) = overlay_query.single_mut() else {
+            return;
+        };
+
+        let coverage = active.coverage();
+        match active.effect {
+            TransitionEffect::Fade => {
+                background.0 = OVERLAY_COLOR.with_alpha(coverage);
+            }
+            TransitionEffect::Slide => {
+                background.0 = OVERLAY_COLOR;
+                // Enters from the left and leaves to the right.
+                let offset = match active.phase {
+                    TransitionPhase::Out => coverage - 1.0,
+                    TransitionPhase::In => 1.0 - coverage,
+                };
+                node.left = Val::Percent(offset * 100.0);
+            }
+            TransitionEffect::Wipe => {
+                background.0 = OVERLAY_COLOR;
+                node.height = Val::Percent(coverage * 100.0);
+            }
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! Bundles for declaring menus. Each returns everything a widget needs, children
+//! included, so a screen is a tree of `spawn` calls. Colours, fonts and sizes come
+//! from the UI theme through the `Themed` role each part carries.
+
+use super::{components::*, messages::ButtonAction};
+use bevy::{ecs::spawn::SpawnIter, prelude::*};
+
+/// Full-screen column centring a menu. Add a `BackAction` to make Escape leave it.
+pub fn screen(marker: impl Component) -> impl Bundle {
+    (
+        marker,
+        Themed::Screen,
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+    )
+}
+
+/// Translucent box grouping widgets over the game.
+pub fn panel() -> impl Bundle {
+    (
+        Themed::Panel,
+        Node {
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+    )
+}
+
+pub fn row() -> impl Bundle {
+    (
+        Themed::Row,
+        Node {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+    )
+}
+
+fn text(text: impl Into<String>, role: Themed) -> impl Bundle {
+    (
+        role,
+        Text::new(text),
+        TextLayout::new_with_justify(Justify::Center),
+    )
+}
+
+pub fn title(title: impl Into<String>) -> impl Bundle {
+    (
+        Themed::Title,
+        Text::new(title),
+        TextLayout {
+            justify: Justify::Center,
+            linebreak: LineBreak::NoWrap,
+        },
+    )
+}
+
+pub fn label(label: impl Into<String>) -> impl Bundle {
+    text(label, Themed::Label)
+}
+
+pub fn caption(caption: impl Into<String>) -> impl Bundle {
+    text(caption, Themed::Caption)
+}
+
+/// Column of labels, one per item.
+pub fn list(items: Vec<String>) -> impl Bundle {
+    (
+        Themed::List,
+        Node {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        Children::spawn(SpawnIter(items.into_iter().map(label))),
+    )
+}
+
+fn sized_button(action: ButtonAction, label_text: impl Into<String>, size: Themed) -> impl Bundle {
+    (
+        ActionButton(action),
+        size,
+        Node {
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        Outline::new(Val::ZERO, Val::ZERO, Color::NONE),
+        children![(ButtonLabel(action), label(label_text))],
+    )
+}
+
+pub fn button(action: ButtonAction, label_text: impl Into<String>) -> impl Bundle {
+    sized_button(action, label_text, Themed::Button)
+}
+
+pub fn small_button(action: ButtonAction, label_text: impl Into<String>) -> impl Bundle {
+    sized_button(action, label_text, Themed::SmallButton)
+}
+
+pub fn toggle(action: ButtonAction, label_text: impl Into<String>, on: bool) -> impl Bundle {
+    let toggle = Toggle {
+        label: label_text.into(),
+        on,
+    };
+    (button(action, toggle.text()), toggle)
+}
+
+/// A labelled bar with `decrease` and `increase` buttons on either side.
+pub fn slider(
+    label_text: impl Into<String>,
+    value: f32,
+    decrease: ButtonAction,
+    increase: ButtonAction,
+) -> impl Bundle {
+    let slider = Slider {
+        label: label_text.into(),
+        value,
+    };
+    (
+        row(),
+        children![
+            (SliderLabel, text(slider.text(), Themed::SliderLabel)),
+            small_button(decrease, "-"),
+            (
+                Themed::SliderTrack,
+                Node::default(),
+                children![(
+                    SliderFill,
+                    Themed::SliderFill,
+                    Node {
+                        width: Val::Percent(value * 100.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                )],
+            ),
+            small_button(increase, "+"),
+        ],
+        slider,
+    )
+}
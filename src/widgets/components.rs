@@ -0,0 +1,92 @@
+use super::messages::ButtonAction;
+use bevy::prelude::*;
+
+/// A button that sends its action when pressed.
+#[derive(Component)]
+#[require(Button)]
+pub struct ActionButton(pub ButtonAction);
+
+/// The text inside an action button.
+#[derive(Component)]
+pub struct ButtonLabel(pub ButtonAction);
+
+impl ButtonLabel {
+    /// Replaces the text of every button sending `action`.
+    pub fn relabel(
+        label_query: &mut Query<(&mut Text, &ButtonLabel)>,
+        action: ButtonAction,
+        text: impl Into<String>,
+    ) {
+        let text = text.into();
+        for (mut label_text, label) in label_query {
+            if label.0 == action {
+                label_text.0.clone_from(&text);
+            }
+        }
+    }
+}
+
+/// An on/off button. Its label is redrawn whenever `on` changes.
+#[derive(Component)]
+pub struct Toggle {
+    pub label: String,
+    pub on: bool,
+}
+
+impl Toggle {
+    pub fn text(&self) -> String {
+        let state = if self.on { "On" } else { "Off" };
+        format!("{} {state}", self.label)
+    }
+}
+
+/// A bar showing `value` from 0 to 1, stepped by the buttons on either side.
+#[derive(Component)]
+pub struct Slider {
+    pub label: String,
+    pub value: f32,
+}
+
+impl Slider {
+    pub fn text(&self) -> String {
+        format!("{} {}%", self.label, (self.value * 100.0).round())
+    }
+}
+
+#[derive(Component)]
+pub struct SliderLabel;
+
+#[derive(Component)]
+pub struct SliderFill;
+
+/// The button that Enter or the gamepad's A button activates.
+#[derive(Component)]
+pub struct Focused;
+
+/// Sent by Escape or the gamepad's B button while the menu carrying it is open.
+#[derive(Component)]
+pub struct BackAction(pub ButtonAction);
+
+/// The part of the UI theme that styles this entity. Applied when it spawns and
+/// again whenever the theme changes.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Themed {
+    Title,
+    Label,
+    Caption,
+    Hint,
+    Error,
+    HudText,
+    Countdown,
+    SliderLabel,
+    Screen,
+    Panel,
+    Row,
+    List,
+    Button,
+    SmallButton,
+    SliderTrack,
+    SliderFill,
+    ProgressTrack,
+    HudBox,
+}
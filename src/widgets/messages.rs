@@ -0,0 +1,26 @@
+use crate::audio::resources::mixer::AudioBus;
+use bevy::prelude::*;
+
+/// What a pressed button asks for. Screens read the actions they own.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ButtonAction {
+    Play,
+    CycleLevel,
+    CycleMode,
+    CycleControls,
+    Online,
+    Settings,
+    Quit,
+    MainMenu,
+    VolumeDown(AudioBus),
+    VolumeUp(AudioBus),
+    ToggleCameraEffects,
+    CycleTheme,
+    CycleUiTheme,
+    Host,
+    Join,
+    Watch,
+    Start,
+    Resume,
+    Restart,
+}
@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+use plugins::*;
+
+pub mod builders;
+pub mod components;
+pub mod messages;
+mod plugins;
+pub mod resources;
+mod systems;
+
+/// Shared menu widgets. Buttons carry a `ButtonAction` that is sent as a message when
+/// pressed, by mouse or through keyboard and gamepad focus, so each screen only
+/// declares its layout and handles the actions it owns. Widgets and the HUD are
+/// styled by the UI theme picked in the settings.
+pub struct WidgetsPlugin;
+impl Plugin for WidgetsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(ButtonsPlugin)
+            .add_plugins(ControlsPlugin)
+            .add_plugins(FocusPlugin)
+            .add_plugins(UiThemePlugin);
+    }
+}
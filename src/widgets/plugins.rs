@@ -0,0 +1,60 @@
+use super::{
+    messages::ButtonAction,
+    resources::{FocusMemory, SelectedUiTheme, UiTheme, UiThemeLibrary},
+    systems::{buttons::*, controls::*, focus::*, theme::*},
+};
+use bevy::prelude::*;
+
+pub struct ButtonsPlugin;
+impl Plugin for ButtonsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<ButtonAction>().add_systems(
+            Update,
+            (interact_with_buttons, handle_common_actions).chain(),
+        );
+    }
+}
+
+pub struct ControlsPlugin;
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (draw_toggles, draw_sliders));
+    }
+}
+
+pub struct FocusPlugin;
+impl Plugin for FocusPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FocusMemory>().add_systems(
+            Update,
+            (
+                restore_focus,
+                focus_hovered_buttons,
+                navigate_focus,
+                activate_focus,
+                draw_focus_ring,
+            )
+                .chain(),
+        );
+    }
+}
+
+pub struct UiThemePlugin;
+impl Plugin for UiThemePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(UiThemeLibrary::load())
+            .init_resource::<SelectedUiTheme>()
+            .init_resource::<UiTheme>()
+            .add_systems(Startup, select_saved_ui_theme)
+            .add_systems(
+                Update,
+                (
+                    apply_ui_theme.run_if(
+                        resource_changed::<SelectedUiTheme>.or(on_message::<AssetEvent<Font>>),
+                    ),
+                    style_widgets,
+                )
+                    .chain(),
+            );
+    }
+}
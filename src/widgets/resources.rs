@@ -0,0 +1,275 @@
+use super::messages::ButtonAction;
+use crate::AppState;
+use bevy::{asset::io::file::FileAssetReader, prelude::*};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// How many recently focused buttons are remembered.
+pub const FOCUS_MEMORY_LEN: usize = 16;
+pub const UI_THEMES_DIR: &str = "ui_themes";
+pub const DEFAULT_FONT: &str = "fonts/FiraSans-Bold.ttf";
+
+/// Recently focused buttons with the screen they were on, newest last. A menu
+/// opening again focuses the newest one it contains, so returning to it lands where
+/// the player left. Keyed by screen, as several menus share actions like Main Menu.
+#[derive(Resource, Default)]
+pub struct FocusMemory {
+    pub recent: Vec<(AppState, ButtonAction)>,
+}
+
+impl FocusMemory {
+    pub fn remember(&mut self, screen: AppState, action: ButtonAction) {
+        self.recent.retain(|recent| *recent != (screen, action));
+        self.recent.push((screen, action));
+        if self.recent.len() > FOCUS_MEMORY_LEN {
+            self.recent.remove(0);
+        }
+    }
+
+    /// Actions focused on `screen`, newest first.
+    pub fn recent_on(&self, screen: AppState) -> impl Iterator<Item = ButtonAction> + '_ {
+        self.recent
+            .iter()
+            .rev()
+            .filter(move |(recent_screen, _)| *recent_screen == screen)
+            .map(|(_, action)| *action)
+    }
+}
+
+/// Linear RGBA, as written in theme files.
+pub type Rgba = (f32, f32, f32, f32);
+
+pub fn rgba(color: Rgba) -> Color {
+    Color::linear_rgba(color.0, color.1, color.2, color.3)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct Palette {
+    pub text: Rgba,
+    pub caption: Rgba,
+    pub button: Rgba,
+    pub button_hovered: Rgba,
+    pub button_pressed: Rgba,
+    pub panel: Rgba,
+    pub slider_track: Rgba,
+    pub slider_fill: Rgba,
+    pub focus_ring: Rgba,
+    pub error: Rgba,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self {
+            text: (1.0, 1.0, 1.0, 1.0),
+            caption: (0.7, 0.7, 0.7, 1.0),
+            button: (0.15, 0.15, 0.15, 1.0),
+            button_hovered: (0.25, 0.25, 0.25, 1.0),
+            button_pressed: (0.35, 0.75, 0.35, 1.0),
+            panel: (0.15, 0.15, 0.15, 0.5),
+            slider_track: (0.08, 0.08, 0.08, 1.0),
+            slider_fill: (0.35, 0.75, 0.35, 1.0),
+            focus_ring: (1.0, 1.0, 1.0, 1.0),
+            error: (0.85, 0.2, 0.2, 1.0),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct FontSizes {
+    pub title: f32,
+    pub label: f32,
+    pub caption: f32,
+    pub hint: f32,
+    pub hud: f32,
+    pub countdown: f32,
+}
+
+impl Default for FontSizes {
+    fn default() -> Self {
+        Self {
+            title: 64.0,
+            label: 32.0,
+            caption: 20.0,
+            hint: 14.0,
+            hud: 48.0,
+            countdown: 160.0,
+        }
+    }
+}
+
+/// Sizes and gaps in logical pixels.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct Spacing {
+    pub gap: f32,
+    pub list_gap: f32,
+    pub panel_padding: f32,
+    pub button_width: f32,
+    pub button_height: f32,
+    pub small_button_width: f32,
+    pub slider_label_width: f32,
+    pub slider_width: f32,
+    pub slider_height: f32,
+    pub focus_ring_width: f32,
+}
+
+impl Default for Spacing {
+    fn default() -> Self {
+        Self {
+            gap: 12.0,
+            list_gap: 4.0,
+            panel_padding: 64.0,
+            button_width: 280.0,
+            button_height: 64.0,
+            small_button_width: 64.0,
+            slider_label_width: 240.0,
+            slider_width: 200.0,
+            slider_height: 16.0,
+            focus_ring_width: 3.0,
+        }
+    }
+}
+
+/// Corner radii in logical pixels.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct Radii {
+    pub button: f32,
+    pub panel: f32,
+    pub slider: f32,
+}
+
+/// A UI theme as stored in `assets/ui_themes/*.ron`. Missing sections fall back to
+/// the default look.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct UiThemeData {
+    pub name: String,
+    /// Path of the font, relative to the assets folder.
+    pub font: String,
+    pub palette: Palette,
+    pub font_sizes: FontSizes,
+    pub spacing: Spacing,
+    pub radii: Radii,
+}
+
+impl Default for UiThemeData {
+    fn default() -> Self {
+        Self {
+            name: "Default".to_string(),
+            font: DEFAULT_FONT.to_string(),
+            palette: Palette::default(),
+            font_sizes: FontSizes::default(),
+            spacing: Spacing::default(),
+            radii: Radii::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UiThemeEntry {
+    /// File name without extension, as saved in the settings.
+    pub id: String,
+    pub data: UiThemeData,
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct UiThemeLibrary {
+    pub themes: Vec<UiThemeEntry>,
+}
+
+impl UiThemeLibrary {
+    /// Reads every `.ron` file of the UI themes folder, sorted by file name. Themes
+    /// that fail to parse are reported and skipped; the default look is used when
+    /// nothing could be loaded.
+    pub fn load() -> Self {
+        let dir = Self::dir();
+        let mut paths: Vec<PathBuf> = fs::read_dir(&dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                    .filter(|path| path.extension().is_some_and(|ext| ext == "ron"))
+                    .collect()
+            })
+            .unwrap_or_else(|err| {
+                error!("Could not read UI themes folder {}: {err}", dir.display());
+                Vec::new()
+            });
+        paths.sort();
+
+        let mut themes: Vec<UiThemeEntry> = paths
+            .into_iter()
+            .filter_map(|path| match Self::read_theme(&path) {
+                Ok(data) => {
+                    let id = path.file_stem()?.to_string_lossy().into_owned();
+                    Some(UiThemeEntry { id, data })
+                }
+                Err(err) => {
+                    error!("Invalid UI theme file {}: {err}", path.display());
+                    None
+                }
+            })
+            .collect();
+
+        if themes.is_empty() {
+            themes.push(UiThemeEntry {
+                id: "default".to_string(),
+                data: UiThemeData::default(),
+            });
+        }
+
+        Self { themes }
+    }
+
+    pub fn dir() -> PathBuf {
+        FileAssetReader::get_base_path().join(UI_THEMES_DIR)
+    }
+
+    pub fn read_theme(path: &Path) -> Result<UiThemeData, String> {
+        let content = fs::read_to_string(path).map_err(|err| err.to_string())?;
+        ron::from_str(&content).map_err(|err| err.to_string())
+    }
+
+    pub fn get(&self, selected: &SelectedUiTheme) -> &UiThemeEntry {
+        &self.themes[selected.index.min(self.themes.len() - 1)]
+    }
+
+    pub fn index_of(&self, id: &str) -> Option<usize> {
+        self.themes.iter().position(|theme| theme.id == id)
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct SelectedUiTheme {
+    pub index: usize,
+}
+
+impl SelectedUiTheme {
+    pub fn next(&mut self, library: &UiThemeLibrary) {
+        self.index = (self.index + 1) % library.themes.len();
+    }
+}
+
+/// The theme every widget and the HUD are styled with. `font` is the theme's own
+/// once loaded, and a stand-in until then.
+#[derive(Resource, Default, PartialEq)]
+pub struct UiTheme {
+    pub data: UiThemeData,
+    pub font: Handle<Font>,
+}
+
+impl UiTheme {
+    pub fn button_color(&self, interaction: Interaction) -> Color {
+        let palette = &self.data.palette;
+        rgba(match interaction {
+            Interaction::Pressed => palette.button_pressed,
+            Interaction::Hovered => palette.button_hovered,
+            Interaction::None => palette.button,
+        })
+    }
+}
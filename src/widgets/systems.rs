@@ -0,0 +1,449 @@
+pub mod buttons {
+    use super::super::{components::ActionButton, messages::ButtonAction, resources::UiTheme};
+    use crate::AppState;
+    use crate::transition::messages::{TransitionEffect, TransitionRequest};
+    use bevy::prelude::*;
+
+    /// Hover and press styling for every action button, sending its action on press.
+    pub fn interact_with_buttons(
+        mut button_query: Query<
+            (&Interaction, &mut BackgroundColor, &ActionButton),
+            Changed<Interaction>,
+        >,
+        mut action_writer: MessageWriter<ButtonAction>,
+        ui_theme: Res<UiTheme>,
+    ) {
+        for (interaction, mut background, button) in &mut button_query {
+            background.0 = ui_theme.button_color(*interaction);
+            if *interaction == Interaction::Pressed {
+                action_writer.write(button.0);
+            }
+        }
+    }
+
+    /// Actions that mean the same on every screen.
+    pub fn handle_common_actions(
+        mut commands: Commands,
+        mut action_reader: MessageReader<ButtonAction>,
+        mut transition_writer: MessageWriter<TransitionRequest>,
+    ) {
+        for action in action_reader.read() {
+            match action {
+                ButtonAction::Quit => {
+                    commands.write_message(AppExit::Success);
+                }
+                ButtonAction::MainMenu => {
+                    transition_writer.write(TransitionRequest::new(
+                        AppState::MainMenu,
+                        TransitionEffect::Slide,
+                    ));
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+pub mod controls {
+    use super::super::components::{ButtonLabel, Slider, SliderFill, SliderLabel, Toggle};
+    use bevy::prelude::*;
+
+    pub fn draw_toggles(
+        toggle_query: Query<(&Toggle, &Children), Changed<Toggle>>,
+        mut label_query: Query<&mut Text, With<ButtonLabel>>,
+    ) {
+        for (toggle, children) in &toggle_query {
+            for child in children {
+                if let Ok(mut text) = label_query.get_mut(*child) {
+                    text.0 = toggle.text();
+                }
+            }
+        }
+    }
+
+    pub fn draw_sliders(
+        slider_query: Query<(Entity, &Slider), Changed<Slider>>,
+        children_query: Query<&Children>,
+        mut fill_query: Query<&mut Node, With<SliderFill>>,
+        mut label_query: Query<&mut Text, With<SliderLabel>>,
+    ) {
+        for (entity, slider) in &slider_query {
+            for descendant in children_query.iter_descendants(entity) {
+                if let Ok(mut node) = fill_query.get_mut(descendant) {
+                    node.width = Val::Percent(slider.value * 100.0);
+                }
+                if let Ok(mut text) = label_query.get_mut(descendant) {
+                    text.0 = slider.text();
+                }
+            }
+        }
+    }
+}
+
+pub mod focus {
+    use super::super::{
+        components::{ActionButton, BackAction, Focused},
+        messages::ButtonAction,
+        resources::{FocusMemory, UiTheme, rgba},
+    };
+    use crate::AppState;
+    use crate::audio::{components::mixer::SoundEffect, resources::mixer::SoundKind};
+    use bevy::prelude::*;
+
+    /// Action buttons in the order they were declared, walking the UI tree depth first.
+    fn layout_order(
+        root_query: &Query<Entity, (With<Node>, Without<ChildOf>)>,
+        children_query: &Query<&Children>,
+        button_query: &Query<(Entity, &ActionButton, &UiGlobalTransform)>,
+    ) -> Vec<Entity> {
+        root_query
+            .iter()
+            .flat_map(|root| std::iter::once(root).chain(children_query.iter_descendants(root)))
+            .filter(|entity| button_query.contains(*entity))
+            .collect()
+    }
+
+    fn move_focus(
+        commands: &mut Commands,
+        focused_query: &Query<Entity, With<Focused>>,
+        target: Entity,
+        action: ButtonAction,
+        screen: AppState,
+        focus_memory: &mut FocusMemory,
+    ) {
+        for entity in focused_query {
+            commands.entity(entity).remove::<Focused>();
+        }
+        commands.entity(target).insert(Focused);
+        focus_memory.remember(screen, action);
+    }
+
+    /// Nearest button from `from` in `direction`, favouring ones straight ahead.
+    fn nearest_in_direction(
+        button_query: &Query<(Entity, &ActionButton, &UiGlobalTransform)>,
+        from: Entity,
+        direction: Vec2,
+    ) -> Option<Entity> {
+        let (_, _, from_transform) = button_query.get(from).ok()?;
+        let origin = from_transform.translation;
+
+        button_query
+            .iter()
+            .filter(|(entity, ..)| *entity != from)
+            .filter_map(|(entity, _, transform)| {
+                let offset = transform.translation - origin;
+                let along = offset.dot(direction);
+                let across = offset.perp_dot(direction).abs();
+                (along > 1.0).then_some((entity, along + across * 2.0))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(entity, _)| entity)
+    }
+
+    /// Focuses a button when a menu opens: the most recently focused one it has,
+    /// otherwise its first.
+    pub fn restore_focus(
+        mut commands: Commands,
+        button_query: Query<(Entity, &ActionButton, &UiGlobalTransform)>,
+        focused_query: Query<Entity, With<Focused>>,
+        root_query: Query<Entity, (With<Node>, Without<ChildOf>)>,
+        children_query: Query<&Children>,
+        app_state: Res<State<AppState>>,
+        focus_memory: Res<FocusMemory>,
+    ) {
+        if button_query.is_empty() || !focused_query.is_empty() {
+            return;
+        }
+
+        let order = layout_order(&root_query, &children_query, &button_query);
+        let remembered = focus_memory.recent_on(*app_state.get()).find_map(|action| {
+            order.iter().copied().find(|entity| {
+                button_query
+                    .get(*entity)
+                    .is_ok_and(|(_, b, _)| b.0 == action)
+            })
+        });
+
+        if let Some(entity) = remembered.or(order.first().copied()) {
+            commands.entity(entity).insert(Focused);
+        }
+    }
+
+    /// Arrows and the D-pad move to the nearest button that way, Tab walks the layout
+    /// order.
+    #[allow(clippy::too_many_arguments)]
+    pub fn navigate_focus(
+        mut commands: Commands,
+        button_query: Query<(Entity, &ActionButton, &UiGlobalTransform)>,
+        focused_query: Query<Entity, With<Focused>>,
+        root_query: Query<Entity, (With<Node>, Without<ChildOf>)>,
+        children_query: Query<&Children>,
+        keyboard_input: Res<ButtonInput<KeyCode>>,
+        gamepad_query: Query<&Gamepad>,
+        app_state: Res<State<AppState>>,
+        mut focus_memory: ResMut<FocusMemory>,
+    ) {
+        let Ok(focused) = focused_query.single() else {
+            return;
+        };
+
+        let pressed = |key: KeyCode, button: GamepadButton| {
+            keyboard_input.just_pressed(key)
+                || gamepad_query
+                    .iter()
+                    .any(|gamepad| gamepad.just_pressed(button))
+        };
+
+        let target = if keyboard_input.just_pressed(KeyCode::Tab) {
+            let order = layout_order(&root_query, &children_query, &button_query);
+            let index = order.iter().position(|entity| *entity == focused);
+            let backwards = keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+            index.map(|index| {
+                let step = if backwards { order.len() - 1 } else { 1 };
+                order[(index + step) % order.len()]
+            })
+        } else {
+            // UI coordinates grow downwards.
+            [
+                (KeyCode::ArrowUp, GamepadButton::DPadUp, Vec2::NEG_Y),
+                (KeyCode::ArrowDown, GamepadButton::DPadDown, Vec2::Y),
+                (KeyCode::ArrowLeft, GamepadButton::DPadLeft, Vec2::NEG_X),
+                (KeyCode::ArrowRight, GamepadButton::DPadRight, Vec2::X),
+            ]
+            .into_iter()
+            .find(|(key, button, _)| pressed(*key, *button))
+            .and_then(|(_, _, direction)| nearest_in_direction(&button_query, focused, direction))
+        };
+
+        if let Some(target) = target
+            && let Ok((_, button, _)) = button_query.get(target)
+        {
+            move_focus(
+                &mut commands,
+                &focused_query,
+                target,
+                button.0,
+                *app_state.get(),
+                &mut focus_memory,
+            );
+        }
+    }
+
+    /// Hovering a button with the mouse focuses it, so only one button looks selected.
+    pub fn focus_hovered_buttons(
+        mut commands: Commands,
+        button_query: Query<(Entity, &Interaction, &ActionButton), Changed<Interaction>>,
+        focused_query: Query<Entity, With<Focused>>,
+        app_state: Res<State<AppState>>,
+        mut focus_memory: ResMut<FocusMemory>,
+    ) {
+        for (entity, interaction, button) in &button_query {
+            if *interaction == Interaction::Hovered && !focused_query.contains(entity) {
+                move_focus(
+                    &mut commands,
+                    &focused_query,
+                    entity,
+                    button.0,
+                    *app_state.get(),
+                    &mut focus_memory,
+                );
+            }
+        }
+    }
+
+    /// Enter or A presses the focused button, Escape or B sends the menu's back action.
+    pub fn activate_focus(
+        mut commands: Commands,
+        focused_query: Query<&ActionButton, With<Focused>>,
+        back_query: Query<&BackAction>,
+        keyboard_input: Res<ButtonInput<KeyCode>>,
+        gamepad_query: Query<&Gamepad>,
+        mut action_writer: MessageWriter<ButtonAction>,
+    ) {
+        let pressed = |keys: &[KeyCode], button: GamepadButton| {
+            keyboard_input.any_just_pressed(keys.iter().copied())
+                || gamepad_query
+                    .iter()
+                    .any(|gamepad| gamepad.just_pressed(button))
+        };
+
+        let action = if pressed(
+            &[KeyCode::Enter, KeyCode::NumpadEnter],
+            GamepadButton::South,
+        ) {
+            focused_query.single().ok().map(|button| button.0)
+        } else if pressed(&[KeyCode::Escape], GamepadButton::East) {
+            back_query.single().ok().map(|back| back.0)
+        } else {
+            None
+        };
+
+        if let Some(action) = action {
+            commands.spawn(SoundEffect::new(SoundKind::ButtonClick));
+            action_writer.write(action);
+        }
+    }
+
+    pub fn draw_focus_ring(
+        mut button_query: Query<(&mut Outline, Has<Focused>), With<ActionButton>>,
+        ui_theme: Res<UiTheme>,
+    ) {
+        let ring_color = rgba(ui_theme.data.palette.focus_ring);
+        for (mut outline, focused) in &mut button_query {
+            let color = if focused { ring_color } else { Color::NONE };
+            if outline.color != color {
+                outline.color = color;
+            }
+        }
+    }
+}
+
+pub mod theme {
+    use super::super::{
+        components::Themed,
+        resources::{SelectedUiTheme, UiTheme, UiThemeLibrary, rgba},
+    };
+    use crate::{loading::resources::GameAssets, settings::resources::Settings};
+    use bevy::prelude::*;
+
+    type ThemedWidget = (
+        Ref<'static, Themed>,
+        &'static mut Node,
+        &'static mut BackgroundColor,
+        &'static mut BorderRadius,
+        Option<&'static mut TextFont>,
+        Option<&'static mut TextColor>,
+        Option<&'static mut Outline>,
+    );
+
+    pub fn select_saved_ui_theme(
+        settings: Res<Settings>,
+        theme_library: Res<UiThemeLibrary>,
+        mut selected_theme: ResMut<SelectedUiTheme>,
+    ) {
+        if let Some(index) = theme_library.index_of(&settings.ui_theme) {
+            selected_theme.index = index;
+        }
+    }
+
+    /// Applies the selected theme, and again as fonts finish loading. Until the
+    /// theme's font is ready, text uses the game's bold font, or Bevy's built-in one
+    /// while that is still loading too.
+    pub fn apply_ui_theme(
+        theme_library: Res<UiThemeLibrary>,
+        selected_theme: Res<SelectedUiTheme>,
+        asset_server: Res<AssetServer>,
+        game_assets: Option<Res<GameAssets>>,
+        mut ui_theme: ResMut<UiTheme>,
+    ) {
+        let data = theme_library.get(&selected_theme).data.clone();
+        let font = [
+            Some(asset_server.load(data.font.clone())),
+            game_assets.map(|assets| assets.fonts.bold.clone()),
+        ]
+        .into_iter()
+        .flatten()
+        .find(|font| asset_server.is_loaded_with_dependencies(font))
+        .unwrap_or_default();
+
+        let name = data.name.clone();
+        if ui_theme.set_if_neq(UiTheme { data, font }) {
+            info!("Applying UI theme \"{name}\"");
+        }
+    }
+
+    /// Styles newly spawned widgets, and every widget when the theme changes.
+    pub fn style_widgets(mut themed_query: Query<ThemedWidget>, ui_theme: Res<UiTheme>) {
+        let theme = &ui_theme.data;
+        let (palette, spacing, radii) = (&theme.palette, &theme.spacing, &theme.radii);
+
+        for (themed, mut node, mut background, mut radius, font, color, outline) in
+            &mut themed_query
+        {
+            if !ui_theme.is_changed() && !themed.is_added() {
+                continue;
+            }
+
+            let text_style = match *themed {
+                Themed::Title => Some((theme.font_sizes.title, palette.text)),
+                Themed::Label | Themed::SliderLabel => Some((theme.font_sizes.label, palette.text)),
+                Themed::Caption => Some((theme.font_sizes.caption, palette.caption)),
+                Themed::Hint => Some((theme.font_sizes.hint, palette.caption)),
+                Themed::Error => Some((theme.font_sizes.caption, palette.error)),
+                Themed::HudText => Some((theme.font_sizes.hud, palette.text)),
+                Themed::Countdown => Some((theme.font_sizes.countdown, palette.text)),
+                _ => None,
+            };
+            if let Some((font_size, text_color)) = text_style {
+                if let Some(mut font) = font {
+                    font.font = ui_theme.font.clone();
+                    font.font_size = font_size;
+                }
+                if let Some(mut color) = color {
+                    color.0 = rgba(text_color);
+                }
+            }
+
+            match *themed {
+                Themed::SliderLabel => {
+                    node.width = Val::Px(spacing.slider_label_width);
+                }
+                Themed::Screen => {
+                    node.row_gap = Val::Px(spacing.gap);
+                }
+                Themed::Panel => {
+                    node.padding = UiRect::all(Val::Px(spacing.panel_padding));
+                    node.row_gap = Val::Px(spacing.gap);
+                    background.0 = rgba(palette.panel);
+                    *radius = BorderRadius::all(Val::Px(radii.panel));
+                }
+                Themed::HudBox => {
+                    background.0 = rgba(palette.panel);
+                    *radius = BorderRadius::all(Val::Px(radii.panel));
+                }
+                Themed::Row => {
+                    node.column_gap = Val::Px(spacing.gap);
+                }
+                Themed::List => {
+                    node.row_gap = Val::Px(spacing.list_gap);
+                }
+                Themed::Button | Themed::SmallButton => {
+                    let width = if *themed == Themed::Button {
+                        spacing.button_width
+                    } else {
+                        spacing.small_button_width
+                    };
+                    node.width = Val::Px(width);
+                    node.height = Val::Px(spacing.button_height);
+                    background.0 = rgba(palette.button);
+                    *radius = BorderRadius::all(Val::Px(radii.button));
+                    if let Some(mut outline) = outline {
+                        outline.width = Val::Px(spacing.focus_ring_width);
+                        outline.offset = Val::Px(spacing.focus_ring_width);
+                    }
+                }
+                Themed::SliderTrack => {
+                    node.width = Val::Px(spacing.slider_width);
+                    node.height = Val::Px(spacing.slider_height);
+                    background.0 = rgba(palette.slider_track);
+                    *radius = BorderRadius::all(Val::Px(radii.slider));
+                }
+                Themed::SliderFill => {
+                    background.0 = rgba(palette.slider_fill);
+                    *radius = BorderRadius::all(Val::Px(radii.slider));
+                }
+                Themed::ProgressTrack => {
+                    background.0 = rgba(palette.slider_track);
+                    *radius = BorderRadius::all(Val::Px(radii.slider));
+                }
+                Themed::Title
+                | Themed::Label
+                | Themed::Caption
+                | Themed::Hint
+                | Themed::Error
+                | Themed::HudText
+                | Themed::Countdown => {}
+            }
+        }
+    }
+}